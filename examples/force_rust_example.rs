@@ -61,7 +61,9 @@ fn simple_force_rust() -> Result<(), AssemblyError> {
 
     // Verify it's actually using Rust
     match processor.selected_strategy() {
-        OptimizationStrategy::RustOptimized | OptimizationStrategy::RustStandard => {
+        OptimizationStrategy::RustSimd
+        | OptimizationStrategy::RustOptimized
+        | OptimizationStrategy::RustStandard => {
             println!("  ✅ Confirmed: Using Rust-only processing");
         }
         _ => {
@@ -158,7 +160,9 @@ fn prefer_rust_example() -> Result<(), AssemblyError> {
 
     // Check what was actually selected
     match processor.selected_strategy() {
-        OptimizationStrategy::RustOptimized | OptimizationStrategy::RustStandard => {
+        OptimizationStrategy::RustSimd
+        | OptimizationStrategy::RustOptimized
+        | OptimizationStrategy::RustStandard => {
             println!("  ✅ Rust preference honored");
         }
         _ => {
@@ -212,7 +216,9 @@ fn verify_rust_only() -> Result<(), AssemblyError> {
     // Verify it's definitely Rust
     assert!(matches!(
         strategy,
-        OptimizationStrategy::RustOptimized | OptimizationStrategy::RustStandard
+        OptimizationStrategy::RustSimd
+            | OptimizationStrategy::RustOptimized
+            | OptimizationStrategy::RustStandard
     ));
     assert_eq!(*preference, OptimizationPreference::ForceRustOnly);
 
@@ -299,7 +305,9 @@ fn production_deployment_example() -> Result<(), AssemblyError> {
     let strategy = processor.selected_strategy();
     assert!(matches!(
         strategy,
-        OptimizationStrategy::RustOptimized | OptimizationStrategy::RustStandard
+        OptimizationStrategy::RustSimd
+            | OptimizationStrategy::RustOptimized
+            | OptimizationStrategy::RustStandard
     ));
 
     println!("  ✅ Production deployment verified - Rust-only processing active");
@@ -319,7 +327,9 @@ mod tests {
         let processor1 = ProcessorBuilder::new().force_rust_only().build()?;
         assert!(matches!(
             processor1.selected_strategy(),
-            OptimizationStrategy::RustOptimized | OptimizationStrategy::RustStandard
+            OptimizationStrategy::RustSimd
+                | OptimizationStrategy::RustOptimized
+                | OptimizationStrategy::RustStandard
         ));
 
         // Method 2: with_strategy(RustOptimized)
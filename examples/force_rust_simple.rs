@@ -59,7 +59,9 @@ mod tests {
         let strategy = processor.selected_strategy();
         assert!(matches!(
             strategy,
-            OptimizationStrategy::RustOptimized | OptimizationStrategy::RustStandard
+            OptimizationStrategy::RustSimd
+                | OptimizationStrategy::RustOptimized
+                | OptimizationStrategy::RustStandard
         ));
 
         // Verify preference
@@ -1,28 +1,26 @@
 use rustyline::DefaultEditor;
+use vi::composer::Composer;
 
 // A REPL for testing transformation result.
 fn main() {
     let method = "telex";
     let mut rl = DefaultEditor::new().unwrap();
 
+    let definition = if method == "telex" {
+        &vi::TELEX
+    } else {
+        &vi::VNI
+    };
+
     loop {
         let Ok(input) = rl.readline("(input): ") else {
             break;
         };
 
-        let mut result = String::new();
-
-        for word in input.split_whitespace() {
-            let definition = if method == "telex" {
-                &vi::TELEX
-            } else {
-                &vi::VNI
-            };
-
-            vi::transform_buffer(definition, word.chars(), &mut result);
-            result.push(' ');
-        }
+        let mut composer = Composer::new(definition);
+        composer.push_str(&input);
+        composer.commit();
 
-        println!("(output): {result}");
+        println!("(output): {}", composer.committed());
     }
 }
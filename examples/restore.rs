@@ -0,0 +1,32 @@
+//! Trains a diacritic restoration model and restores the diacritics of text.
+//!
+//! ```text
+//! cargo run --example restore -- train corpus.txt model.bin
+//! cargo run --example restore -- restore model.bin "tieng viet rat hay"
+//! ```
+use std::error::Error;
+
+use vi::restoration::{restore_diacritics, NgramModel};
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.as_slice() {
+        [command, corpus, model] if command == "train" => {
+            let trained = NgramModel::train_file(corpus)?;
+            trained.save(model)?;
+            println!("Trained {} syllables into {model}", trained.len());
+        }
+        [command, model, text @ ..] if command == "restore" => {
+            let model = NgramModel::load(model)?;
+            let restoration = restore_diacritics(&text.join(" "), &model);
+            println!("{}", restoration.text);
+            for token in &restoration.tokens {
+                println!("  {} ({:.2})", token.restored, token.confidence);
+            }
+        }
+        _ => {
+            eprintln!("Usage: restore train <corpus> <model> | restore restore <model> <text>...");
+        }
+    }
+    Ok(())
+}
@@ -68,7 +68,9 @@ fn demo_force_rust_only() -> Result<(), AssemblyError> {
     // Verify it's actually using Rust
     let selected_strategy = processor.selected_strategy();
     match selected_strategy {
-        OptimizationStrategy::RustOptimized | OptimizationStrategy::RustStandard => {
+        OptimizationStrategy::RustSimd
+        | OptimizationStrategy::RustOptimized
+        | OptimizationStrategy::RustStandard => {
             println!("  ✅ Confirmed: Using Rust-only processing");
         }
         _ => {
@@ -199,7 +201,9 @@ mod tests {
         let strategy = processor.selected_strategy();
         assert!(matches!(
             strategy,
-            OptimizationStrategy::RustOptimized | OptimizationStrategy::RustStandard
+            OptimizationStrategy::RustSimd
+                | OptimizationStrategy::RustOptimized
+                | OptimizationStrategy::RustStandard
         ));
 
         Ok(())
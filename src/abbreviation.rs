@@ -0,0 +1,368 @@
+//! Abbreviations (gõ tắt) that are expanded at the end of a word.
+//!
+//! An [`AbbreviationTable`] maps short words such as `vn` to their expansion, `Việt Nam`. When a table is given
+//! to an [`IncrementalBuffer`](crate::methods::IncrementalBuffer) with
+//! [`with_abbreviations`](crate::methods::IncrementalBuffer::with_abbreviations), the keystrokes of a word are
+//! looked up when the word is finished, and the expansion follows the capitalization of what was typed:
+//!
+//! - `vn` -> `Việt Nam`, lowercase abbreviations are expanded as written
+//! - `Ko` -> `Không`, the first letter of the expansion is capitalized
+//! - `VN` -> `VIỆT NAM`, the whole expansion is in uppercase
+//!
+//! ## File format
+//!
+//! A table can be loaded from a file where every line maps an abbreviation to its expansion:
+//!
+//! ```text
+//! # Comments start with `#` or `;`.
+//! vn = Việt Nam
+//! ko = không
+//! # Quote an expansion to keep its surrounding spaces.
+//! dc = " được "
+//! ```
+//!
+//! ## Example
+//!
+//! ```
+//! use vi::{abbreviation::AbbreviationTable, methods::IncrementalBuffer};
+//!
+//! let table: AbbreviationTable = "vn = Việt Nam".parse()?;
+//!
+//! let mut buffer = IncrementalBuffer::new(&vi::TELEX).with_abbreviations(&table);
+//! buffer.push('V');
+//! buffer.push('n');
+//! assert!(buffer.finish().abbreviation_expanded);
+//! assert_eq!(buffer.view(), "Việt Nam");
+//! # Ok::<(), vi::abbreviation::AbbreviationError>(())
+//! ```
+use std::{collections::HashMap, fmt::Display, path::Path, str::FromStr};
+
+use crate::keymap::unescape;
+
+/// An error that happened while loading an abbreviation table.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum AbbreviationError {
+    /// The abbreviation file cannot be read.
+    Io(std::io::Error),
+    /// A line is not in the `abbreviation = expansion` form.
+    InvalidLine {
+        /// The line number, starting from 1.
+        line: usize,
+    },
+    /// An abbreviation is empty or contains a whitespace.
+    InvalidAbbreviation {
+        /// The line number, starting from 1.
+        line: usize,
+        /// The abbreviation as written in the file.
+        abbreviation: String,
+    },
+    /// An abbreviation is defined more than once.
+    DuplicateAbbreviation {
+        /// The line number, starting from 1.
+        line: usize,
+        /// The duplicated abbreviation.
+        abbreviation: String,
+    },
+}
+
+impl Display for AbbreviationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AbbreviationError::Io(error) => write!(f, "Cannot read abbreviations: {error}"),
+            AbbreviationError::InvalidLine { line } => {
+                write!(f, "Line {line}: expected `abbreviation = expansion`")
+            }
+            AbbreviationError::InvalidAbbreviation { line, abbreviation } => {
+                write!(
+                    f,
+                    "Line {line}: abbreviation `{abbreviation}` must be a single word"
+                )
+            }
+            AbbreviationError::DuplicateAbbreviation { line, abbreviation } => {
+                write!(
+                    f,
+                    "Line {line}: abbreviation `{abbreviation}` is already defined"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for AbbreviationError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AbbreviationError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for AbbreviationError {
+    fn from(error: std::io::Error) -> Self {
+        AbbreviationError::Io(error)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Entry {
+    expansion: String,
+    enabled: bool,
+}
+
+/// A table of abbreviations and their expansions.
+///
+/// Abbreviations are case-insensitive and stored in lowercase. Every abbreviation can be disabled without
+/// removing it from the table.
+///
+/// # Examples
+///
+/// ```
+/// use vi::abbreviation::AbbreviationTable;
+///
+/// let mut table = AbbreviationTable::new();
+/// table.insert("ko", "không");
+///
+/// assert_eq!(table.expand("ko").as_deref(), Some("không"));
+/// assert_eq!(table.expand("KO").as_deref(), Some("KHÔNG"));
+///
+/// table.set_enabled("ko", false);
+/// assert_eq!(table.expand("ko"), None);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AbbreviationTable {
+    entries: HashMap<String, Entry>,
+}
+
+impl AbbreviationTable {
+    /// Creates an empty table.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an enabled abbreviation, returning the expansion previously associated with it.
+    pub fn insert(&mut self, abbreviation: &str, expansion: &str) -> Option<String> {
+        self.entries
+            .insert(
+                abbreviation.to_lowercase(),
+                Entry {
+                    expansion: expansion.to_owned(),
+                    enabled: true,
+                },
+            )
+            .map(|entry| entry.expansion)
+    }
+
+    /// Removes an abbreviation, returning its expansion.
+    pub fn remove(&mut self, abbreviation: &str) -> Option<String> {
+        self.entries
+            .remove(&abbreviation.to_lowercase())
+            .map(|entry| entry.expansion)
+    }
+
+    /// Returns the expansion of an abbreviation as written in the table, whether it's enabled or not.
+    #[must_use]
+    pub fn get(&self, abbreviation: &str) -> Option<&str> {
+        self.entries
+            .get(&abbreviation.to_lowercase())
+            .map(|entry| entry.expansion.as_str())
+    }
+
+    /// Enables or disables an abbreviation. Returns `false` if the abbreviation is not in the table.
+    pub fn set_enabled(&mut self, abbreviation: &str, enabled: bool) -> bool {
+        self.entries
+            .get_mut(&abbreviation.to_lowercase())
+            .map(|entry| entry.enabled = enabled)
+            .is_some()
+    }
+
+    /// Indicates whether an abbreviation is in the table and enabled.
+    #[must_use]
+    pub fn is_enabled(&self, abbreviation: &str) -> bool {
+        self.entries
+            .get(&abbreviation.to_lowercase())
+            .is_some_and(|entry| entry.enabled)
+    }
+
+    /// The number of abbreviations in the table, including the disabled ones.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Indicates whether the table has no abbreviation.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Expands a typed word if it's an enabled abbreviation, following the capitalization of the word.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vi::abbreviation::AbbreviationTable;
+    ///
+    /// let mut table = AbbreviationTable::new();
+    /// table.insert("vn", "Việt Nam");
+    ///
+    /// assert_eq!(table.expand("vn").as_deref(), Some("Việt Nam"));
+    /// assert_eq!(table.expand("Vn").as_deref(), Some("Việt Nam"));
+    /// assert_eq!(table.expand("VN").as_deref(), Some("VIỆT NAM"));
+    /// assert_eq!(table.expand("vnd"), None);
+    /// ```
+    #[must_use]
+    pub fn expand(&self, word: &str) -> Option<String> {
+        let entry = self
+            .entries
+            .get(&word.to_lowercase())
+            .filter(|entry| entry.enabled)?;
+        Some(match_case(word, &entry.expansion))
+    }
+
+    /// Loads a table from a file in the format described in the [module documentation](self).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AbbreviationError::Io`] if the file cannot be read, or any other [`AbbreviationError`] with
+    /// the line number if the content is invalid.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, AbbreviationError> {
+        std::fs::read_to_string(path)?.parse()
+    }
+}
+
+impl FromStr for AbbreviationTable {
+    type Err = AbbreviationError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut table = Self::new();
+        for (index, raw_line) in input.lines().enumerate() {
+            let line = index + 1;
+            let raw_line = raw_line.trim();
+            if raw_line.is_empty() || raw_line.starts_with('#') || raw_line.starts_with(';') {
+                continue;
+            }
+
+            let (abbreviation, expansion) = raw_line
+                .split_once('=')
+                .ok_or(AbbreviationError::InvalidLine { line })?;
+            let abbreviation = abbreviation.trim();
+            if abbreviation.is_empty() || abbreviation.contains(char::is_whitespace) {
+                return Err(AbbreviationError::InvalidAbbreviation {
+                    line,
+                    abbreviation: abbreviation.to_owned(),
+                });
+            }
+
+            let expansion = expansion.trim();
+            let expansion = expansion
+                .strip_prefix('"')
+                .and_then(|expansion| expansion.strip_suffix('"'))
+                .map_or_else(|| expansion.to_owned(), unescape);
+            if expansion.is_empty() {
+                return Err(AbbreviationError::InvalidLine { line });
+            }
+
+            if table.get(abbreviation).is_some() {
+                return Err(AbbreviationError::DuplicateAbbreviation {
+                    line,
+                    abbreviation: abbreviation.to_owned(),
+                });
+            }
+            table.insert(abbreviation, &expansion);
+        }
+        Ok(table)
+    }
+}
+
+/// Applies the capitalization of a typed word to its expansion.
+pub(crate) fn match_case(word: &str, expansion: &str) -> String {
+    let mut letters = word.chars().filter(|ch| ch.is_alphabetic()).peekable();
+    if !letters.peek().is_some_and(|ch| ch.is_uppercase()) {
+        return expansion.to_owned();
+    }
+
+    let (count, all_uppercase) = letters.fold((0, true), |(count, all_uppercase), ch| {
+        (count + 1, all_uppercase && ch.is_uppercase())
+    });
+    if count > 1 && all_uppercase {
+        return expansion.to_uppercase();
+    }
+
+    let mut chars = expansion.chars();
+    chars.next().map_or_else(String::new, |first| {
+        first.to_uppercase().chain(chars).collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_abbreviations() {
+        let table: AbbreviationTable = r#"
+            # Common abbreviations
+            vn = Việt Nam
+            KO = không
+            ; Quoted expansions keep their spaces
+            dc = " được "
+            eq = a = b
+        "#
+        .parse()
+        .unwrap();
+
+        assert_eq!(table.len(), 4);
+        assert_eq!(table.get("vn"), Some("Việt Nam"));
+        assert_eq!(table.get("ko"), Some("không"));
+        assert_eq!(table.get("dc"), Some(" được "));
+        assert_eq!(table.get("eq"), Some("a = b"));
+    }
+
+    #[test]
+    fn parse_abbreviations_errors() {
+        assert!(matches!(
+            "vn".parse::<AbbreviationTable>(),
+            Err(AbbreviationError::InvalidLine { line: 1 })
+        ));
+        assert!(matches!(
+            "vn =".parse::<AbbreviationTable>(),
+            Err(AbbreviationError::InvalidLine { line: 1 })
+        ));
+        assert!(matches!(
+            "v n = Việt Nam".parse::<AbbreviationTable>(),
+            Err(AbbreviationError::InvalidAbbreviation { line: 1, .. })
+        ));
+        assert!(matches!(
+            "vn = Việt Nam\nVN = Việt Nam".parse::<AbbreviationTable>(),
+            Err(AbbreviationError::DuplicateAbbreviation { line: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn expand_matches_case() {
+        assert_eq!(match_case("ko", "không"), "không");
+        assert_eq!(match_case("Ko", "không"), "Không");
+        assert_eq!(match_case("KO", "không"), "KHÔNG");
+        assert_eq!(match_case("K", "không"), "Không");
+        assert_eq!(match_case("kO", "không"), "không");
+        assert_eq!(match_case("Vn", "Việt Nam"), "Việt Nam");
+        assert_eq!(match_case("VN", "Việt Nam"), "VIỆT NAM");
+        assert_eq!(match_case("Dd", "đường"), "Đường");
+    }
+
+    #[test]
+    fn disabled_abbreviations_are_not_expanded() {
+        let mut table = AbbreviationTable::new();
+        assert!(!table.set_enabled("vn", false));
+
+        table.insert("vn", "Việt Nam");
+        assert!(table.set_enabled("VN", false));
+        assert!(!table.is_enabled("vn"));
+        assert_eq!(table.expand("vn"), None);
+
+        assert!(table.set_enabled("vn", true));
+        assert_eq!(table.expand("vn").as_deref(), Some("Việt Nam"));
+    }
+}
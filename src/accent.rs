@@ -0,0 +1,241 @@
+//! Converting existing text between accent styles.
+//!
+//! [`AccentStyle`] decides where the tone mark of a syllable goes, e.g. `hòa` in the old style and `hoà` in the
+//! new style. While typing, the style is chosen with
+//! [`IncrementalBuffer::new_with_style`](crate::methods::IncrementalBuffer::new_with_style). This module deals
+//! with text that was already written: [`convert_accent_style`] re-places the tone marks of every syllable in
+//! a chosen style, and [`detect_accent_style`] reports the style of every syllable to find documents that mix
+//! both styles.
+//!
+//! Only the syllables whose tone mark placement differs between the two styles are affected, such as `hoà`,
+//! `thuý` or `khoẻ`. A syllable like `việt` is written the same way in both styles.
+//!
+//! ## Example
+//!
+//! ```
+//! use vi::{accent::{convert_accent_style, detect_accent_style}, processor::AccentStyle};
+//!
+//! let text = "Hoà bình và hòa hợp";
+//! assert!(detect_accent_style(text).is_mixed());
+//!
+//! assert_eq!(convert_accent_style(text, &AccentStyle::Old), "Hòa bình và hòa hợp");
+//! assert_eq!(convert_accent_style(text, &AccentStyle::New), "Hoà bình và hoà hợp");
+//! ```
+use crate::{parsing::extract_tone, processor::AccentStyle, syllable::Syllable, util::word_ranges};
+
+/// A syllable of a text whose tone mark placement tells its accent style.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StyledSyllable {
+    /// The byte index of the first character of the syllable in the text.
+    pub start: usize,
+    /// The byte index after the last character of the syllable in the text.
+    pub end: usize,
+    /// The accent style the syllable is written in.
+    pub style: AccentStyle,
+}
+
+/// The accent styles found in a text by [`detect_accent_style`].
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct AccentStyleReport {
+    /// The syllables whose tone mark placement differs between the two styles, in the order of the text.
+    pub syllables: Vec<StyledSyllable>,
+}
+
+impl AccentStyleReport {
+    /// The number of syllables written in the old style.
+    #[must_use]
+    pub fn old_count(&self) -> usize {
+        self.count(&AccentStyle::Old)
+    }
+
+    /// The number of syllables written in the new style.
+    #[must_use]
+    pub fn new_count(&self) -> usize {
+        self.count(&AccentStyle::New)
+    }
+
+    /// Indicates whether the text has syllables written in both styles.
+    #[must_use]
+    pub fn is_mixed(&self) -> bool {
+        self.old_count() > 0 && self.new_count() > 0
+    }
+
+    /// Returns the accent style of the text, or `None` if it's mixed or no syllable tells the style.
+    #[must_use]
+    pub fn style(&self) -> Option<AccentStyle> {
+        match (self.old_count(), self.new_count()) {
+            (0, 0) => None,
+            (_, 0) => Some(AccentStyle::Old),
+            (0, _) => Some(AccentStyle::New),
+            _ => None,
+        }
+    }
+
+    fn count(&self, style: &AccentStyle) -> usize {
+        self.syllables
+            .iter()
+            .filter(|syllable| syllable.style == *style)
+            .count()
+    }
+}
+
+/// Re-places the tone marks of every syllable of a text in the given accent style.
+///
+/// Words that are not written in either style, such as words that are not Vietnamese, are kept as is.
+///
+/// # Examples
+///
+/// ```
+/// use vi::{accent::convert_accent_style, processor::AccentStyle};
+///
+/// assert_eq!(convert_accent_style("Thuý khoẻ", &AccentStyle::Old), "Thúy khỏe");
+/// assert_eq!(convert_accent_style("Thúy khỏe", &AccentStyle::New), "Thuý khoẻ");
+/// ```
+#[must_use]
+pub fn convert_accent_style(text: &str, accent_style: &AccentStyle) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut last_end = 0;
+    for (start, end) in word_ranges(text) {
+        if let Some((style, syllable)) = syllable_style(&text[start..end]) {
+            if style != *accent_style {
+                result.push_str(&text[last_end..start]);
+                result.push_str(&render(&syllable, accent_style));
+                last_end = end;
+            }
+        }
+    }
+    result.push_str(&text[last_end..]);
+    result
+}
+
+/// Finds the accent style of every syllable of a text that is written differently in the two styles.
+///
+/// # Examples
+///
+/// ```
+/// use vi::{accent::detect_accent_style, processor::AccentStyle};
+///
+/// let report = detect_accent_style("Hòa bình, thúy");
+/// assert_eq!(report.old_count(), 2);
+/// assert_eq!(report.style(), Some(AccentStyle::Old));
+/// assert_eq!(report.syllables[1].start, 12);
+/// ```
+#[must_use]
+pub fn detect_accent_style(text: &str) -> AccentStyleReport {
+    AccentStyleReport {
+        syllables: word_ranges(text)
+            .filter_map(|(start, end)| {
+                syllable_style(&text[start..end]).map(|(style, _)| StyledSyllable {
+                    start,
+                    end,
+                    style,
+                })
+            })
+            .collect(),
+    }
+}
+
+/// Returns the accent style a word is written in, together with the parsed syllable, if the tone mark
+/// placement differs between the two styles.
+fn syllable_style(word: &str) -> Option<(AccentStyle, Syllable)> {
+    extract_tone(word)?;
+
+    let mut syllable = Syllable::default();
+    syllable.set(word.to_owned());
+
+    let old = render(&syllable, &AccentStyle::Old);
+    let new = render(&syllable, &AccentStyle::New);
+    if old == new {
+        return None;
+    }
+    if word == old {
+        Some((AccentStyle::Old, syllable))
+    } else if word == new {
+        Some((AccentStyle::New, syllable))
+    } else {
+        None
+    }
+}
+
+/// Checks whether the tone mark of a word is placed where either accent style would place it. Words without a
+/// tone mark are always well placed.
+pub(crate) fn is_tone_well_placed(word: &str) -> bool {
+    if extract_tone(word).is_none() {
+        return true;
+    }
+    let mut syllable = Syllable::default();
+    syllable.set(word.to_owned());
+    [AccentStyle::New, AccentStyle::Old]
+        .iter()
+        .any(|accent_style| render(&syllable, accent_style) == word)
+}
+
+fn render(syllable: &Syllable, accent_style: &AccentStyle) -> String {
+    let mut syllable = syllable.clone();
+    syllable.accent_style = accent_style.clone();
+    syllable.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn convert_syllables() {
+        let pairs = [
+            ("hòa", "hoà"),
+            ("Hòa", "Hoà"),
+            ("HÒA", "HOÀ"),
+            ("thúy", "thuý"),
+            ("khỏe", "khoẻ"),
+            ("lòe", "loè"),
+            ("hóa", "hoá"),
+            ("thủy", "thuỷ"),
+        ];
+        for (old, new) in pairs {
+            assert_eq!(convert_accent_style(old, &AccentStyle::New), new);
+            assert_eq!(convert_accent_style(new, &AccentStyle::Old), old);
+            assert_eq!(convert_accent_style(old, &AccentStyle::Old), old);
+            assert_eq!(convert_accent_style(new, &AccentStyle::New), new);
+        }
+    }
+
+    #[test]
+    fn convert_keeps_other_words() {
+        let text = "Việt Nam, hòa bình! (coffee) 123 hoàng";
+        assert_eq!(
+            convert_accent_style(text, &AccentStyle::New),
+            "Việt Nam, hoà bình! (coffee) 123 hoàng"
+        );
+    }
+
+    #[test]
+    fn detect_mixed_styles() {
+        let report = detect_accent_style("hoà và hòa, việt");
+        assert!(report.is_mixed());
+        assert_eq!(report.style(), None);
+        assert_eq!(
+            report.syllables,
+            vec![
+                StyledSyllable {
+                    start: 0,
+                    end: 4,
+                    style: AccentStyle::New,
+                },
+                StyledSyllable {
+                    start: 9,
+                    end: 13,
+                    style: AccentStyle::Old,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn detect_without_styled_syllables() {
+        let report = detect_accent_style("việt nam");
+        assert!(report.syllables.is_empty());
+        assert!(!report.is_mixed());
+        assert_eq!(report.style(), None);
+    }
+}
@@ -82,6 +82,39 @@ vietnamese_specific_1ea0_arm64:
     .long 0x55, 0x75, 0x59, 0x79, 0x59, 0x79, 0x59, 0x79  // 1EF0-1EF7: Ự,ự,Ỳ,ỳ,Ỵ,ỵ,Ỷ,ỷ -> U,u,Y,y,Y,y,Y,y
     .long 0x59, 0x79                                        // 1EF8-1EF9: Ỹ,ỹ -> Y,y
 
+// Byte-level UTF-8 lookup tables: the ASCII base letter of a code point, or 0 if it's unchanged
+// U+00C0-U+01BF, encoded as 0xC3 0x80 - 0xC6 0xBF
+.p2align 6
+vietnamese_utf8_2byte_arm64:
+    .byte 0x41, 0x41, 0x41, 0x41, 0x00, 0x00, 0x00, 0x00, 0x45, 0x45, 0x45, 0x00, 0x49, 0x49, 0x00, 0x00  // 00C0-00CF: ÀÁÂÃÄÅÆÇÈÉÊËÌÍÎÏ
+    .byte 0x00, 0x00, 0x4F, 0x4F, 0x4F, 0x4F, 0x00, 0x00, 0x00, 0x55, 0x55, 0x00, 0x00, 0x59, 0x00, 0x00  // 00D0-00DF: ÐÑÒÓÔÕÖ×ØÙÚÛÜÝÞß
+    .byte 0x61, 0x61, 0x61, 0x61, 0x00, 0x00, 0x00, 0x00, 0x65, 0x65, 0x65, 0x00, 0x69, 0x69, 0x00, 0x00  // 00E0-00EF: àáâãäåæçèéêëìíîï
+    .byte 0x00, 0x00, 0x6F, 0x6F, 0x6F, 0x6F, 0x00, 0x00, 0x00, 0x75, 0x75, 0x00, 0x00, 0x79, 0x00, 0x00  // 00F0-00FF: ðñòóôõö÷øùúûüýþÿ
+    .byte 0x00, 0x00, 0x41, 0x61, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00  // 0100-010F: ĀāĂăĄąĆćĈĉĊċČčĎď
+    .byte 0x44, 0x64, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00  // 0110-011F: ĐđĒēĔĕĖėĘęĚěĜĝĞğ
+    .byte 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x49, 0x69, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00  // 0120-012F: ĠġĢģĤĥĦħĨĩĪīĬĭĮį
+    .byte 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00  // 0130-013F: İıĲĳĴĵĶķĸĹĺĻļĽľĿ
+    .byte 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00  // 0140-014F: ŀŁłŃńŅņŇňŉŊŋŌōŎŏ
+    .byte 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00  // 0150-015F: ŐőŒœŔŕŖŗŘřŚśŜŝŞş
+    .byte 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x55, 0x75, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00  // 0160-016F: ŠšŢţŤťŦŧŨũŪūŬŭŮů
+    .byte 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00  // 0170-017F: ŰűŲųŴŵŶŷŸŹźŻżŽžſ
+    .byte 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00  // 0180-018F: ƀƁƂƃƄƅƆƇƈƉƊƋƌƍƎƏ
+    .byte 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00  // 0190-019F: ƐƑƒƓƔƕƖƗƘƙƚƛƜƝƞƟ
+    .byte 0x4F, 0x6F, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x55  // 01A0-01AF: ƠơƢƣƤƥƦƧƨƩƪƫƬƭƮƯ
+    .byte 0x75, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00  // 01B0-01BF: ưƱƲƳƴƵƶƷƸƹƺƻƼƽƾƿ
+
+// U+1E80-U+1EFF, encoded as 0xE1 0xBA 0x80 - 0xE1 0xBB 0xBF
+.p2align 6
+vietnamese_utf8_3byte_arm64:
+    .byte 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00  // 1E80-1E8F: ẀẁẂẃẄẅẆẇẈẉẊẋẌẍẎẏ
+    .byte 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00  // 1E90-1E9F: ẐẑẒẓẔẕẖẗẘẙẚẛẜẝẞẟ
+    .byte 0x41, 0x61, 0x41, 0x61, 0x41, 0x61, 0x41, 0x61, 0x41, 0x61, 0x41, 0x61, 0x41, 0x61, 0x41, 0x61  // 1EA0-1EAF: ẠạẢảẤấẦầẨẩẪẫẬậẮắ
+    .byte 0x41, 0x61, 0x41, 0x61, 0x41, 0x61, 0x41, 0x61, 0x45, 0x65, 0x45, 0x65, 0x45, 0x65, 0x45, 0x65  // 1EB0-1EBF: ẰằẲẳẴẵẶặẸẹẺẻẼẽẾế
+    .byte 0x45, 0x65, 0x45, 0x65, 0x45, 0x65, 0x45, 0x65, 0x49, 0x69, 0x49, 0x69, 0x4F, 0x6F, 0x4F, 0x6F  // 1EC0-1ECF: ỀềỂểỄễỆệỈỉỊịỌọỎỏ
+    .byte 0x4F, 0x6F, 0x4F, 0x6F, 0x4F, 0x6F, 0x4F, 0x6F, 0x4F, 0x6F, 0x4F, 0x6F, 0x4F, 0x6F, 0x4F, 0x6F  // 1ED0-1EDF: ỐốỒồỔổỖỗỘộỚớỜờỞở
+    .byte 0x4F, 0x6F, 0x4F, 0x6F, 0x55, 0x75, 0x55, 0x75, 0x55, 0x75, 0x55, 0x75, 0x55, 0x75, 0x55, 0x75  // 1EE0-1EEF: ỠỡỢợỤụỦủỨứỪừỬửỮữ
+    .byte 0x55, 0x75, 0x59, 0x79, 0x59, 0x79, 0x59, 0x79, 0x59, 0x79, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00  // 1EF0-1EFF: ỰựỲỳỴỵỶỷỸỹỺỻỼỽỾỿ
+
 
 
 .section __TEXT,__text,regular,pure_instructions
@@ -409,11 +442,140 @@ Lsafe_cancelled:
     ldp x29, x30, [sp], #16
     ret
 
+// Byte-level UTF-8 cleaning with safety checks
+// Cleans valid UTF-8 text in place: runs of ASCII bytes are skipped 16 bytes at a time with NEON, and the 2- and
+// 3-byte sequences of Vietnamese letters are rewritten as their ASCII base letter. The cleaned text is never longer
+// than the input, so it's written over the bytes already read.
+// Input: x0 = buffer pointer, x1 = length in bytes, x2 = control pointer
+// Output: x0 = length of the cleaned text (the text cleaned so far if cancelled)
+.global _hybrid_clean_utf8_bulk_safe
+.p2align 2
+_hybrid_clean_utf8_bulk_safe:
+    // Leaf function: only uses caller-saved registers
+    mov x9, #0   // read index
+    mov x10, #0  // write index
+    mov x11, #0  // iteration counter
+    adrp x12, vietnamese_utf8_2byte_arm64@PAGE
+    add x12, x12, vietnamese_utf8_2byte_arm64@PAGEOFF
+    adrp x13, vietnamese_utf8_3byte_arm64@PAGE
+    add x13, x13, vietnamese_utf8_3byte_arm64@PAGEOFF
+
+Lutf8_loop_arm64:
+    // Check the cancel flag (first byte of AssemblyControl) every 1024 iterations
+    add x11, x11, #1
+    tst x11, #0x3FF
+    b.ne Lutf8_block_arm64
+    ldrb w14, [x2]
+    cbnz w14, Lutf8_done_arm64
+
+Lutf8_block_arm64:
+    // Process 16 bytes at once while they are all ASCII
+    sub x14, x1, x9
+    cmp x14, #16
+    b.lo Lutf8_scalar_arm64
+    ldr q0, [x0, x9]
+    umaxv b1, v0.16b
+    umov w14, v1.b[0]
+    tbnz w14, #7, Lutf8_ascii_prefix_arm64
+
+    // Store the block back, which moves it down if the text already shrank
+    str q0, [x0, x10]
+    add x9, x9, #16
+    add x10, x10, #16
+    b Lutf8_loop_arm64
+
+Lutf8_ascii_prefix_arm64:
+    // Copy the ASCII bytes before the first non-ASCII byte of the block
+    ldrb w14, [x0, x9]
+    tbnz w14, #7, Lutf8_scalar_arm64
+    strb w14, [x0, x10]
+    add x9, x9, #1
+    add x10, x10, #1
+    b Lutf8_ascii_prefix_arm64
+
+Lutf8_scalar_arm64:
+    cmp x9, x1
+    b.hs Lutf8_done_arm64
+
+    // Dispatch on the leading byte of the sequence
+    ldrb w14, [x0, x9]
+    cmp w14, #0x80
+    b.lo Lutf8_copy_1_arm64
+    cmp w14, #0xE0
+    b.lo Lutf8_two_bytes_arm64
+    cmp w14, #0xE1
+    b.ne Lutf8_other_sequence_arm64
+
+    // 3-byte sequence 0xE1 0xBA/0xBB xx: U+1E80-U+1EFF, index = ((b1 & 1) << 6) | (b2 & 0x3F)
+    add x15, x0, x9
+    ldrb w16, [x15, #1]
+    and w17, w16, #0xFE
+    cmp w17, #0xBA
+    b.ne Lutf8_copy_3_arm64
+    ldrb w17, [x15, #2]
+    and w16, w16, #1
+    and w17, w17, #0x3F
+    orr w16, w17, w16, lsl #6
+    ldrb w16, [x13, w16, uxtw]
+    cbz w16, Lutf8_copy_3_arm64
+    strb w16, [x0, x10]
+    add x9, x9, #3
+    add x10, x10, #1
+    b Lutf8_loop_arm64
+
+Lutf8_two_bytes_arm64:
+    // 2-byte sequence 0xC3-0xC6 xx: U+00C0-U+01BF, index = ((b0 - 0xC3) << 6) | (b1 & 0x3F)
+    cmp w14, #0xC3
+    b.lo Lutf8_copy_2_arm64
+    cmp w14, #0xC6
+    b.hi Lutf8_copy_2_arm64
+    add x15, x0, x9
+    ldrb w16, [x15, #1]
+    sub w14, w14, #0xC3
+    and w16, w16, #0x3F
+    orr w16, w16, w14, lsl #6
+    ldrb w16, [x12, w16, uxtw]
+    cbz w16, Lutf8_copy_2_arm64
+    strb w16, [x0, x10]
+    add x9, x9, #2
+    add x10, x10, #1
+    b Lutf8_loop_arm64
+
+Lutf8_other_sequence_arm64:
+    cmp w14, #0xF0
+    b.hs Lutf8_copy_4_arm64
+Lutf8_copy_3_arm64:
+    mov w15, #3
+    b Lutf8_copy_arm64
+Lutf8_copy_4_arm64:
+    mov w15, #4
+    b Lutf8_copy_arm64
+Lutf8_copy_2_arm64:
+    mov w15, #2
+    b Lutf8_copy_arm64
+Lutf8_copy_1_arm64:
+    mov w15, #1
+
+Lutf8_copy_arm64:
+    // Copy a sequence that isn't changed
+    ldrb w14, [x0, x9]
+    strb w14, [x0, x10]
+    add x9, x9, #1
+    add x10, x10, #1
+    subs w15, w15, #1
+    b.ne Lutf8_copy_arm64
+    b Lutf8_loop_arm64
+
+Lutf8_done_arm64:
+    mov x0, x10  // Return the length of the cleaned text
+    ret
+
 // Export symbols for C linkage
 .global _hybrid_clean_char_aarch64
 .global _hybrid_clean_char_neon
 .global _hybrid_clean_chars_bulk_neon
 .global _hybrid_clean_chars_bulk_safe
+.global _hybrid_clean_utf8_bulk_safe
 
 // Compatibility aliases for cross-platform function calls
 // These allow x86_64 function names to work on ARM64
@@ -427,6 +589,7 @@ Lsafe_cancelled:
 .global __hybrid_clean_char_x86_64
 .global __hybrid_clean_chars_bulk_neon
 .global __hybrid_clean_chars_bulk_safe
+.global __hybrid_clean_utf8_bulk_safe
 
 // Compatibility function implementations
 _hybrid_clean_char_x86_64:
@@ -461,3 +624,7 @@ __hybrid_clean_chars_bulk_neon:
 __hybrid_clean_chars_bulk_safe:
     // Delegate to the safe implementation
     b _hybrid_clean_chars_bulk_safe
+
+__hybrid_clean_utf8_bulk_safe:
+    // Delegate to the UTF-8 implementation
+    b _hybrid_clean_utf8_bulk_safe
@@ -4,7 +4,9 @@
 //! for Vietnamese character processing. It handles platform detection, function
 //! selection, and safe calling conventions.
 
+use crate::normalize::compose;
 use crate::safety::{AssemblyControl, AssemblyError};
+use std::borrow::Cow;
 
 // Platform-specific assembly function declarations
 // These functions are compiled and linked by build.rs
@@ -93,6 +95,32 @@ extern "C" {
     fn _hybrid_clean_chars_bulk_neon(input: *const u32, output: *mut u32, len: usize) -> usize;
 }
 
+// UTF-8 byte-level functions, provided by the kernels of the target architecture
+#[cfg(any(
+    all(target_arch = "x86_64", feature = "x86_64_assembly"),
+    all(target_arch = "aarch64", feature = "aarch64_assembly")
+))]
+extern "C" {
+    /// Clean UTF-8 text in place, returning the length of the cleaned text
+    ///
+    /// # Safety
+    /// - `bytes` must point to `len` writable bytes of valid UTF-8
+    /// - The cleaned text is written over the input and is never longer than it
+    #[cfg(target_arch = "x86_64")]
+    fn hybrid_clean_utf8_bulk_safe(
+        bytes: *mut u8,
+        len: usize,
+        control: *const AssemblyControl,
+    ) -> usize;
+
+    #[cfg(target_arch = "aarch64")]
+    fn _hybrid_clean_utf8_bulk_safe(
+        bytes: *mut u8,
+        len: usize,
+        control: *const AssemblyControl,
+    ) -> usize;
+}
+
 /// Assembly platform detection and selection
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum AssemblyPlatform {
@@ -336,7 +364,7 @@ impl AssemblyInterface {
                             input.as_ptr(),
                             output.as_mut_ptr(),
                             input.len(),
-                            control as *const AssemblyControl,
+                            std::ptr::from_ref(control),
                         )
                     };
 
@@ -346,7 +374,7 @@ impl AssemblyInterface {
                             input.as_ptr(),
                             output.as_mut_ptr(),
                             input.len(),
-                            control as *const AssemblyControl,
+                            std::ptr::from_ref(control),
                         )
                     };
 
@@ -428,6 +456,139 @@ impl AssemblyInterface {
         }
     }
 
+    /// Process UTF-8 text in place using the best available assembly with safety
+    ///
+    /// The byte-level kernels skip runs of ASCII bytes with SIMD and rewrite the 2- and 3-byte sequences of
+    /// Vietnamese letters without decoding the text. Platforms without them decode the text and use
+    /// [`Self::process_chars_bulk_safe`]. Combining marks are composed with their letter first, as
+    /// [`crate::clean_string`] does.
+    ///
+    /// Returns the length of the cleaned text, which is written at the start of the buffer.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AssemblyError::InvalidInput`] if the buffer isn't valid UTF-8 or is too large, and
+    /// [`AssemblyError::Cancelled`] if the operation was cancelled.
+    pub fn process_utf8_bulk_safe(
+        &self,
+        bytes: &mut [u8],
+        control: &AssemblyControl,
+    ) -> Result<usize, AssemblyError> {
+        let Ok(text) = std::str::from_utf8(bytes) else {
+            return Err(AssemblyError::InvalidInput);
+        };
+
+        // The kernels clean one character at a time, so marks are composed with their letter first.
+        // Composing never makes the text longer, which lets it go back into the buffer.
+        let len = match compose(text) {
+            Cow::Borrowed(_) => bytes.len(),
+            Cow::Owned(composed) => {
+                bytes
+                    .get_mut(..composed.len())
+                    .ok_or(AssemblyError::InvalidInput)?
+                    .copy_from_slice(composed.as_bytes());
+                composed.len()
+            }
+        };
+        let bytes = bytes.get_mut(..len).ok_or(AssemblyError::InvalidInput)?;
+
+        if bytes.is_empty() {
+            return Ok(0);
+        }
+
+        // Same limit as the 100M characters of the bulk functions
+        if bytes.len() > 400_000_000 {
+            return Err(AssemblyError::InvalidInput);
+        }
+
+        if !self.is_assembly_available() {
+            return self.process_utf8_chars_fallback(bytes, control);
+        }
+
+        #[cfg(any(
+            all(target_arch = "x86_64", feature = "x86_64_assembly"),
+            all(target_arch = "aarch64", feature = "aarch64_assembly")
+        ))]
+        {
+            control.reset_for_operation(bytes.len());
+
+            // SAFETY:
+            // - bytes has been validated as UTF-8 above
+            // - bytes.as_mut_ptr() points to bytes.len() writable bytes
+            // - The kernel only writes before the bytes it has read, within the buffer
+            // - control pointer is valid for the duration of this call
+            #[cfg(target_arch = "x86_64")]
+            let written = unsafe {
+                hybrid_clean_utf8_bulk_safe(
+                    bytes.as_mut_ptr(),
+                    bytes.len(),
+                    std::ptr::from_ref(control),
+                )
+            };
+
+            #[cfg(target_arch = "aarch64")]
+            let written = unsafe {
+                _hybrid_clean_utf8_bulk_safe(
+                    bytes.as_mut_ptr(),
+                    bytes.len(),
+                    std::ptr::from_ref(control),
+                )
+            };
+
+            if control.was_cancelled() {
+                return Err(AssemblyError::Cancelled);
+            }
+
+            // Validate return value
+            if written > bytes.len() {
+                return Err(AssemblyError::ExecutionError(format!(
+                    "Assembly returned invalid length: {} > {}",
+                    written,
+                    bytes.len()
+                )));
+            }
+
+            Ok(written)
+        }
+        #[cfg(not(any(
+            all(target_arch = "x86_64", feature = "x86_64_assembly"),
+            all(target_arch = "aarch64", feature = "aarch64_assembly")
+        )))]
+        self.process_utf8_chars_fallback(bytes, control)
+    }
+
+    /// Fallback for UTF-8 text using the character bulk processing
+    fn process_utf8_chars_fallback(
+        &self,
+        bytes: &mut [u8],
+        control: &AssemblyControl,
+    ) -> Result<usize, AssemblyError> {
+        let input: Vec<u32> = std::str::from_utf8(bytes)
+            .map_err(|_| AssemblyError::InvalidInput)?
+            .chars()
+            .map(u32::from)
+            .collect();
+        let mut output = vec![0; input.len()];
+        let processed = self.process_chars_bulk_safe(&input, &mut output, control)?;
+        if processed < input.len() {
+            return Err(AssemblyError::Cancelled);
+        }
+
+        let mut written = 0;
+        for ch in output
+            .into_iter()
+            .map(|ch| char::from_u32(ch).unwrap_or(char::REPLACEMENT_CHARACTER))
+        {
+            let end = written + ch.len_utf8();
+            let target = bytes.get_mut(written..end).ok_or_else(|| {
+                AssemblyError::ExecutionError("Cleaned text is longer than the input".to_string())
+            })?;
+            ch.encode_utf8(target);
+            written = end;
+        }
+        Ok(written)
+    }
+
     /// Rust fallback implementation for single character
     fn process_char_rust_fallback(&self, ch: char) -> Result<char, AssemblyError> {
         // Use the existing Rust implementation from util module
@@ -539,6 +700,22 @@ pub fn process_chars_bulk_unsafe(input: &[char]) -> Vec<char> {
     input.iter().map(|&ch| process_char_unsafe(ch)).collect()
 }
 
+/// Performance-optimized UTF-8 string processing without safety overhead
+#[cfg(any(test, feature = "unsafe_performance"))]
+#[must_use]
+pub fn process_utf8_bulk_unsafe(input: &str) -> String {
+    let mut bytes = input.as_bytes().to_vec();
+    let control = AssemblyControl::new();
+    match get_assembly_interface().process_utf8_bulk_safe(&mut bytes, &control) {
+        Ok(len) => {
+            bytes.truncate(len);
+            String::from_utf8(bytes)
+                .unwrap_or_else(|_| input.chars().map(process_char_unsafe).collect())
+        }
+        Err(_) => input.chars().map(process_char_unsafe).collect(),
+    }
+}
+
 /// Get assembly platform information
 #[must_use]
 pub fn get_assembly_info() -> String {
@@ -176,6 +176,7 @@ sidechannel_protection:
 .global hybrid_clean_chars_bulk_bmi2
 .global hybrid_clean_chars_bulk_x86_64_safe
 .global hybrid_clean_chars_bulk_safe
+.global hybrid_clean_utf8_bulk_safe
 .global apple_hybrid_clean_char_optimized
 .global hybrid_clean_chars_bulk_neon
 
@@ -267,6 +268,39 @@ vietnamese_specific_1ea0:
     # Y family (0x1EF2-0x1EF9)
     .long 0x59, 0x79, 0x59, 0x79, 0x59, 0x79, 0x59, 0x79  # Ỳ,ỳ,Ỵ,ỵ,Ỷ,ỷ,Ỹ,ỹ -> Y,y,Y,y,Y,y,Y,y
 
+# Byte-level UTF-8 lookup tables: the ASCII base letter of a code point, or 0 if it's unchanged
+# U+00C0-U+01BF, encoded as 0xC3 0x80 - 0xC6 0xBF
+.p2align 6
+vietnamese_utf8_2byte:
+    .byte 0x41, 0x41, 0x41, 0x41, 0x00, 0x00, 0x00, 0x00, 0x45, 0x45, 0x45, 0x00, 0x49, 0x49, 0x00, 0x00  # 00C0-00CF: ÀÁÂÃÄÅÆÇÈÉÊËÌÍÎÏ
+    .byte 0x00, 0x00, 0x4F, 0x4F, 0x4F, 0x4F, 0x00, 0x00, 0x00, 0x55, 0x55, 0x00, 0x00, 0x59, 0x00, 0x00  # 00D0-00DF: ÐÑÒÓÔÕÖ×ØÙÚÛÜÝÞß
+    .byte 0x61, 0x61, 0x61, 0x61, 0x00, 0x00, 0x00, 0x00, 0x65, 0x65, 0x65, 0x00, 0x69, 0x69, 0x00, 0x00  # 00E0-00EF: àáâãäåæçèéêëìíîï
+    .byte 0x00, 0x00, 0x6F, 0x6F, 0x6F, 0x6F, 0x00, 0x00, 0x00, 0x75, 0x75, 0x00, 0x00, 0x79, 0x00, 0x00  # 00F0-00FF: ðñòóôõö÷øùúûüýþÿ
+    .byte 0x00, 0x00, 0x41, 0x61, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00  # 0100-010F: ĀāĂăĄąĆćĈĉĊċČčĎď
+    .byte 0x44, 0x64, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00  # 0110-011F: ĐđĒēĔĕĖėĘęĚěĜĝĞğ
+    .byte 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x49, 0x69, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00  # 0120-012F: ĠġĢģĤĥĦħĨĩĪīĬĭĮį
+    .byte 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00  # 0130-013F: İıĲĳĴĵĶķĸĹĺĻļĽľĿ
+    .byte 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00  # 0140-014F: ŀŁłŃńŅņŇňŉŊŋŌōŎŏ
+    .byte 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00  # 0150-015F: ŐőŒœŔŕŖŗŘřŚśŜŝŞş
+    .byte 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x55, 0x75, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00  # 0160-016F: ŠšŢţŤťŦŧŨũŪūŬŭŮů
+    .byte 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00  # 0170-017F: ŰűŲųŴŵŶŷŸŹźŻżŽžſ
+    .byte 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00  # 0180-018F: ƀƁƂƃƄƅƆƇƈƉƊƋƌƍƎƏ
+    .byte 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00  # 0190-019F: ƐƑƒƓƔƕƖƗƘƙƚƛƜƝƞƟ
+    .byte 0x4F, 0x6F, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x55  # 01A0-01AF: ƠơƢƣƤƥƦƧƨƩƪƫƬƭƮƯ
+    .byte 0x75, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00  # 01B0-01BF: ưƱƲƳƴƵƶƷƸƹƺƻƼƽƾƿ
+
+# U+1E80-U+1EFF, encoded as 0xE1 0xBA 0x80 - 0xE1 0xBB 0xBF
+.p2align 6
+vietnamese_utf8_3byte:
+    .byte 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00  # 1E80-1E8F: ẀẁẂẃẄẅẆẇẈẉẊẋẌẍẎẏ
+    .byte 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00  # 1E90-1E9F: ẐẑẒẓẔẕẖẗẘẙẚẛẜẝẞẟ
+    .byte 0x41, 0x61, 0x41, 0x61, 0x41, 0x61, 0x41, 0x61, 0x41, 0x61, 0x41, 0x61, 0x41, 0x61, 0x41, 0x61  # 1EA0-1EAF: ẠạẢảẤấẦầẨẩẪẫẬậẮắ
+    .byte 0x41, 0x61, 0x41, 0x61, 0x41, 0x61, 0x41, 0x61, 0x45, 0x65, 0x45, 0x65, 0x45, 0x65, 0x45, 0x65  # 1EB0-1EBF: ẰằẲẳẴẵẶặẸẹẺẻẼẽẾế
+    .byte 0x45, 0x65, 0x45, 0x65, 0x45, 0x65, 0x45, 0x65, 0x49, 0x69, 0x49, 0x69, 0x4F, 0x6F, 0x4F, 0x6F  # 1EC0-1ECF: ỀềỂểỄễỆệỈỉỊịỌọỎỏ
+    .byte 0x4F, 0x6F, 0x4F, 0x6F, 0x4F, 0x6F, 0x4F, 0x6F, 0x4F, 0x6F, 0x4F, 0x6F, 0x4F, 0x6F, 0x4F, 0x6F  # 1ED0-1EDF: ỐốỒồỔổỖỗỘộỚớỜờỞở
+    .byte 0x4F, 0x6F, 0x4F, 0x6F, 0x55, 0x75, 0x55, 0x75, 0x55, 0x75, 0x55, 0x75, 0x55, 0x75, 0x55, 0x75  # 1EE0-1EEF: ỠỡỢợỤụỦủỨứỪừỬửỮữ
+    .byte 0x55, 0x75, 0x59, 0x79, 0x59, 0x79, 0x59, 0x79, 0x59, 0x79, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00  # 1EF0-1EFF: ỰựỲỳỴỵỶỷỸỹỺỻỼỽỾỿ
+
 # BMI2 bit manipulation masks (cache-line aligned)
 .p2align 6
 bmi2_vietnamese_mask:
@@ -966,6 +1000,162 @@ hybrid_clean_chars_bulk_avx512_safe:
     # TODO: Add AVX-512-specific safety optimizations
     jmp hybrid_clean_chars_bulk_x86_64_safe
 
+# Byte-level UTF-8 cleaning with safety checks
+# Cleans valid UTF-8 text in place: runs of ASCII bytes are skipped 16 bytes at a time with SSE2, and the 2- and
+# 3-byte sequences of Vietnamese letters are rewritten as their ASCII base letter. The cleaned text is never longer
+# than the input, so it's written over the bytes already read.
+# Input: rdi = buffer pointer, rsi = length in bytes, rdx = control pointer
+# Output: rax = length of the cleaned text (the text cleaned so far if cancelled)
+hybrid_clean_utf8_bulk_safe:
+    push rbp
+    mov rbp, rsp
+    push rbx
+    push r12
+    push r13
+    push r14
+    push r15
+
+    # Save parameters
+    mov r12, rdi  # buffer pointer
+    mov r13, rsi  # length
+    mov r14, rdx  # control pointer
+
+    # Initialize indexes & lookup tables
+    xor r8, r8    # read index
+    xor r9, r9    # write index
+    xor r15, r15  # iteration counter
+    lea r10, [rip + vietnamese_utf8_2byte]
+    lea r11, [rip + vietnamese_utf8_3byte]
+
+.utf8_loop:
+    # Check the cancel flag (first byte of AssemblyControl) every 1024 iterations
+    inc r15
+    test r15, 0x3FF
+    jnz .utf8_block
+    movzx eax, byte ptr [r14]
+    test eax, eax
+    jnz .utf8_done
+
+.utf8_block:
+    # Process 16 bytes at once while they are all ASCII
+    mov rax, r13
+    sub rax, r8
+    cmp rax, 16
+    jb .utf8_scalar
+    movdqu xmm0, xmmword ptr [r12 + r8]
+    pmovmskb eax, xmm0
+    test eax, eax
+    jnz .utf8_ascii_prefix
+
+    # Move the block down only if the text already shrank
+    cmp r8, r9
+    je .utf8_block_in_place
+    movdqu xmmword ptr [r12 + r9], xmm0
+.utf8_block_in_place:
+    add r8, 16
+    add r9, 16
+    jmp .utf8_loop
+
+.utf8_ascii_prefix:
+    # Copy the ASCII bytes before the first non-ASCII byte of the block
+    bsf ecx, eax
+    test ecx, ecx
+    jz .utf8_scalar
+.utf8_prefix_copy:
+    movzx eax, byte ptr [r12 + r8]
+    mov byte ptr [r12 + r9], al
+    inc r8
+    inc r9
+    dec ecx
+    jnz .utf8_prefix_copy
+
+.utf8_scalar:
+    cmp r8, r13
+    jae .utf8_done
+
+    # Dispatch on the leading byte of the sequence
+    movzx eax, byte ptr [r12 + r8]
+    cmp eax, 0x80
+    jb .utf8_copy_1
+    cmp eax, 0xE0
+    jb .utf8_two_bytes
+    cmp eax, 0xE1
+    jne .utf8_other_sequence
+
+    # 3-byte sequence 0xE1 0xBA/0xBB xx: U+1E80-U+1EFF, index = ((b1 & 1) << 6) | (b2 & 0x3F)
+    movzx ebx, byte ptr [r12 + r8 + 1]
+    mov ecx, ebx
+    and ecx, 0xFE
+    cmp ecx, 0xBA
+    jne .utf8_copy_3
+    movzx ecx, byte ptr [r12 + r8 + 2]
+    and ebx, 1
+    shl ebx, 6
+    and ecx, 0x3F
+    or ebx, ecx
+    movzx eax, byte ptr [r11 + rbx]
+    test eax, eax
+    jz .utf8_copy_3
+    mov byte ptr [r12 + r9], al
+    add r8, 3
+    inc r9
+    jmp .utf8_loop
+
+.utf8_two_bytes:
+    # 2-byte sequence 0xC3-0xC6 xx: U+00C0-U+01BF, index = ((b0 - 0xC3) << 6) | (b1 & 0x3F)
+    cmp eax, 0xC3
+    jb .utf8_copy_2
+    cmp eax, 0xC6
+    ja .utf8_copy_2
+    movzx ecx, byte ptr [r12 + r8 + 1]
+    sub eax, 0xC3
+    shl eax, 6
+    and ecx, 0x3F
+    or eax, ecx
+    movzx eax, byte ptr [r10 + rax]
+    test eax, eax
+    jz .utf8_copy_2
+    mov byte ptr [r12 + r9], al
+    add r8, 2
+    inc r9
+    jmp .utf8_loop
+
+.utf8_other_sequence:
+    cmp eax, 0xF0
+    jae .utf8_copy_4
+.utf8_copy_3:
+    mov ecx, 3
+    jmp .utf8_copy
+.utf8_copy_4:
+    mov ecx, 4
+    jmp .utf8_copy
+.utf8_copy_2:
+    mov ecx, 2
+    jmp .utf8_copy
+.utf8_copy_1:
+    mov ecx, 1
+
+.utf8_copy:
+    # Copy a sequence that isn't changed
+    movzx eax, byte ptr [r12 + r8]
+    mov byte ptr [r12 + r9], al
+    inc r8
+    inc r9
+    dec ecx
+    jnz .utf8_copy
+    jmp .utf8_loop
+
+.utf8_done:
+    mov rax, r9  # Return the length of the cleaned text
+
+    pop r15
+    pop r14
+    pop r13
+    pop r12
+    pop rbx
+    pop rbp
+    ret
+
 # Function size information removed for macOS compatibility
 # .size directives are not supported on macOS assembler
 
@@ -0,0 +1,479 @@
+//! Case conversion of Vietnamese text.
+//!
+//! Vietnamese uses many precomposed letters outside of ASCII, like `đ` or `ặ`. The functions of this module convert
+//! their case with a constant table, like [`clean_char`](crate::util::clean_char), falling back to the Unicode case
+//! mapping of the standard library for other characters.
+//!
+//! [`title_case`] follows the Vietnamese convention for names & titles where every syllable is capitalized, e.g.
+//! `Nguyễn Thị Minh Khai`, and [`sentence_case`] only capitalizes the first letter of every sentence. The `make_*`
+//! functions convert a string in place, without reallocating it.
+//!
+//! ## Example
+//!
+//! ```
+//! use vi::case::{make_upper, sentence_case, title_case, to_lower};
+//!
+//! assert_eq!(title_case("nguyễn thị minh khai"), "Nguyễn Thị Minh Khai");
+//! assert_eq!(sentence_case("ĐẶNG VĂN LÂM. đội tuyển"), "Đặng văn lâm. Đội tuyển");
+//! assert_eq!(to_lower("ĐƯỜNG"), "đường");
+//!
+//! let mut text = String::from("đặc biệt");
+//! make_upper(&mut text);
+//! assert_eq!(text, "ĐẶC BIỆT");
+//! ```
+use crate::normalize::is_combining_mark;
+
+/// Convert a character to uppercase.
+///
+/// Every Vietnamese letter is converted, other characters outside of ASCII are kept as is. Use [`to_upper`] to
+/// convert any character.
+///
+/// # Examples
+///
+/// ```
+/// use vi::case::to_upper_char;
+///
+/// assert_eq!(to_upper_char('đ'), 'Đ');
+/// assert_eq!(to_upper_char('ặ'), 'Ặ');
+/// assert_eq!(to_upper_char('q'), 'Q');
+/// ```
+#[inline]
+#[must_use]
+pub const fn to_upper_char(ch: char) -> char {
+    match ch {
+        // a family
+        'à' => 'À',
+        'ả' => 'Ả',
+        'ã' => 'Ã',
+        'á' => 'Á',
+        'ạ' => 'Ạ',
+        'ă' => 'Ă',
+        'ằ' => 'Ằ',
+        'ẳ' => 'Ẳ',
+        'ẵ' => 'Ẵ',
+        'ắ' => 'Ắ',
+        'ặ' => 'Ặ',
+        'â' => 'Â',
+        'ầ' => 'Ầ',
+        'ẩ' => 'Ẩ',
+        'ẫ' => 'Ẫ',
+        'ấ' => 'Ấ',
+        'ậ' => 'Ậ',
+        // d family
+        'đ' => 'Đ',
+        // e family
+        'è' => 'È',
+        'ẻ' => 'Ẻ',
+        'ẽ' => 'Ẽ',
+        'é' => 'É',
+        'ẹ' => 'Ẹ',
+        'ê' => 'Ê',
+        'ề' => 'Ề',
+        'ể' => 'Ể',
+        'ễ' => 'Ễ',
+        'ế' => 'Ế',
+        'ệ' => 'Ệ',
+        // i family
+        'ì' => 'Ì',
+        'ỉ' => 'Ỉ',
+        'ĩ' => 'Ĩ',
+        'í' => 'Í',
+        'ị' => 'Ị',
+        // o family
+        'ò' => 'Ò',
+        'ỏ' => 'Ỏ',
+        'õ' => 'Õ',
+        'ó' => 'Ó',
+        'ọ' => 'Ọ',
+        'ô' => 'Ô',
+        'ồ' => 'Ồ',
+        'ổ' => 'Ổ',
+        'ỗ' => 'Ỗ',
+        'ố' => 'Ố',
+        'ộ' => 'Ộ',
+        'ơ' => 'Ơ',
+        'ờ' => 'Ờ',
+        'ở' => 'Ở',
+        'ỡ' => 'Ỡ',
+        'ớ' => 'Ớ',
+        'ợ' => 'Ợ',
+        // u family
+        'ù' => 'Ù',
+        'ủ' => 'Ủ',
+        'ũ' => 'Ũ',
+        'ú' => 'Ú',
+        'ụ' => 'Ụ',
+        'ư' => 'Ư',
+        'ừ' => 'Ừ',
+        'ử' => 'Ử',
+        'ữ' => 'Ữ',
+        'ứ' => 'Ứ',
+        'ự' => 'Ự',
+        // y family
+        'ỳ' => 'Ỳ',
+        'ỷ' => 'Ỷ',
+        'ỹ' => 'Ỹ',
+        'ý' => 'Ý',
+        'ỵ' => 'Ỵ',
+        _ => ch.to_ascii_uppercase(),
+    }
+}
+
+/// Convert a character to lowercase.
+///
+/// Every Vietnamese letter is converted, other characters outside of ASCII are kept as is. Use [`to_lower`] to
+/// convert any character.
+///
+/// # Examples
+///
+/// ```
+/// use vi::case::to_lower_char;
+///
+/// assert_eq!(to_lower_char('Đ'), 'đ');
+/// assert_eq!(to_lower_char('Ặ'), 'ặ');
+/// assert_eq!(to_lower_char('Q'), 'q');
+/// ```
+#[inline]
+#[must_use]
+pub const fn to_lower_char(ch: char) -> char {
+    match ch {
+        // a family
+        'À' => 'à',
+        'Ả' => 'ả',
+        'Ã' => 'ã',
+        'Á' => 'á',
+        'Ạ' => 'ạ',
+        'Ă' => 'ă',
+        'Ằ' => 'ằ',
+        'Ẳ' => 'ẳ',
+        'Ẵ' => 'ẵ',
+        'Ắ' => 'ắ',
+        'Ặ' => 'ặ',
+        'Â' => 'â',
+        'Ầ' => 'ầ',
+        'Ẩ' => 'ẩ',
+        'Ẫ' => 'ẫ',
+        'Ấ' => 'ấ',
+        'Ậ' => 'ậ',
+        // d family
+        'Đ' => 'đ',
+        // e family
+        'È' => 'è',
+        'Ẻ' => 'ẻ',
+        'Ẽ' => 'ẽ',
+        'É' => 'é',
+        'Ẹ' => 'ẹ',
+        'Ê' => 'ê',
+        'Ề' => 'ề',
+        'Ể' => 'ể',
+        'Ễ' => 'ễ',
+        'Ế' => 'ế',
+        'Ệ' => 'ệ',
+        // i family
+        'Ì' => 'ì',
+        'Ỉ' => 'ỉ',
+        'Ĩ' => 'ĩ',
+        'Í' => 'í',
+        'Ị' => 'ị',
+        // o family
+        'Ò' => 'ò',
+        'Ỏ' => 'ỏ',
+        'Õ' => 'õ',
+        'Ó' => 'ó',
+        'Ọ' => 'ọ',
+        'Ô' => 'ô',
+        'Ồ' => 'ồ',
+        'Ổ' => 'ổ',
+        'Ỗ' => 'ỗ',
+        'Ố' => 'ố',
+        'Ộ' => 'ộ',
+        'Ơ' => 'ơ',
+        'Ờ' => 'ờ',
+        'Ở' => 'ở',
+        'Ỡ' => 'ỡ',
+        'Ớ' => 'ớ',
+        'Ợ' => 'ợ',
+        // u family
+        'Ù' => 'ù',
+        'Ủ' => 'ủ',
+        'Ũ' => 'ũ',
+        'Ú' => 'ú',
+        'Ụ' => 'ụ',
+        'Ư' => 'ư',
+        'Ừ' => 'ừ',
+        'Ử' => 'ử',
+        'Ữ' => 'ữ',
+        'Ứ' => 'ứ',
+        'Ự' => 'ự',
+        // y family
+        'Ỳ' => 'ỳ',
+        'Ỷ' => 'ỷ',
+        'Ỹ' => 'ỹ',
+        'Ý' => 'ý',
+        'Ỵ' => 'ỵ',
+        _ => ch.to_ascii_lowercase(),
+    }
+}
+
+/// The case conversion applied to a text.
+#[derive(Debug, Clone, Copy)]
+enum Style {
+    Upper,
+    Lower,
+    Title,
+    Sentence,
+}
+
+/// Decides the case of every character of a text, one character after the other.
+struct Caser {
+    style: Style,
+    /// Whether the previous character is part of a word.
+    in_word: bool,
+    /// Whether no letter was seen since the start of the current sentence.
+    sentence_start: bool,
+}
+
+impl Caser {
+    const fn new(style: Style) -> Self {
+        Self {
+            style,
+            in_word: false,
+            sentence_start: true,
+        }
+    }
+
+    /// Returns whether the next character of the text should be in uppercase.
+    fn is_upper(&mut self, ch: char) -> bool {
+        let is_word_char = ch.is_alphanumeric() || is_combining_mark(ch);
+        let word_start = is_word_char && !self.in_word;
+        self.in_word = is_word_char;
+
+        let sentence_start = self.sentence_start && ch.is_alphanumeric();
+        if ch.is_alphanumeric() {
+            self.sentence_start = false;
+        } else if matches!(ch, '.' | '!' | '?' | '…') {
+            self.sentence_start = true;
+        }
+
+        match self.style {
+            Style::Upper => true,
+            Style::Lower => false,
+            Style::Title => word_start,
+            Style::Sentence => sentence_start,
+        }
+    }
+}
+
+fn convert(text: &str, style: Style) -> String {
+    let mut caser = Caser::new(style);
+    let mut result = String::with_capacity(text.len());
+    for ch in text.chars() {
+        let is_upper = caser.is_upper(ch);
+        match convert_char(ch, is_upper) {
+            converted if converted != ch => result.push(converted),
+            _ if is_upper => result.extend(ch.to_uppercase()),
+            _ => result.extend(ch.to_lowercase()),
+        }
+    }
+    result
+}
+
+fn convert_in_place(text: &mut String, style: Style) {
+    let mut caser = Caser::new(style);
+    let mut index = 0;
+    while let Some(ch) = text[index..].chars().next() {
+        let is_upper = caser.is_upper(ch);
+        let converted = match convert_char(ch, is_upper) {
+            converted if converted != ch => Some(converted),
+            _ if is_upper => single_char(ch.to_uppercase()),
+            _ => single_char(ch.to_lowercase()),
+        };
+        if let Some(converted) = converted.filter(|converted| converted.len_utf8() == ch.len_utf8())
+        {
+            let mut buffer = [0; 4];
+            text.replace_range(
+                index..index + ch.len_utf8(),
+                converted.encode_utf8(&mut buffer),
+            );
+        }
+        index += ch.len_utf8();
+    }
+}
+
+const fn convert_char(ch: char, is_upper: bool) -> char {
+    if is_upper {
+        to_upper_char(ch)
+    } else {
+        to_lower_char(ch)
+    }
+}
+
+/// Returns the only character of a case mapping of the standard library, if it maps to a single character.
+fn single_char(mut chars: impl Iterator<Item = char>) -> Option<char> {
+    chars.next().filter(|_| chars.next().is_none())
+}
+
+/// Convert a text to uppercase.
+///
+/// # Examples
+///
+/// ```
+/// use vi::case::to_upper;
+///
+/// assert_eq!(to_upper("Tiếng Việt"), "TIẾNG VIỆT");
+/// ```
+#[must_use]
+pub fn to_upper(text: &str) -> String {
+    convert(text, Style::Upper)
+}
+
+/// Convert a text to lowercase.
+///
+/// # Examples
+///
+/// ```
+/// use vi::case::to_lower;
+///
+/// assert_eq!(to_lower("TIẾNG VIỆT"), "tiếng việt");
+/// ```
+#[must_use]
+pub fn to_lower(text: &str) -> String {
+    convert(text, Style::Lower)
+}
+
+/// Capitalize every word of a text and convert the rest to lowercase, as written in Vietnamese names & titles.
+///
+/// # Examples
+///
+/// ```
+/// use vi::case::title_case;
+///
+/// assert_eq!(title_case("nguyễn thị minh khai"), "Nguyễn Thị Minh Khai");
+/// assert_eq!(title_case("ĐÀ NẴNG, việt nam"), "Đà Nẵng, Việt Nam");
+/// ```
+#[must_use]
+pub fn title_case(text: &str) -> String {
+    convert(text, Style::Title)
+}
+
+/// Capitalize the first letter of every sentence of a text and convert the rest to lowercase.
+///
+/// A sentence ends with `.`, `!`, `?` or `…`. Proper nouns are converted to lowercase too.
+///
+/// # Examples
+///
+/// ```
+/// use vi::case::sentence_case;
+///
+/// assert_eq!(sentence_case("XIN CHÀO! ăn cơm chưa?"), "Xin chào! Ăn cơm chưa?");
+/// ```
+#[must_use]
+pub fn sentence_case(text: &str) -> String {
+    convert(text, Style::Sentence)
+}
+
+/// Convert a text to uppercase in place, without reallocating it.
+///
+/// Characters whose uppercase is longer, like `ß`, are kept as is.
+///
+/// # Examples
+///
+/// ```
+/// use vi::case::make_upper;
+///
+/// let mut text = String::from("đường");
+/// let capacity = text.capacity();
+/// make_upper(&mut text);
+/// assert_eq!(text, "ĐƯỜNG");
+/// assert_eq!(text.capacity(), capacity);
+/// ```
+pub fn make_upper(text: &mut String) {
+    convert_in_place(text, Style::Upper);
+}
+
+/// Convert a text to lowercase in place, without reallocating it.
+///
+/// Characters whose lowercase is longer are kept as is.
+pub fn make_lower(text: &mut String) {
+    convert_in_place(text, Style::Lower);
+}
+
+/// Convert a text to title case in place, without reallocating it. See [`title_case`].
+pub fn make_title_case(text: &mut String) {
+    convert_in_place(text, Style::Title);
+}
+
+/// Convert a text to sentence case in place, without reallocating it. See [`sentence_case`].
+pub fn make_sentence_case(text: &mut String) {
+    convert_in_place(text, Style::Sentence);
+}
+
+/// Checks whether a text is written in all caps, i.e. it has more than one letter and all of them are uppercase.
+pub(crate) fn is_all_caps(text: &str) -> bool {
+    let (count, all_uppercase) = text
+        .chars()
+        .filter(|ch| ch.is_alphabetic())
+        .fold((0, true), |(count, all_uppercase), ch| {
+            (count + 1, all_uppercase && ch.is_uppercase())
+        });
+    count > 1 && all_uppercase
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOWERCASE_LETTERS: &str =
+        "aàảãáạăằẳẵắặâầẩẫấậbcdđeèẻẽéẹêềểễếệghiìỉĩíịklmnoòỏõóọôồổỗốộơờởỡớợpqrstuùủũúụưừửữứựvxyỳỷỹýỵ";
+
+    #[test]
+    fn char_table_matches_unicode() {
+        for ch in LOWERCASE_LETTERS.chars() {
+            let upper = to_upper_char(ch);
+            assert_eq!(Some(upper), ch.to_uppercase().next(), "{ch}");
+            assert_ne!(upper, ch, "{ch}");
+            assert_eq!(to_lower_char(upper), ch, "{ch}");
+        }
+        assert_eq!(to_upper_char('ñ'), 'ñ');
+        assert_eq!(to_upper_char('1'), '1');
+    }
+
+    #[test]
+    fn convert_strings() {
+        let upper = to_upper(LOWERCASE_LETTERS);
+        assert_eq!(upper, LOWERCASE_LETTERS.to_uppercase());
+        assert_eq!(to_lower(&upper), LOWERCASE_LETTERS);
+        // Characters outside of the table use the standard library
+        assert_eq!(to_upper("straße ñ"), "STRASSE Ñ");
+        // Decomposed letters
+        assert_eq!(to_upper("vie\u{323}\u{302}t"), "VIE\u{323}\u{302}T");
+    }
+
+    #[test]
+    fn title_and_sentence_case() {
+        assert_eq!(title_case("trần HƯNG đạo"), "Trần Hưng Đạo");
+        assert_eq!(title_case("bà rịa–vũng tàu"), "Bà Rịa–Vũng Tàu");
+        assert_eq!(title_case("e\u{301}p  ổi"), "E\u{301}p  Ổi");
+        assert_eq!(title_case("3g và 4g"), "3g Và 4g");
+        assert_eq!(
+            sentence_case("  hôm nay TRỜI ĐẸP... đi chơi thôi!ừ"),
+            "  Hôm nay trời đẹp... Đi chơi thôi!Ừ"
+        );
+        assert_eq!(sentence_case("2 con mèo. đen"), "2 con mèo. Đen");
+    }
+
+    #[test]
+    fn in_place_conversion() {
+        let mut text = String::from("nguyễn thị minh khai, straße");
+        let pointer = text.as_ptr();
+        make_title_case(&mut text);
+        assert_eq!(text, "Nguyễn Thị Minh Khai, Straße");
+        make_upper(&mut text);
+        assert_eq!(text, "NGUYỄN THỊ MINH KHAI, STRAßE");
+        make_lower(&mut text);
+        assert_eq!(text, "nguyễn thị minh khai, straße");
+        make_sentence_case(&mut text);
+        assert_eq!(text, "Nguyễn thị minh khai, straße");
+        assert_eq!(text.as_ptr(), pointer);
+    }
+}
@@ -0,0 +1,330 @@
+//! Sorting Vietnamese text in dictionary order.
+//!
+//! Comparing strings with [`str::cmp`] compares code points, which puts `đ` after `z` and `ă` after every other
+//! Latin letter. This module sorts text the way Vietnamese dictionaries do, comparing strings level by level:
+//!
+//! 1. The letters of the Vietnamese alphabet, `a ă â b c d đ e ê g h i k l m n o ô ơ p q r s t u ư v x y`. A
+//!    letter modification makes a different letter, so `ăn` comes after `ao`. The letters `f`, `j`, `w` & `z` of
+//!    foreign words take their Latin place.
+//! 2. The tone marks, in the order `a à ả ã á ạ`.
+//! 3. The case, lowercase first.
+//!
+//! A difference at a level is only looked at when the strings are equal at all the levels before it, so `má`
+//! comes before `mai` even though `mà` comes before `má`. Digits, whitespace & punctuation come before letters.
+//!
+//! A [`Strength`] limits the differences taken into account: [`Strength::Secondary`] stops after the letters, and
+//! [`Strength::Tertiary`] (the default) compares all the levels. [`Strength::Primary`] is not one of these levels
+//! but a different ordering, for matching strings regardless of their diacritics: it only compares the base
+//! letters, without their modifications, so `viet` is equal to `việt` but `ăn` comes before `ao`.
+//!
+//! ## Example
+//!
+//! ```
+//! use vi::collation::compare;
+//!
+//! let mut words = ["đi", "zoo", "ăn", "du", "Ăn", "ào", "ân", "an", "ao"];
+//! words.sort_by(|a, b| compare(a, b));
+//! assert_eq!(words, ["an", "ao", "ào", "ăn", "Ăn", "ân", "du", "đi", "zoo"]);
+//! ```
+use std::cmp::Ordering;
+
+use crate::{
+    editing::add_modification_char,
+    normalize::compose,
+    parsing::{extract_letter_modification_char, extract_tone_char},
+    processor::ToneMark,
+    util::clean_char,
+};
+
+/// The letters of the Vietnamese alphabet, with the Latin letters only used in foreign words.
+const ALPHABET: [char; 33] = [
+    'a', 'ă', 'â', 'b', 'c', 'd', 'đ', 'e', 'ê', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o',
+    'ô', 'ơ', 'p', 'q', 'r', 's', 't', 'u', 'ư', 'v', 'w', 'x', 'y', 'z',
+];
+
+/// The primary weight of the first letter of [`ALPHABET`]. Characters that aren't letters weigh less, other
+/// letters weigh more.
+const ALPHABET_WEIGHT: u32 = 0x20_0000;
+
+/// The primary weight of the first letter that isn't in [`ALPHABET`].
+const OTHER_LETTER_WEIGHT: u32 = 0x30_0000;
+
+/// The differences taken into account when comparing strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum Strength {
+    /// Only the base letters are compared, e.g. `Việt` is equal to `viet`.
+    ///
+    /// This is a different ordering rather than a coarser level of the dictionary order: strings that aren't equal
+    /// are ordered by their base letters, so `ăn` comes before `ao`, while it comes after it at the other
+    /// strengths.
+    Primary,
+    /// The letters & their modifications are compared, e.g. `Việt` is equal to `viêt` but not to `viet`.
+    Secondary,
+    /// The letters, their modifications, the tone marks & the case are compared (default).
+    #[default]
+    Tertiary,
+}
+
+/// A key that sorts like its string in Vietnamese dictionary order.
+///
+/// Computing the key of every string once is faster than comparing the strings again and again when sorting large
+/// lists, e.g. with [`slice::sort_by_cached_key`].
+///
+/// # Examples
+///
+/// ```
+/// use vi::collation::sort_key;
+///
+/// let mut words = vec!["Việt", "viết", "viet", "vịt"];
+/// words.sort_by_cached_key(|word| sort_key(word));
+/// assert_eq!(words, ["viet", "viết", "Việt", "vịt"]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SortKey(Vec<u32>);
+
+impl SortKey {
+    /// Returns the key as bytes that sort in the same order when compared byte by byte, e.g. to store it in a
+    /// database index.
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.0
+            .iter()
+            .flat_map(|weight| weight.to_be_bytes())
+            .collect()
+    }
+}
+
+/// The collation weights of a character. Weights start at 1, as 0 separates the levels of a key.
+struct Weights {
+    /// The weight of the base letter, without modification.
+    base: u32,
+    /// The weight of the letter with its modification.
+    letter: u32,
+    tone: u32,
+    case: u32,
+}
+
+impl Weights {
+    fn of(ch: char) -> Self {
+        let lowercase = ch.to_lowercase().next().unwrap_or(ch);
+        let base = clean_char(lowercase);
+        let letter = extract_letter_modification_char(lowercase).map_or(base, |modification| {
+            add_modification_char(base, &modification)
+        });
+
+        let weight = |letter: char| {
+            let alphabet_weight =
+                ALPHABET
+                    .iter()
+                    .zip(ALPHABET_WEIGHT..)
+                    .find_map(|(alphabet_letter, weight)| {
+                        (*alphabet_letter == letter).then_some(weight)
+                    });
+            match alphabet_weight {
+                Some(weight) => weight,
+                None if ch.is_alphabetic() => OTHER_LETTER_WEIGHT + u32::from(lowercase),
+                None => 1 + u32::from(ch),
+            }
+        };
+        let tone = match extract_tone_char(lowercase) {
+            None => 1,
+            Some(ToneMark::Grave) => 2,
+            Some(ToneMark::HookAbove) => 3,
+            Some(ToneMark::Tilde) => 4,
+            Some(ToneMark::Acute) => 5,
+            Some(ToneMark::Underdot) => 6,
+        };
+        let case = if ch.is_uppercase() { 2 } else { 1 };
+        Self {
+            base: weight(base),
+            letter: weight(letter),
+            tone,
+            case,
+        }
+    }
+}
+
+/// Computes the key of a string for sorting it in Vietnamese dictionary order.
+///
+/// # Examples
+///
+/// ```
+/// use vi::collation::sort_key;
+///
+/// assert!(sort_key("đi") < sort_key("em"));
+/// assert!(sort_key("ăn") > sort_key("ao"));
+/// assert_eq!(sort_key("Vie\u{323}\u{302}t"), sort_key("Việt"));
+/// ```
+#[must_use]
+pub fn sort_key(text: &str) -> SortKey {
+    sort_key_with_strength(text, Strength::Tertiary)
+}
+
+/// Computes the key of a string for sorting it in Vietnamese dictionary order, only taking into account the
+/// differences up to a level.
+///
+/// # Examples
+///
+/// ```
+/// use vi::collation::{sort_key_with_strength, Strength};
+///
+/// let key = |text| sort_key_with_strength(text, Strength::Primary);
+/// assert_eq!(key("Việt"), key("viet"));
+///
+/// let key = |text| sort_key_with_strength(text, Strength::Secondary);
+/// assert_eq!(key("Việt"), key("viêt"));
+/// assert!(key("viet") < key("viêt"));
+/// ```
+#[must_use]
+pub fn sort_key_with_strength(text: &str, strength: Strength) -> SortKey {
+    let weights: Vec<Weights> = compose(text).chars().map(Weights::of).collect();
+    if strength == Strength::Primary {
+        return SortKey(weights.iter().map(|weights| weights.base).collect());
+    }
+    // The modifications make different letters, so they're compared together with the base letters
+    let mut key: Vec<u32> = weights.iter().map(|weights| weights.letter).collect();
+    if strength >= Strength::Tertiary {
+        key.push(0);
+        key.extend(weights.iter().map(|weights| weights.tone));
+        key.push(0);
+        key.extend(weights.iter().map(|weights| weights.case));
+    }
+    SortKey(key)
+}
+
+/// Compares two strings in Vietnamese dictionary order.
+///
+/// The ordering is total and consistent with [`sort_key`], so it can be used with [`slice::sort_by`] or
+/// [`Iterator::max_by`]. Strings that only differ by their composition, like `ệ` and `e` followed by combining
+/// marks, are equal.
+///
+/// # Examples
+///
+/// ```
+/// use std::cmp::Ordering;
+/// use vi::collation::compare;
+///
+/// assert_eq!(compare("đá", "dạ"), Ordering::Greater);
+/// assert_eq!(compare("ma", "má"), Ordering::Less);
+/// assert_eq!(compare("má", "mai"), Ordering::Less);
+/// ```
+#[must_use]
+pub fn compare(a: &str, b: &str) -> Ordering {
+    compare_with_strength(a, b, Strength::Tertiary)
+}
+
+/// Compares two strings in Vietnamese dictionary order, only taking into account the differences up to a level.
+///
+/// # Examples
+///
+/// ```
+/// use std::cmp::Ordering;
+/// use vi::collation::{compare_with_strength, Strength};
+///
+/// assert_eq!(compare_with_strength("viet", "việt", Strength::Primary), Ordering::Equal);
+/// assert_eq!(compare_with_strength("Hà Nội", "hà nôi", Strength::Secondary), Ordering::Equal);
+/// assert_eq!(compare_with_strength("Hà Nội", "hà nội", Strength::Tertiary), Ordering::Greater);
+/// ```
+#[must_use]
+pub fn compare_with_strength(a: &str, b: &str, strength: Strength) -> Ordering {
+    sort_key_with_strength(a, strength).cmp(&sort_key_with_strength(b, strength))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted(mut words: Vec<&str>) -> Vec<&str> {
+        words.sort_by(|a, b| compare(a, b));
+        words
+    }
+
+    #[test]
+    fn alphabet_order() {
+        let mut letters: Vec<String> = ALPHABET.iter().rev().map(char::to_string).collect();
+        letters.sort_by(|a, b| compare(a, b));
+        let expected: Vec<String> = ALPHABET.iter().map(char::to_string).collect();
+        assert_eq!(letters, expected);
+
+        assert_eq!(
+            sorted(vec!["ơn", "ôm", "ong", "ưa", "uy", "êm", "en"]),
+            ["en", "êm", "ong", "ôm", "ơn", "uy", "ưa"]
+        );
+    }
+
+    #[test]
+    fn tone_order() {
+        assert_eq!(
+            sorted(vec!["mạ", "má", "mã", "mả", "mà", "ma"]),
+            ["ma", "mà", "mả", "mã", "má", "mạ"]
+        );
+        assert_eq!(
+            sorted(vec!["ậm", "ầm", "âm", "am"]),
+            ["am", "âm", "ầm", "ậm"]
+        );
+    }
+
+    #[test]
+    fn levels() {
+        // A letter difference wins over earlier tone mark & case differences
+        assert_eq!(compare("Má", "mai"), Ordering::Less);
+        assert_eq!(compare("mà b", "ma c"), Ordering::Less);
+        // A tone mark difference wins over an earlier case difference
+        assert_eq!(compare("Ma", "mà"), Ordering::Less);
+        assert_eq!(compare("ma", "Ma"), Ordering::Less);
+        assert_eq!(compare("Đường", "đường"), Ordering::Greater);
+        assert_eq!(
+            compare_with_strength("ĐƯỜNG", "đường", Strength::Secondary),
+            Ordering::Equal
+        );
+        assert_eq!(
+            compare_with_strength("đường", "đương", Strength::Secondary),
+            Ordering::Equal
+        );
+        assert_eq!(
+            compare_with_strength("đường", "dương", Strength::Secondary),
+            Ordering::Greater
+        );
+        // The primary strength only compares the base letters, which orders strings differently
+        assert_eq!(
+            compare_with_strength("viet", "Việt", Strength::Primary),
+            Ordering::Equal
+        );
+        assert_eq!(
+            compare_with_strength("ĐƯỜNG", "duong", Strength::Primary),
+            Ordering::Equal
+        );
+        assert_eq!(
+            compare_with_strength("ăn", "ao", Strength::Primary),
+            Ordering::Less
+        );
+        assert_eq!(
+            compare_with_strength("ăn", "ao", Strength::Secondary),
+            Ordering::Greater
+        );
+    }
+
+    #[test]
+    fn other_characters() {
+        assert_eq!(
+            sorted(vec!["b", "a b", "ab", "1", "ß", "a"]),
+            ["1", "a", "a b", "ab", "b", "ß"]
+        );
+        assert_eq!(compare("", "a"), Ordering::Less);
+        assert_eq!(compare("", ""), Ordering::Equal);
+    }
+
+    #[test]
+    fn key_bytes() {
+        let words = ["an", "ăn", "Ăn", "ắn", "đi", "zoo", "a"];
+        for a in words {
+            for b in words {
+                assert_eq!(
+                    sort_key(a).to_bytes().cmp(&sort_key(b).to_bytes()),
+                    compare(a, b),
+                    "{a} {b}"
+                );
+            }
+        }
+    }
+}
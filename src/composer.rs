@@ -0,0 +1,420 @@
+//! A multi-syllable composing session on top of [`IncrementalBuffer`].
+//!
+//! An [`IncrementalBuffer`] models exactly one syllable. A [`Composer`] takes a whole stream of keystrokes,
+//! including spaces, punctuation and digits, and decides when the syllable being typed (the *preedit*) is done
+//! and should be committed to the editor. Every keystroke returns the [`ComposerEvent`]s an editor plugin
+//! needs to update its preedit area and insert committed text.
+//!
+//! ## Example
+//!
+//! ```
+//! use vi::composer::{Composer, ComposerEvent};
+//!
+//! let mut composer = Composer::new(&vi::TELEX);
+//!
+//! composer.push_str("xin chaof");
+//! assert_eq!(composer.committed(), "xin ");
+//! assert_eq!(composer.preedit(), "chào");
+//!
+//! let events = composer.push(',');
+//! assert_eq!(
+//!     events,
+//!     vec![
+//!         ComposerEvent::Commit("chào,".to_owned()),
+//!         ComposerEvent::PreeditChanged(String::new()),
+//!     ]
+//! );
+//! assert_eq!(composer.committed(), "xin chào,");
+//! ```
+use crate::{
+    abbreviation::AbbreviationTable,
+    methods::{BackspaceMode, IncrementalBuffer, ToneEntryPolicy, TypingDefinition, UndoPolicy},
+    processor::AccentStyle,
+    validation::ValidationMode,
+};
+
+/// The language a [`Composer`] is currently typing in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InputMode {
+    /// Keystrokes are transformed using the typing definition.
+    #[default]
+    Vietnamese,
+    /// Keystrokes are committed as is.
+    English,
+}
+
+/// An event emitted by a [`Composer`] for the editor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ComposerEvent {
+    /// The text being composed has changed and should be displayed as the new preedit.
+    PreeditChanged(String),
+    /// The text should be inserted into the editor. The preedit has been committed if it was part of it.
+    Commit(String),
+    /// The text, which was the last to be committed, should be removed from the editor.
+    Retract(String),
+}
+
+/// A composing session that turns a stream of keystrokes into committed text and a live preedit.
+///
+/// A syllable is committed when a word boundary is typed: a whitespace, or a character that is neither a
+/// letter nor a key of the typing definition. Keys of the definition that are not letters, such as the
+/// digits of [`VNI`](crate::VNI) or the marks of [`VIQR`](crate::VIQR), are committed directly when there is
+/// nothing to apply them to.
+#[derive(Debug, Clone)]
+pub struct Composer<'def> {
+    /// Reference to the typing method definition
+    definition: &'def dyn TypingDefinition,
+    /// The syllable being composed
+    buffer: IncrementalBuffer<'def>,
+    /// All the text committed so far
+    committed: String,
+    /// The current input mode
+    mode: InputMode,
+    /// The last committed text if it expanded an abbreviation, with the text it replaces when the expansion is
+    /// undone. It's kept until the next key.
+    expansion: Option<(String, String)>,
+}
+
+// Composers can be moved across threads, like the buffers they hold
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Composer<'static>>();
+};
+
+impl<'def> Composer<'def> {
+    /// Creates a new composer with the specified typing definition.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vi::composer::Composer;
+    ///
+    /// let composer = Composer::new(&vi::VNI);
+    /// assert_eq!(composer.preedit(), "");
+    /// ```
+    #[must_use]
+    pub fn new<D: TypingDefinition>(definition: &'def D) -> Self {
+        Self::new_with_style(definition, AccentStyle::default())
+    }
+
+    /// Creates a new composer with the specified typing definition and accent style.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vi::{composer::Composer, processor::AccentStyle};
+    ///
+    /// let mut composer = Composer::new_with_style(&vi::TELEX, AccentStyle::Old);
+    /// composer.push_str("hoas ");
+    /// assert_eq!(composer.committed(), "hóa ");
+    /// ```
+    #[must_use]
+    pub fn new_with_style<D: TypingDefinition>(
+        definition: &'def D,
+        accent_style: AccentStyle,
+    ) -> Self {
+        Self {
+            definition,
+            buffer: IncrementalBuffer::new_with_style(definition, accent_style),
+            committed: String::new(),
+            mode: InputMode::default(),
+            expansion: None,
+        }
+    }
+
+    /// Sets the policy deciding when tone keys are applied as tone marks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vi::{composer::Composer, methods::ToneEntryPolicy};
+    ///
+    /// let mut composer = Composer::new(&vi::TELEX).with_tone_entry_policy(ToneEntryPolicy::Strict);
+    /// composer.push_str("taxi toans ");
+    /// assert_eq!(composer.committed(), "taxi toán ");
+    /// ```
+    #[must_use]
+    pub fn with_tone_entry_policy(mut self, policy: ToneEntryPolicy) -> Self {
+        self.buffer = self.buffer.with_tone_entry_policy(policy);
+        self
+    }
+
+    /// Sets how actions are undone when their key is typed again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vi::{composer::Composer, methods::UndoPolicy};
+    ///
+    /// let mut composer = Composer::new(&vi::TELEX).with_undo_policy(UndoPolicy::TOGGLE);
+    /// composer.push_str("caasss ");
+    /// assert_eq!(composer.committed(), "cấ ");
+    /// ```
+    #[must_use]
+    pub fn with_undo_policy(mut self, policy: UndoPolicy) -> Self {
+        self.buffer = self.buffer.with_undo_policy(policy);
+        self
+    }
+
+    /// Sets the rules a syllable must follow for an action to be applied.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vi::{composer::Composer, validation::ValidationMode};
+    ///
+    /// let mut composer = Composer::new(&vi::TELEX).with_validation_mode(ValidationMode::Strict);
+    /// composer.push_str("hopf hopj ");
+    /// assert_eq!(composer.committed(), "hopf họp ");
+    /// ```
+    #[must_use]
+    pub fn with_validation_mode(mut self, mode: ValidationMode) -> Self {
+        self.buffer = self.buffer.with_validation_mode(mode);
+        self
+    }
+
+    /// Sets the abbreviations expanded when a word is committed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vi::{abbreviation::AbbreviationTable, composer::Composer};
+    ///
+    /// let table: AbbreviationTable = "vn = Việt Nam".parse()?;
+    ///
+    /// let mut composer = Composer::new(&vi::TELEX).with_abbreviations(&table);
+    /// composer.push_str("VN vaf vn.");
+    /// assert_eq!(composer.committed(), "VIỆT NAM và Việt Nam.");
+    /// # Ok::<(), vi::abbreviation::AbbreviationError>(())
+    /// ```
+    #[must_use]
+    pub fn with_abbreviations(mut self, abbreviations: &'def AbbreviationTable) -> Self {
+        self.buffer = self.buffer.with_abbreviations(abbreviations);
+        self
+    }
+
+    /// Enables or disables restoring the keystrokes of English words when they are committed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vi::composer::Composer;
+    ///
+    /// let mut composer = Composer::new(&vi::TELEX).with_english_restoration(true);
+    /// composer.push_str("those tieengs ");
+    /// assert_eq!(composer.committed(), "those tiếng ");
+    /// ```
+    #[must_use]
+    pub fn with_english_restoration(mut self, enable: bool) -> Self {
+        self.buffer = self.buffer.with_english_restoration(enable);
+        self
+    }
+
+    /// Processes a keystroke and returns the events the editor should apply.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vi::composer::{Composer, ComposerEvent};
+    ///
+    /// let mut composer = Composer::new(&vi::TELEX);
+    ///
+    /// assert_eq!(composer.push('a'), vec![ComposerEvent::PreeditChanged("a".to_owned())]);
+    /// assert_eq!(composer.push('s'), vec![ComposerEvent::PreeditChanged("á".to_owned())]);
+    /// assert_eq!(
+    ///     composer.push(' '),
+    ///     vec![
+    ///         ComposerEvent::Commit("á ".to_owned()),
+    ///         ComposerEvent::PreeditChanged(String::new()),
+    ///     ]
+    /// );
+    /// ```
+    pub fn push(&mut self, ch: char) -> Vec<ComposerEvent> {
+        self.expansion = None;
+        if self.mode == InputMode::English {
+            self.committed.push(ch);
+            return vec![ComposerEvent::Commit(ch.to_string())];
+        }
+
+        if self.is_word_boundary(ch) {
+            return self.commit_with(Some(ch));
+        }
+
+        let _ = self.buffer.push(ch);
+        vec![ComposerEvent::PreeditChanged(self.buffer.view().to_owned())]
+    }
+
+    /// Processes every character of a string as a keystroke and returns all the emitted events.
+    pub fn push_str(&mut self, input: &str) -> Vec<ComposerEvent> {
+        input.chars().flat_map(|ch| self.push(ch)).collect()
+    }
+
+    /// Commits the current preedit, if there's any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vi::composer::Composer;
+    ///
+    /// let mut composer = Composer::new(&vi::TELEX);
+    /// composer.push_str("vieetj");
+    /// composer.commit();
+    /// assert_eq!(composer.committed(), "việt");
+    /// assert_eq!(composer.preedit(), "");
+    /// ```
+    pub fn commit(&mut self) -> Vec<ComposerEvent> {
+        self.commit_with(None)
+    }
+
+    /// Removes either the last keystroke or the last visible character of the preedit.
+    ///
+    /// If the last commit expanded an abbreviation and nothing was typed since, the expansion is undone instead:
+    /// it's retracted, and the abbreviation is committed in its place.
+    ///
+    /// Returns no event if there's no preedit, in which case the editor should handle the backspace itself.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vi::{composer::{Composer, ComposerEvent}, methods::BackspaceMode};
+    ///
+    /// let mut composer = Composer::new(&vi::TELEX);
+    /// composer.push_str("chaof");
+    ///
+    /// let events = composer.backspace(BackspaceMode::Keystroke);
+    /// assert_eq!(events, vec![ComposerEvent::PreeditChanged("chao".to_owned())]);
+    /// ```
+    pub fn backspace(&mut self, mode: BackspaceMode) -> Vec<ComposerEvent> {
+        if let Some((expansion, text)) = self.expansion.take() {
+            // The committed text may have been taken out already
+            if self.committed.ends_with(&expansion) {
+                self.committed
+                    .truncate(self.committed.len() - expansion.len());
+            }
+            self.committed.push_str(&text);
+            return vec![
+                ComposerEvent::Retract(expansion),
+                ComposerEvent::Commit(text),
+            ];
+        }
+
+        if self.buffer.backspace(mode).is_none() {
+            return Vec::new();
+        }
+        vec![ComposerEvent::PreeditChanged(self.buffer.view().to_owned())]
+    }
+
+    /// Discards the current preedit without committing it.
+    pub fn reset(&mut self) -> Vec<ComposerEvent> {
+        self.expansion = None;
+        if self.buffer.is_empty() {
+            return Vec::new();
+        }
+        self.buffer.clear();
+        vec![ComposerEvent::PreeditChanged(String::new())]
+    }
+
+    /// Switches the input mode. The current preedit is committed first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vi::composer::{Composer, InputMode};
+    ///
+    /// let mut composer = Composer::new(&vi::TELEX);
+    /// composer.push_str("tieengs");
+    /// composer.set_mode(InputMode::English);
+    /// composer.push_str(" English");
+    /// assert_eq!(composer.committed(), "tiếng English");
+    /// ```
+    pub fn set_mode(&mut self, mode: InputMode) -> Vec<ComposerEvent> {
+        let events = self.commit();
+        self.mode = mode;
+        events
+    }
+
+    /// Switches between [`InputMode::Vietnamese`] and [`InputMode::English`]. The current preedit is
+    /// committed first.
+    pub fn toggle_mode(&mut self) -> Vec<ComposerEvent> {
+        let mode = match self.mode {
+            InputMode::Vietnamese => InputMode::English,
+            InputMode::English => InputMode::Vietnamese,
+        };
+        self.set_mode(mode)
+    }
+
+    /// Returns the current input mode.
+    #[inline]
+    #[must_use]
+    pub const fn mode(&self) -> InputMode {
+        self.mode
+    }
+
+    /// Returns the text being composed.
+    #[inline]
+    #[must_use]
+    pub fn preedit(&self) -> &str {
+        self.buffer.view()
+    }
+
+    /// Returns all the text committed so far.
+    #[inline]
+    #[must_use]
+    pub fn committed(&self) -> &str {
+        &self.committed
+    }
+
+    /// Takes the committed text out of the composer, leaving the preedit untouched.
+    pub fn take_committed(&mut self) -> String {
+        std::mem::take(&mut self.committed)
+    }
+
+    /// Returns the underlying buffer of the syllable being composed.
+    #[inline]
+    #[must_use]
+    pub const fn buffer(&self) -> &IncrementalBuffer<'def> {
+        &self.buffer
+    }
+
+    /// Indicates whether a character ends the syllable being composed.
+    fn is_word_boundary(&self, ch: char) -> bool {
+        if ch.is_whitespace() {
+            return true;
+        }
+        if ch.is_alphabetic() {
+            return false;
+        }
+        let is_key = self
+            .definition
+            .actions(ch.to_ascii_lowercase())
+            .is_some_and(|actions| !actions.is_empty());
+        // Non-letter keys only make sense when there's something to apply them to
+        !is_key || self.buffer.is_empty()
+    }
+
+    /// Commits the preedit, followed by the boundary character if there's one.
+    fn commit_with(&mut self, boundary: Option<char>) -> Vec<ComposerEvent> {
+        let had_preedit = !self.buffer.is_empty();
+        let expanded = self.buffer.finish().abbreviation_expanded;
+        let mut text = self.buffer.view().to_owned();
+        text.extend(boundary);
+        // The abbreviation is kept until the next key, so the expansion can still be undone
+        self.expansion = expanded.then(|| {
+            let _ = self.buffer.undo_expansion();
+            let mut original = self.buffer.view().to_owned();
+            original.extend(boundary);
+            (text.clone(), original)
+        });
+        self.buffer.clear();
+
+        let mut events = Vec::with_capacity(2);
+        if !text.is_empty() {
+            self.committed.push_str(&text);
+            events.push(ComposerEvent::Commit(text));
+        }
+        if had_preedit {
+            events.push(ComposerEvent::PreeditChanged(String::new()));
+        }
+        events
+    }
+}
@@ -35,7 +35,7 @@ pub fn get_tone_mark_placement(raw_syllable: &str, accent_style: &AccentStyle) -
         Ok(result) => result,
         Err(_) => return 0, // Return 0 if parsing fails
     };
-    let vowel = &syllable.vowel;
+    let vowel = syllable.vowel.to_lowercase();
     let vowel_len = vowel.chars().count();
     let vowel_index = syllable.initial_consonant.chars().count();
     // If there's only one vowel, then it's guaranteed that the tone mark will go there
@@ -254,5 +254,13 @@ mod tests {
         let result = get_tone_mark_placement("cAu", &AccentStyle::New);
         let expected = 1;
         assert_eq!(result, expected);
+
+        let result = get_tone_mark_placement("HOA", &AccentStyle::New);
+        let expected = 2;
+        assert_eq!(result, expected);
+
+        let result = get_tone_mark_placement("NGƯƠI", &AccentStyle::New);
+        let expected = 3;
+        assert_eq!(result, expected);
     }
 }
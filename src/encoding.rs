@@ -0,0 +1,586 @@
+//! Converters for legacy Vietnamese encodings.
+//!
+//! Before Unicode was widespread, Vietnamese text was written with 8-bit encodings and font-specific layouts.
+//! Documents in these encodings are still common, and show up as garbage when read as UTF-8 or Latin-1. This
+//! module decodes them to Unicode, encodes Unicode text back, and guesses the encoding of a document.
+//!
+//! The supported encodings are listed in [`LegacyEncoding`].
+//!
+//! ## Example
+//!
+//! ```
+//! use vi::encoding::{detect_encoding, LegacyEncoding};
+//!
+//! let bytes = LegacyEncoding::VniWindows.encode("Tiếng Việt").unwrap();
+//! assert_eq!(bytes, b"Tie\xe1ng Vie\xe4t");
+//!
+//! let guess = detect_encoding(&bytes).unwrap();
+//! assert_eq!(guess.encoding, LegacyEncoding::VniWindows);
+//! assert_eq!(guess.encoding.decode(&bytes), "Tiếng Việt");
+//! ```
+use std::{collections::HashMap, fmt::Display, sync::OnceLock};
+
+use crate::{
+    accent::is_tone_well_placed,
+    maps::{ACCUTE_MAP, DOT_MAP, GRAVE_MAP, HOOK_ABOVE_MAP, TILDE_MAP, VOWELS},
+    normalize::compose,
+    parsing::extract_tone_char,
+    processor::ToneMark,
+    validation::is_valid_syllable,
+};
+
+/// A legacy Vietnamese encoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum LegacyEncoding {
+    /// TCVN 5712:1993, used by the `.Vn` (ABC) fonts. Lowercase letters are single bytes in the upper half;
+    /// uppercase letters follow the full (VN1) table, some of them replacing C0 control characters. Files
+    /// written with the uppercase-only `.VnH` fonts use the lowercase codes and decode to lowercase text.
+    Tcvn3,
+    /// The VNI-Windows font encoding, where most letters are a base letter followed by a mark byte.
+    VniWindows,
+    /// VISCII (RFC 1456), where every letter is a single byte, some of them replacing C0 control characters.
+    Viscii,
+    /// Windows-1258, where the letters that don't have a code point are written with a combining tone mark.
+    Cp1258,
+    /// VPS, from the Vietnamese Professionals Society, where every letter is a single byte. The letters of Latin-1
+    /// keep their code; the others replace the remaining Latin-1 characters and some C0 control characters.
+    Vps,
+}
+
+impl LegacyEncoding {
+    /// All the supported encodings.
+    pub const ALL: [Self; 5] = [
+        Self::Tcvn3,
+        Self::VniWindows,
+        Self::Viscii,
+        Self::Cp1258,
+        Self::Vps,
+    ];
+
+    /// Returns the usual name of the encoding.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Tcvn3 => "TCVN3",
+            Self::VniWindows => "VNI-Windows",
+            Self::Viscii => "VISCII",
+            Self::Cp1258 => "Windows-1258",
+            Self::Vps => "VPS",
+        }
+    }
+
+    /// Decodes bytes in this encoding to a Unicode string.
+    ///
+    /// Every byte sequence can be decoded. Combining tone marks are composed with their letters, and bytes
+    /// that are not part of a VNI-Windows letter are decoded as Latin-1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vi::encoding::LegacyEncoding;
+    ///
+    /// assert_eq!(LegacyEncoding::Viscii.decode(b"Vi\xaet"), "Việt");
+    /// assert_eq!(LegacyEncoding::Tcvn3.decode(b"Vi\xd6t"), "Việt");
+    /// assert_eq!(LegacyEncoding::Cp1258.decode(b"Vi\xea\xf2t"), "Việt");
+    /// assert_eq!(LegacyEncoding::Vps.decode(b"Vi\xc5t"), "Việt");
+    /// ```
+    #[must_use]
+    pub fn decode(self, bytes: &[u8]) -> String {
+        match self {
+            Self::Tcvn3 => TCVN3.decode(bytes),
+            Self::VniWindows => decode_vni(bytes),
+            Self::Viscii => VISCII.decode(bytes),
+            Self::Cp1258 => CP1258.decode(bytes),
+            Self::Vps => VPS.decode(bytes),
+        }
+    }
+
+    /// Encodes a Unicode string in this encoding.
+    ///
+    /// Decomposed letters are composed before encoding.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EncodingError::Unmappable`] if a character of the text can't be written in this encoding.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vi::encoding::LegacyEncoding;
+    ///
+    /// assert_eq!(LegacyEncoding::Viscii.encode("Việt")?, b"Vi\xaet");
+    /// assert_eq!(LegacyEncoding::Cp1258.encode("Việt")?, b"Vi\xea\xf2t");
+    /// assert!(LegacyEncoding::Viscii.encode("Việt ☕").is_err());
+    /// # Ok::<(), vi::encoding::EncodingError>(())
+    /// ```
+    pub fn encode(self, text: &str) -> Result<Vec<u8>, EncodingError> {
+        let text = compose(text);
+        let mut result = Vec::with_capacity(text.len());
+        for (index, ch) in text.char_indices() {
+            let encoded = match self {
+                Self::Tcvn3 => TCVN3.encode_char(ch).map(|byte| (byte, None)),
+                Self::VniWindows => encode_vni_char(ch),
+                Self::Viscii => VISCII.encode_char(ch).map(|byte| (byte, None)),
+                Self::Cp1258 => encode_cp1258_char(ch),
+                Self::Vps => VPS.encode_char(ch).map(|byte| (byte, None)),
+            };
+            let Some((first, second)) = encoded else {
+                return Err(EncodingError::Unmappable {
+                    character: ch,
+                    index,
+                    encoding: self,
+                });
+            };
+            result.push(first);
+            result.extend(second);
+        }
+        Ok(result)
+    }
+}
+
+impl Display for LegacyEncoding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// An error that happened while encoding text in a legacy encoding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum EncodingError {
+    /// A character of the text can't be written in the encoding.
+    Unmappable {
+        /// The character that can't be written.
+        character: char,
+        /// The byte index of the character in the text.
+        index: usize,
+        /// The encoding the text was encoded in.
+        encoding: LegacyEncoding,
+    },
+}
+
+impl Display for EncodingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EncodingError::Unmappable {
+                character,
+                index,
+                encoding,
+            } => write!(
+                f,
+                "Character {character:?} at byte {index} cannot be written in {encoding}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for EncodingError {}
+
+/// The result of [`detect_encoding`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EncodingGuess {
+    /// The most likely encoding.
+    pub encoding: LegacyEncoding,
+    /// The number of words with non-ASCII letters that are valid Vietnamese syllables once decoded.
+    pub valid_words: usize,
+    /// The number of words with non-ASCII letters once decoded.
+    pub words: usize,
+}
+
+impl EncodingGuess {
+    /// The share of the decoded words with non-ASCII letters that are valid Vietnamese syllables, between 0 and 1.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn confidence(&self) -> f64 {
+        if self.words == 0 {
+            return 0.0;
+        }
+        self.valid_words as f64 / self.words as f64
+    }
+}
+
+/// Guesses the legacy encoding of a document.
+///
+/// The bytes are decoded with every encoding, and the words with non-ASCII letters are checked against the
+/// syllable validator: a word counts when it's a valid syllable and its tone mark is where it would be typed.
+/// The encoding with the highest share of valid words wins. Returns `None` if no encoding produces a single
+/// valid word, e.g. for plain ASCII text.
+///
+/// # Examples
+///
+/// ```
+/// use vi::encoding::{detect_encoding, LegacyEncoding};
+///
+/// let guess = detect_encoding(b"Vi\xd6t Nam").unwrap();
+/// assert_eq!(guess.encoding, LegacyEncoding::Tcvn3);
+/// assert_eq!(guess.confidence(), 1.0);
+///
+/// assert_eq!(detect_encoding(b"Viet Nam"), None);
+/// ```
+#[must_use]
+pub fn detect_encoding(bytes: &[u8]) -> Option<EncodingGuess> {
+    let mut best: Option<EncodingGuess> = None;
+    for encoding in LegacyEncoding::ALL {
+        let guess = score_encoding(bytes, encoding);
+        let is_better = best.map_or(true, |best| {
+            (guess.confidence(), guess.valid_words) > (best.confidence(), best.valid_words)
+        });
+        if guess.valid_words > 0 && is_better {
+            best = Some(guess);
+        }
+    }
+    best
+}
+
+/// Decodes a document with an encoding and counts its valid Vietnamese words.
+fn score_encoding(bytes: &[u8], encoding: LegacyEncoding) -> EncodingGuess {
+    let text = encoding.decode(bytes);
+    let mut guess = EncodingGuess {
+        encoding,
+        valid_words: 0,
+        words: 0,
+    };
+    for word in text
+        .split(|ch: char| !ch.is_alphabetic())
+        .filter(|word| !word.is_ascii())
+    {
+        guess.words += 1;
+        if is_typed_syllable(word) {
+            guess.valid_words += 1;
+        }
+    }
+    guess
+}
+
+/// Checks whether a word is a valid syllable written with its tone mark in either accent style.
+fn is_typed_syllable(word: &str) -> bool {
+    is_valid_syllable(word) && is_tone_well_placed(word)
+}
+
+/// Splits a letter with a tone mark into the letter without the tone mark and the tone mark.
+fn split_tone(ch: char) -> Option<(char, ToneMark)> {
+    let tone_mark = extract_tone_char(ch)?;
+    let tone_mark_map = match tone_mark {
+        ToneMark::Acute => &ACCUTE_MAP,
+        ToneMark::Grave => &GRAVE_MAP,
+        ToneMark::HookAbove => &HOOK_ABOVE_MAP,
+        ToneMark::Tilde => &TILDE_MAP,
+        ToneMark::Underdot => &DOT_MAP,
+    };
+    tone_mark_map
+        .entries()
+        .find(|(_, toned)| **toned == ch)
+        .map(|(letter, _)| (*letter, tone_mark))
+}
+
+/// Returns the 134 Vietnamese letters that are not ASCII, lowercase first.
+fn vietnamese_letters() -> impl Iterator<Item = char> {
+    let lowercase = || {
+        VOWELS
+            .iter()
+            .copied()
+            .chain(['đ'])
+            .filter(|ch| !ch.is_ascii())
+    };
+    lowercase().chain(lowercase().flat_map(char::to_uppercase))
+}
+
+/// A single-byte encoding. The lower half is ASCII, except for some bytes that are replaced with letters.
+struct SingleByte {
+    /// The bytes of the lower half that are replaced with letters.
+    low: &'static [(u8, char)],
+    /// The characters of the upper half, from `0x80`.
+    high: [char; 128],
+}
+
+impl SingleByte {
+    fn decode(&self, bytes: &[u8]) -> String {
+        let decoded: String = bytes.iter().map(|byte| self.decode_byte(*byte)).collect();
+        compose(&decoded).into_owned()
+    }
+
+    fn decode_byte(&self, byte: u8) -> char {
+        if let Some(ch) = self.high.get(usize::from(byte).wrapping_sub(0x80)) {
+            return *ch;
+        }
+        self.low
+            .iter()
+            .find(|(low_byte, _)| *low_byte == byte)
+            .map_or(char::from(byte), |(_, ch)| *ch)
+    }
+
+    fn encode_char(&self, ch: char) -> Option<u8> {
+        if let Some((byte, _)) = self.low.iter().find(|(_, low_ch)| *low_ch == ch) {
+            return Some(*byte);
+        }
+        if let Some(index) = self.high.iter().position(|high_ch| *high_ch == ch) {
+            return u8::try_from(index + 0x80).ok();
+        }
+        u8::try_from(ch)
+            .ok()
+            .filter(|byte| byte.is_ascii() && self.low.iter().all(|(low, _)| low != byte))
+    }
+}
+
+/// Encodes a character in Windows-1258, writing the letters without a code point with a combining tone mark.
+fn encode_cp1258_char(ch: char) -> Option<(u8, Option<u8>)> {
+    if let Some(byte) = CP1258.encode_char(ch) {
+        return Some((byte, None));
+    }
+    let (letter, tone_mark) = split_tone(ch)?;
+    let tone_byte = match tone_mark {
+        ToneMark::Grave => 0xCC,
+        ToneMark::HookAbove => 0xD2,
+        ToneMark::Tilde => 0xDE,
+        ToneMark::Acute => 0xEC,
+        ToneMark::Underdot => 0xF2,
+    };
+    Some((CP1258.encode_char(letter)?, Some(tone_byte)))
+}
+
+/// The VNI-Windows letters written with a single byte, in lowercase. The uppercase byte is `0x20` lower.
+const VNI_SINGLE_LETTERS: [(char, u8); 9] = [
+    ('í', 0xED),
+    ('ì', 0xEC),
+    ('ỉ', 0xE6),
+    ('ĩ', 0xF3),
+    ('ị', 0xF2),
+    ('ỵ', 0xEE),
+    ('đ', 0xF1),
+    ('ơ', 0xF4),
+    ('ư', 0xF6),
+];
+
+/// The VNI-Windows mark bytes in lowercase, by tone mark: none, acute, grave, hook above, tilde & underdot. The
+/// uppercase byte is `0x20` lower.
+const VNI_TONE_MARKS: [u8; 6] = [0, 0xF9, 0xF8, 0xFB, 0xF5, 0xEF];
+const VNI_CIRCUMFLEX_MARKS: [u8; 6] = [0xE2, 0xE1, 0xE0, 0xE5, 0xE3, 0xE4];
+const VNI_BREVE_MARKS: [u8; 6] = [0xEA, 0xE9, 0xE8, 0xFA, 0xFC, 0xEB];
+
+/// Encodes a character in VNI-Windows, as a base byte optionally followed by a mark byte.
+fn encode_vni_char(ch: char) -> Option<(u8, Option<u8>)> {
+    if ch.is_ascii() {
+        return u8::try_from(ch).ok().map(|byte| (byte, None));
+    }
+    let Some(letter) = vietnamese_letters().find(|letter| *letter == ch) else {
+        // Other Latin-1 characters are kept as is, like when decoding.
+        return u8::try_from(ch).ok().map(|byte| (byte, None));
+    };
+
+    let lowercase = letter.to_lowercase().next().unwrap_or(letter);
+    let case_offset = if lowercase == letter { 0 } else { 0x20 };
+    let to_case = |byte: u8| byte - case_offset;
+
+    if let Some((_, byte)) = VNI_SINGLE_LETTERS.iter().find(|(ch, _)| *ch == lowercase) {
+        return Some((to_case(*byte), None));
+    }
+
+    let (base, tone_mark) = split_tone(lowercase).map_or((lowercase, None), |(base, tone_mark)| {
+        (base, Some(tone_mark))
+    });
+    let (base_byte, marks) = match base {
+        'â' => (b'a', VNI_CIRCUMFLEX_MARKS),
+        'ê' => (b'e', VNI_CIRCUMFLEX_MARKS),
+        'ô' => (b'o', VNI_CIRCUMFLEX_MARKS),
+        'ă' => (b'a', VNI_BREVE_MARKS),
+        'ơ' => (0xF4, VNI_TONE_MARKS),
+        'ư' => (0xF6, VNI_TONE_MARKS),
+        _ => (u8::try_from(base).ok()?, VNI_TONE_MARKS),
+    };
+    let [none, acute, grave, hook_above, tilde, underdot] = marks;
+    let mark_byte = match tone_mark {
+        None => none,
+        Some(ToneMark::Acute) => acute,
+        Some(ToneMark::Grave) => grave,
+        Some(ToneMark::HookAbove) => hook_above,
+        Some(ToneMark::Tilde) => tilde,
+        Some(ToneMark::Underdot) => underdot,
+    };
+    let base_byte = if base_byte.is_ascii() {
+        if case_offset == 0 {
+            base_byte
+        } else {
+            base_byte.to_ascii_uppercase()
+        }
+    } else {
+        to_case(base_byte)
+    };
+    Some((base_byte, Some(to_case(mark_byte))))
+}
+
+/// Decodes VNI-Windows bytes, matching the longest letter at each position.
+fn decode_vni(bytes: &[u8]) -> String {
+    static LETTERS: OnceLock<HashMap<(u8, Option<u8>), char>> = OnceLock::new();
+    let letters = LETTERS.get_or_init(|| {
+        vietnamese_letters()
+            .filter_map(|letter| encode_vni_char(letter).map(|bytes| (bytes, letter)))
+            .collect()
+    });
+
+    let mut result = String::with_capacity(bytes.len());
+    let mut index = 0;
+    while let Some(&byte) = bytes.get(index) {
+        let next = bytes.get(index + 1).copied();
+        if let Some(letter) = next.and_then(|next| letters.get(&(byte, Some(next)))) {
+            result.push(*letter);
+            index += 2;
+        } else {
+            result.push(
+                letters
+                    .get(&(byte, None))
+                    .copied()
+                    .unwrap_or(char::from(byte)),
+            );
+            index += 1;
+        }
+    }
+    result
+}
+
+const VISCII: SingleByte = SingleByte {
+    low: &[
+        (0x02, 'Ẳ'),
+        (0x05, 'Ẵ'),
+        (0x06, 'Ẫ'),
+        (0x14, 'Ỷ'),
+        (0x19, 'Ỹ'),
+        (0x1E, 'Ỵ'),
+    ],
+    high: [
+        'Ạ', 'Ắ', 'Ằ', 'Ặ', 'Ấ', 'Ầ', 'Ẩ', 'Ậ', 'Ẽ', 'Ẹ', 'Ế', 'Ề', 'Ể', 'Ễ', 'Ệ',
+        'Ố', // 0x80
+        'Ồ', 'Ổ', 'Ỗ', 'Ộ', 'Ợ', 'Ớ', 'Ờ', 'Ở', 'Ị', 'Ỏ', 'Ọ', 'Ỉ', 'Ủ', 'Ũ', 'Ụ',
+        'Ỳ', // 0x90
+        'Õ', 'ắ', 'ằ', 'ặ', 'ấ', 'ầ', 'ẩ', 'ậ', 'ẽ', 'ẹ', 'ế', 'ề', 'ể', 'ễ', 'ệ',
+        'ố', // 0xA0
+        'ồ', 'ổ', 'ỗ', 'Ỡ', 'Ơ', 'ộ', 'ờ', 'ở', 'ị', 'Ự', 'Ứ', 'Ừ', 'Ử', 'ơ', 'ớ',
+        'Ư', // 0xB0
+        'À', 'Á', 'Â', 'Ã', 'Ả', 'Ă', 'ẳ', 'ẵ', 'È', 'É', 'Ê', 'Ẻ', 'Ì', 'Í', 'Ĩ',
+        'ỳ', // 0xC0
+        'Đ', 'ứ', 'Ò', 'Ó', 'Ô', 'ạ', 'ỷ', 'ừ', 'ử', 'Ù', 'Ú', 'ỹ', 'ỵ', 'Ý', 'ỡ',
+        'ư', // 0xD0
+        'à', 'á', 'â', 'ã', 'ả', 'ă', 'ữ', 'ẫ', 'è', 'é', 'ê', 'ẻ', 'ì', 'í', 'ĩ',
+        'ỉ', // 0xE0
+        'đ', 'ự', 'ò', 'ó', 'ô', 'õ', 'ỏ', 'ọ', 'ụ', 'ù', 'ú', 'ũ', 'ủ', 'ý', 'ợ',
+        'Ữ', // 0xF0
+    ],
+};
+
+const TCVN3: SingleByte = SingleByte {
+    low: &[
+        (0x01, 'Ú'),
+        (0x02, 'Ụ'),
+        (0x04, 'Ừ'),
+        (0x05, 'Ử'),
+        (0x06, 'Ữ'),
+        (0x11, 'Ứ'),
+        (0x12, 'Ự'),
+        (0x13, 'Ỳ'),
+        (0x14, 'Ỷ'),
+        (0x15, 'Ỹ'),
+        (0x16, 'Ý'),
+        (0x17, 'Ỵ'),
+    ],
+    high: [
+        'À', 'Ả', 'Ã', 'Á', 'Ạ', 'Ặ', 'Ậ', 'È', 'Ẻ', 'Ẽ', 'É', 'Ẹ', 'Ệ', 'Ì', 'Ỉ',
+        'Ĩ', // 0x80
+        'Í', 'Ị', 'Ò', 'Ỏ', 'Õ', 'Ó', 'Ọ', 'Ộ', 'Ờ', 'Ở', 'Ỡ', 'Ớ', 'Ợ', 'Ù', 'Ủ',
+        'Ũ', // 0x90
+        '\u{A0}', 'Ă', 'Â', 'Ê', 'Ô', 'Ơ', 'Ư', 'Đ', 'ă', 'â', 'ê', 'ô', 'ơ', 'ư', 'đ',
+        'Ằ', // 0xA0
+        '\u{300}', '\u{309}', '\u{303}', '\u{301}', '\u{323}', 'à', 'ả', 'ã', 'á', 'ạ', 'Ẳ', 'ằ',
+        'ẳ', 'ẵ', 'ắ', 'Ẵ', // 0xB0
+        'Ắ', 'Ầ', 'Ẩ', 'Ẫ', 'Ấ', 'Ề', 'ặ', 'ầ', 'ẩ', 'ẫ', 'ấ', 'ậ', 'è', 'Ể', 'ẻ',
+        'ẽ', // 0xC0
+        'é', 'ẹ', 'ề', 'ể', 'ễ', 'ế', 'ệ', 'ì', 'ỉ', 'Ễ', 'Ế', 'Ồ', 'ĩ', 'í', 'ị',
+        'ò', // 0xD0
+        'Ổ', 'ỏ', 'õ', 'ó', 'ọ', 'ồ', 'ổ', 'ỗ', 'ố', 'ộ', 'ờ', 'ở', 'ỡ', 'ớ', 'ợ',
+        'ù', // 0xE0
+        'Ỗ', 'ủ', 'ũ', 'ú', 'ụ', 'ừ', 'ử', 'ữ', 'ứ', 'ự', 'ỳ', 'ỷ', 'ỹ', 'ý', 'ỵ',
+        'Ố', // 0xF0
+    ],
+};
+
+const VPS: SingleByte = SingleByte {
+    low: &[
+        (0x02, 'Ả'),
+        (0x04, 'Ạ'),
+        (0x05, 'Ă'),
+        (0x06, 'Ằ'),
+        (0x11, 'Ẳ'),
+        (0x12, 'Ẵ'),
+        (0x13, 'Ắ'),
+        (0x14, 'Ặ'),
+        (0x15, 'Ầ'),
+        (0x16, 'Ẩ'),
+        (0x17, 'Ẫ'),
+    ],
+    high: [
+        'Ấ', 'Ậ', 'Ẻ', 'Ẽ', 'Ẹ', 'Ề', 'Ể', 'Ễ', 'Ế', 'Ệ', 'Ỉ', 'Ĩ', 'Ị', 'Ỏ', 'Ọ',
+        'Ồ', // 0x80
+        'Ổ', 'Ỗ', 'Ố', 'Ộ', 'Ơ', 'Ờ', 'Ở', 'Ỡ', 'Ớ', 'Ợ', 'Ủ', 'Ũ', 'Ụ', 'Ư', 'Ừ',
+        'Ử', // 0x90
+        '\u{A0}', 'Ữ', 'Ứ', 'Ự', 'Ỳ', 'Ỷ', 'Ỹ', '§', 'Ỵ', '©', 'Đ', '«', 'ả', 'ạ', 'ă',
+        'ằ', // 0xA0
+        'ẳ', 'ẵ', 'ắ', 'ặ', 'ầ', 'ẩ', 'ẫ', 'ấ', 'ậ', 'ẻ', 'ẽ', '»', 'ẹ', 'ề', 'ể',
+        'ễ', // 0xB0
+        'À', 'Á', 'Â', 'Ã', 'ế', 'ệ', 'ỉ', 'ĩ', 'È', 'É', 'Ê', 'ị', 'Ì', 'Í', 'ỏ',
+        'ọ', // 0xC0
+        'ồ', 'ổ', 'Ò', 'Ó', 'Ô', 'Õ', 'ỗ', 'ố', 'ộ', 'Ù', 'Ú', 'ơ', 'ờ', 'Ý', 'ở',
+        'ỡ', // 0xD0
+        'à', 'á', 'â', 'ã', 'ớ', 'ợ', 'ủ', 'ũ', 'è', 'é', 'ê', 'ụ', 'ì', 'í', 'ư',
+        'ừ', // 0xE0
+        'ử', 'ữ', 'ò', 'ó', 'ô', 'õ', 'ứ', 'ự', 'ỳ', 'ù', 'ú', 'ỷ', 'ỹ', 'ý', 'ỵ',
+        'đ', // 0xF0
+    ],
+};
+
+const CP1258: SingleByte = SingleByte {
+    low: &[],
+    high: [
+        '€', '\u{81}', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', '\u{8A}', '‹', 'Œ', '\u{8D}',
+        '\u{8E}', '\u{8F}', // 0x80
+        '\u{90}', '‘', '’', '“', '”', '•', '–', '—', '˜', '™', '\u{9A}', '›', 'œ', '\u{9D}',
+        '\u{9E}', 'Ÿ', // 0x90
+        '\u{A0}', '¡', '¢', '£', '¤', '¥', '¦', '§', '¨', '©', 'ª', '«', '¬', '\u{AD}', '®',
+        '¯', // 0xA0
+        '°', '±', '²', '³', '´', 'µ', '¶', '·', '¸', '¹', 'º', '»', '¼', '½', '¾',
+        '¿', // 0xB0
+        'À', 'Á', 'Â', 'Ă', 'Ä', 'Å', 'Æ', 'Ç', 'È', 'É', 'Ê', 'Ë', '\u{300}', 'Í', 'Î',
+        'Ï', // 0xC0
+        'Đ', 'Ñ', '\u{309}', 'Ó', 'Ô', 'Ơ', 'Ö', '×', 'Ø', 'Ù', 'Ú', 'Û', 'Ü', 'Ư', '\u{303}',
+        'ß', // 0xD0
+        'à', 'á', 'â', 'ă', 'ä', 'å', 'æ', 'ç', 'è', 'é', 'ê', 'ë', '\u{301}', 'í', 'î',
+        'ï', // 0xE0
+        'đ', 'ñ', '\u{323}', 'ó', 'ô', 'ơ', 'ö', '÷', 'ø', 'ù', 'ú', 'û', 'ü', 'ư', '₫',
+        'ÿ', // 0xF0
+    ],
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vietnamese_letters_count() {
+        let letters: Vec<char> = vietnamese_letters().collect();
+        assert_eq!(letters.len(), 134);
+        assert!(letters.contains(&'Đ'));
+        assert!(letters.contains(&'ỵ'));
+    }
+
+    #[test]
+    fn split_tone_marks() {
+        assert_eq!(split_tone('ấ'), Some(('â', ToneMark::Acute)));
+        assert_eq!(split_tone('Ự'), Some(('Ư', ToneMark::Underdot)));
+        assert_eq!(split_tone('ă'), None);
+    }
+
+    #[test]
+    fn vni_uppercase_letters() {
+        assert_eq!(encode_vni_char('Ấ'), Some((b'A', Some(0xC1))));
+        assert_eq!(encode_vni_char('Ợ'), Some((0xD4, Some(0xCF))));
+        assert_eq!(encode_vni_char('Đ'), Some((0xD1, None)));
+    }
+}
@@ -0,0 +1,72 @@
+//! A bundled list of common English words.
+//!
+//! Typing English words with a Vietnamese typing method often produces strings that still look like Vietnamese
+//! syllables, e.g. `those` becomes `thoé` in Telex. The list is used by
+//! [`IncrementalBuffer::finish`](crate::methods::IncrementalBuffer::finish) to recognise these words and restore
+//! their keystrokes.
+//!
+//! The words are stored front-coded, each word only keeping the characters it doesn't share with the previous
+//! one, and are decoded the first time the list is looked up.
+use std::{collections::HashSet, sync::OnceLock};
+
+/// The front-coded word list. See the header of the file for the format.
+static ENGLISH_WORDS: &str = include_str!("english_words.txt");
+
+static WORD_SET: OnceLock<HashSet<String>> = OnceLock::new();
+
+/// Checks whether a word is in the bundled English word list. The comparison is case-insensitive.
+///
+/// # Examples
+///
+/// ```
+/// use vi::english::is_english_word;
+///
+/// assert!(is_english_word("window"));
+/// assert!(is_english_word("Those"));
+/// assert!(!is_english_word("việt"));
+/// ```
+#[must_use]
+pub fn is_english_word(word: &str) -> bool {
+    if !word.is_ascii() {
+        return false;
+    }
+    WORD_SET
+        .get_or_init(|| decode(ENGLISH_WORDS).collect())
+        .contains(&word.to_ascii_lowercase())
+}
+
+/// Decodes a front-coded word list, skipping comments and malformed lines.
+fn decode(table: &str) -> impl Iterator<Item = String> + '_ {
+    let mut previous = String::new();
+    table
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(move |line| {
+            let mut chars = line.chars();
+            let shared = chars.next()?.to_digit(36)? as usize;
+            previous.truncate(shared);
+            previous.push_str(chars.as_str());
+            Some(previous.clone())
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_front_coded_words() {
+        let words: Vec<String> = decode("# comment\n0this\n2ose\n1o\n").collect();
+        assert_eq!(words, vec!["this", "those", "to"]);
+    }
+
+    #[test]
+    fn bundled_words_are_sorted_and_lowercase() {
+        let words: Vec<String> = decode(ENGLISH_WORDS).collect();
+        assert!(words.len() > 500);
+        assert!(words.windows(2).all(|pair| matches!(pair, [a, b] if a < b)));
+        assert!(words
+            .iter()
+            .all(|word| word.chars().all(|ch| ch.is_ascii_lowercase())));
+    }
+}
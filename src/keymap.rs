@@ -0,0 +1,640 @@
+//! Typing definitions that are built at runtime.
+//!
+//! A [`Definition`] is a static [`phf::Map`], so it can only be declared at compile time. When the key bindings
+//! come from a user or an admin, use an [`OwnedDefinition`] instead. It can be used anywhere a static definition
+//! can, since both implement [`TypingDefinition`].
+//!
+//! ## Key-map format
+//!
+//! An [`OwnedDefinition`] can be loaded from a simple TOML-like key-map where every line maps a key to a list of
+//! actions, in the order they should be tried:
+//!
+//! ```text
+//! # Comments start with `#` or `;`. A `[keys]` table header is allowed and ignored.
+//! s = AddTonemark(Acute)
+//! w = [ResetInsertedƯ, ModifyLetter(Horn), ModifyLetter(Breve), InsertƯ]
+//! a = ModifyLetterOnCharacterFamily(Circumflex, a)
+//! '[' = InsertWithHorn(o)
+//! "\\" = EscapeNext
+//! ```
+//!
+//! Action names are the same as the [`Action`] variants. `InsertU` and `ResetInsertedU` are accepted as ASCII
+//! spellings of `InsertƯ` and `ResetInsertedƯ`. Keys that are not a single letter or digit can be quoted.
+//!
+//! Key-map files exported by `UniKey` (`key = Tone1`, `key = Roof-A`, ...) can be imported with
+//! [`OwnedDefinition::from_unikey_keymap`].
+//!
+//! ## Example
+//!
+//! ```
+//! use vi::{keymap::OwnedDefinition, methods::transform_buffer};
+//!
+//! let definition: OwnedDefinition = "
+//!     s = AddTonemark(Acute)
+//!     a = ModifyLetterOnCharacterFamily(Circumflex, a)
+//! "
+//! .parse()?;
+//!
+//! let mut result = String::new();
+//! transform_buffer(&definition, "caas".chars(), &mut result);
+//! assert_eq!(result, "cấ");
+//! # Ok::<(), vi::keymap::KeymapError>(())
+//! ```
+use std::{collections::HashMap, fmt::Display, path::Path, str::FromStr};
+
+use crate::{
+    methods::{Action, Definition, TypingDefinition},
+    processor::{LetterModification, ToneMark},
+};
+
+/// An error that happened while loading a key-map.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum KeymapError {
+    /// The key-map file cannot be read.
+    Io(std::io::Error),
+    /// A line is not in the `key = actions` form.
+    InvalidLine {
+        /// The line number, starting from 1.
+        line: usize,
+    },
+    /// A key is not exactly one character.
+    InvalidKey {
+        /// The line number, starting from 1.
+        line: usize,
+        /// The key as written in the key-map.
+        key: String,
+    },
+    /// A key is defined more than once.
+    DuplicateKey {
+        /// The line number, starting from 1.
+        line: usize,
+        /// The duplicated key.
+        key: char,
+    },
+    /// An action name or one of its arguments is not recognised.
+    UnknownAction {
+        /// The line number, starting from 1.
+        line: usize,
+        /// The action as written in the key-map.
+        action: String,
+    },
+    /// A key is mapped to no action at all.
+    EmptyActionList {
+        /// The line number, starting from 1.
+        line: usize,
+    },
+}
+
+impl Display for KeymapError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KeymapError::Io(error) => write!(f, "Cannot read key-map: {error}"),
+            KeymapError::InvalidLine { line } => {
+                write!(f, "Line {line}: expected `key = actions`")
+            }
+            KeymapError::InvalidKey { line, key } => {
+                write!(f, "Line {line}: key `{key}` must be a single character")
+            }
+            KeymapError::DuplicateKey { line, key } => {
+                write!(f, "Line {line}: key `{key}` is already defined")
+            }
+            KeymapError::UnknownAction { line, action } => {
+                write!(f, "Line {line}: unknown action `{action}`")
+            }
+            KeymapError::EmptyActionList { line } => {
+                write!(
+                    f,
+                    "Line {line}: a key must be mapped to at least one action"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for KeymapError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            KeymapError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for KeymapError {
+    fn from(error: std::io::Error) -> Self {
+        KeymapError::Io(error)
+    }
+}
+
+/// A typing definition that is built at runtime.
+///
+/// Keys are stored in lowercase, the same way [`IncrementalBuffer`](crate::methods::IncrementalBuffer) looks
+/// them up.
+///
+/// # Examples
+///
+/// ```
+/// use vi::{keymap::OwnedDefinition, methods::transform_buffer, TELEX};
+///
+/// // Start from the built-in telex and remove the standalone `w`
+/// let mut definition = OwnedDefinition::from(&TELEX);
+/// definition.remove('w');
+///
+/// let mut result = String::new();
+/// transform_buffer(&definition, "w".chars(), &mut result);
+/// assert_eq!(result, "w");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OwnedDefinition {
+    actions: HashMap<char, Vec<Action>>,
+}
+
+impl OwnedDefinition {
+    /// Creates an empty definition.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maps a key to a list of actions, returning the actions previously mapped to that key.
+    ///
+    /// An empty action list makes the key behave as a regular character.
+    pub fn insert(&mut self, key: char, actions: Vec<Action>) -> Option<Vec<Action>> {
+        self.actions.insert(key.to_ascii_lowercase(), actions)
+    }
+
+    /// Removes a key from the definition, returning the actions that were mapped to it.
+    pub fn remove(&mut self, key: char) -> Option<Vec<Action>> {
+        self.actions.remove(&key.to_ascii_lowercase())
+    }
+
+    /// The number of keys in the definition.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.actions.len()
+    }
+
+    /// Indicates whether the definition has no key.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
+
+    /// Loads a definition from a key-map file in the format described in the [module documentation](self).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`KeymapError::Io`] if the file cannot be read, or any other [`KeymapError`] with the line
+    /// number if the content is invalid.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, KeymapError> {
+        std::fs::read_to_string(path)?.parse()
+    }
+
+    /// Loads a definition from a key-map file exported by `UniKey`.
+    ///
+    /// # Errors
+    ///
+    /// See [`OwnedDefinition::from_unikey_keymap`].
+    pub fn load_unikey<P: AsRef<Path>>(path: P) -> Result<Self, KeymapError> {
+        Self::from_unikey_keymap(&std::fs::read_to_string(path)?)
+    }
+
+    /// Parses a key-map exported by `UniKey`, where every line maps a key to a single event name such as
+    /// `Tone1`, `Roof-A`, `Hook-Bowl`, `D-Mark` or `Telex-W`. Comments start with `;`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`KeymapError`] with the line number if a line is malformed, an event is not supported or a
+    /// key is defined twice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vi::{keymap::OwnedDefinition, methods::transform_buffer};
+    ///
+    /// let definition = OwnedDefinition::from_unikey_keymap(
+    ///     ";UniKey user-defined key mapping\n1 = Tone1\n6 = Roof-All\n",
+    /// )?;
+    ///
+    /// let mut result = String::new();
+    /// transform_buffer(&definition, "to61i".chars(), &mut result);
+    /// assert_eq!(result, "tối");
+    /// # Ok::<(), vi::keymap::KeymapError>(())
+    /// ```
+    pub fn from_unikey_keymap(input: &str) -> Result<Self, KeymapError> {
+        let mut definition = Self::new();
+        for (index, raw_line) in input.lines().enumerate() {
+            let line = index + 1;
+            let raw_line = raw_line.trim();
+            if raw_line.is_empty() || raw_line.starts_with(';') {
+                continue;
+            }
+
+            let (key, event) = split_key(raw_line, line)?;
+            let actions =
+                unikey_actions(event.trim()).ok_or_else(|| KeymapError::UnknownAction {
+                    line,
+                    action: event.trim().to_string(),
+                })?;
+            definition.insert_new(key, actions, line)?;
+        }
+        Ok(definition)
+    }
+
+    fn insert_new(
+        &mut self,
+        key: char,
+        actions: Vec<Action>,
+        line: usize,
+    ) -> Result<(), KeymapError> {
+        let key = key.to_ascii_lowercase();
+        if self.actions.contains_key(&key) {
+            return Err(KeymapError::DuplicateKey { line, key });
+        }
+        self.actions.insert(key, actions);
+        Ok(())
+    }
+}
+
+impl TypingDefinition for OwnedDefinition {
+    #[inline]
+    fn actions(&self, key: char) -> Option<&[Action]> {
+        self.actions.get(&key).map(Vec::as_slice)
+    }
+}
+
+impl From<&Definition> for OwnedDefinition {
+    fn from(definition: &Definition) -> Self {
+        Self {
+            actions: definition
+                .entries()
+                .map(|(key, actions)| (*key, actions.to_vec()))
+                .collect(),
+        }
+    }
+}
+
+impl FromStr for OwnedDefinition {
+    type Err = KeymapError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut definition = Self::new();
+        for (index, raw_line) in input.lines().enumerate() {
+            let line = index + 1;
+            let raw_line = raw_line.trim();
+            if raw_line.is_empty()
+                || raw_line.starts_with('#')
+                || raw_line.starts_with(';')
+                || (raw_line.starts_with('[') && raw_line.ends_with(']') && !raw_line.contains('='))
+            {
+                continue;
+            }
+
+            let (key, value) = split_key(raw_line, line)?;
+            let value = value.trim();
+            let value = value
+                .strip_prefix('[')
+                .and_then(|value| value.strip_suffix(']'))
+                .unwrap_or(value);
+
+            let actions = split_top_level(value)
+                .into_iter()
+                .map(|action| action.trim().trim_matches('"').trim())
+                .filter(|action| !action.is_empty())
+                .map(|action| {
+                    parse_action(action).ok_or_else(|| KeymapError::UnknownAction {
+                        line,
+                        action: action.to_string(),
+                    })
+                })
+                .collect::<Result<Vec<Action>, KeymapError>>()?;
+
+            if actions.is_empty() {
+                return Err(KeymapError::EmptyActionList { line });
+            }
+            definition.insert_new(key, actions, line)?;
+        }
+        Ok(definition)
+    }
+}
+
+/// Splits a `key = value` line into the key character and the raw value.
+fn split_key(line_content: &str, line: usize) -> Result<(char, &str), KeymapError> {
+    let (raw_key, value) = if let Some(quote @ ('\'' | '"')) = line_content.chars().next() {
+        let rest = &line_content[quote.len_utf8()..];
+        let closing = find_closing_quote(rest, quote).ok_or(KeymapError::InvalidLine { line })?;
+        let value = rest[closing + quote.len_utf8()..]
+            .trim_start()
+            .strip_prefix('=')
+            .ok_or(KeymapError::InvalidLine { line })?;
+        (unescape(&rest[..closing]), value)
+    } else {
+        let (key, value) = line_content
+            .split_once('=')
+            .ok_or(KeymapError::InvalidLine { line })?;
+        (key.trim().to_string(), value)
+    };
+
+    let mut chars = raw_key.chars();
+    match (chars.next(), chars.next()) {
+        (Some(key), None) => Ok((key, value)),
+        _ => Err(KeymapError::InvalidKey { line, key: raw_key }),
+    }
+}
+
+/// Finds the byte index of the closing quote, skipping escaped characters.
+fn find_closing_quote(input: &str, quote: char) -> Option<usize> {
+    let mut escaped = false;
+    for (index, ch) in input.char_indices() {
+        match ch {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            _ if ch == quote => return Some(index),
+            _ => {}
+        }
+    }
+    None
+}
+
+pub(crate) fn unescape(input: &str) -> String {
+    let mut result = String::with_capacity(input.len());
+    let mut chars = input.chars();
+    while let Some(ch) = chars.next() {
+        if ch == '\\' {
+            if let Some(escaped) = chars.next() {
+                result.push(escaped);
+                continue;
+            }
+        }
+        result.push(ch);
+    }
+    result
+}
+
+/// Splits a list of actions on the commas that are not inside parentheses.
+fn split_top_level(input: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (index, ch) in input.char_indices() {
+        match ch {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                parts.push(&input[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&input[start..]);
+    parts
+}
+
+fn parse_action(input: &str) -> Option<Action> {
+    let (name, arguments) = match input.split_once('(') {
+        Some((name, rest)) => (name.trim(), rest.strip_suffix(')')?.trim()),
+        None => (input, ""),
+    };
+    let mut arguments = arguments
+        .split(',')
+        .map(str::trim)
+        .filter(|argument| !argument.is_empty());
+    let first = arguments.next();
+    let second = arguments.next();
+    if arguments.next().is_some() {
+        return None;
+    }
+
+    let action = match (name, first, second) {
+        ("AddTonemark", Some(tone_mark), None) => Action::AddTonemark(parse_tone_mark(tone_mark)?),
+        ("ModifyLetter", Some(modification), None) => {
+            Action::ModifyLetter(parse_letter_modification(modification)?)
+        }
+        ("ModifyLetterOnCharacterFamily", Some(modification), Some(family)) => {
+            Action::ModifyLetterOnCharacterFamily(
+                parse_letter_modification(modification)?,
+                parse_char_argument(family)?,
+            )
+        }
+        ("InsertWithHorn", Some(letter), None) => {
+            let letter = parse_char_argument(letter)?;
+            if !matches!(letter, 'u' | 'o' | 'U' | 'O') {
+                return None;
+            }
+            Action::InsertWithHorn(letter)
+        }
+        ("InsertƯ" | "InsertU", None, None) => Action::InsertƯ,
+        ("ResetInsertedƯ" | "ResetInsertedU", None, None) => Action::ResetInsertedƯ,
+        ("RemoveToneMark", None, None) => Action::RemoveToneMark,
+        ("EscapeNext", None, None) => Action::EscapeNext,
+        _ => return None,
+    };
+    Some(action)
+}
+
+fn parse_tone_mark(input: &str) -> Option<ToneMark> {
+    match input {
+        "Acute" => Some(ToneMark::Acute),
+        "Grave" => Some(ToneMark::Grave),
+        "HookAbove" => Some(ToneMark::HookAbove),
+        "Tilde" => Some(ToneMark::Tilde),
+        "Underdot" => Some(ToneMark::Underdot),
+        _ => None,
+    }
+}
+
+fn parse_letter_modification(input: &str) -> Option<LetterModification> {
+    match input {
+        "Circumflex" => Some(LetterModification::Circumflex),
+        "Breve" => Some(LetterModification::Breve),
+        "Horn" => Some(LetterModification::Horn),
+        "Dyet" => Some(LetterModification::Dyet),
+        _ => None,
+    }
+}
+
+/// Parses a single character argument, which can be quoted.
+fn parse_char_argument(input: &str) -> Option<char> {
+    let input = input.trim_matches(|c| c == '\'' || c == '"');
+    let mut chars = input.chars();
+    match (chars.next(), chars.next()) {
+        (Some(ch), None) => Some(ch),
+        _ => None,
+    }
+}
+
+/// Maps a `UniKey` key-map event name to the equivalent list of actions.
+fn unikey_actions(event: &str) -> Option<Vec<Action>> {
+    let actions = match event {
+        "Tone0" => vec![Action::RemoveToneMark],
+        "Tone1" => vec![Action::AddTonemark(ToneMark::Acute)],
+        "Tone2" => vec![Action::AddTonemark(ToneMark::Grave)],
+        "Tone3" => vec![Action::AddTonemark(ToneMark::HookAbove)],
+        "Tone4" => vec![Action::AddTonemark(ToneMark::Tilde)],
+        "Tone5" => vec![Action::AddTonemark(ToneMark::Underdot)],
+        "Roof-All" => vec![Action::ModifyLetter(LetterModification::Circumflex)],
+        "Roof-A" => vec![Action::ModifyLetterOnCharacterFamily(
+            LetterModification::Circumflex,
+            'a',
+        )],
+        "Roof-E" => vec![Action::ModifyLetterOnCharacterFamily(
+            LetterModification::Circumflex,
+            'e',
+        )],
+        "Roof-O" => vec![Action::ModifyLetterOnCharacterFamily(
+            LetterModification::Circumflex,
+            'o',
+        )],
+        "Hook-Bowl" => vec![
+            Action::ModifyLetter(LetterModification::Horn),
+            Action::ModifyLetter(LetterModification::Breve),
+        ],
+        "Hook-UO" => vec![Action::ModifyLetter(LetterModification::Horn)],
+        "Hook-U" => vec![Action::ModifyLetterOnCharacterFamily(
+            LetterModification::Horn,
+            'u',
+        )],
+        "Hook-O" => vec![Action::ModifyLetterOnCharacterFamily(
+            LetterModification::Horn,
+            'o',
+        )],
+        "Bowl" => vec![Action::ModifyLetter(LetterModification::Breve)],
+        "D-Mark" => vec![Action::ModifyLetter(LetterModification::Dyet)],
+        "Telex-W" => vec![
+            Action::ResetInsertedƯ,
+            Action::ModifyLetter(LetterModification::Horn),
+            Action::ModifyLetter(LetterModification::Breve),
+            Action::InsertƯ,
+        ],
+        "Escape" => vec![Action::EscapeNext],
+        "u+" => vec![Action::InsertWithHorn('u')],
+        "U+" => vec![Action::InsertWithHorn('U')],
+        "o+" => vec![Action::InsertWithHorn('o')],
+        "O+" => vec![Action::InsertWithHorn('O')],
+        _ => return None,
+    };
+    Some(actions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{methods::transform_buffer, TELEX};
+
+    fn transform(definition: &OwnedDefinition, input: &str) -> String {
+        let mut result = String::new();
+        transform_buffer(definition, input.chars(), &mut result);
+        result
+    }
+
+    #[test]
+    fn parse_keymap() {
+        let definition: OwnedDefinition = r#"
+            # A telex-like definition
+            [keys]
+            s = AddTonemark(Acute)
+            "w" = ["ResetInsertedU", "ModifyLetter(Horn)", "ModifyLetter(Breve)", "InsertU"]
+            a = ModifyLetterOnCharacterFamily(Circumflex, 'a')
+            '[' = InsertWithHorn(o)
+            "\\" = EscapeNext
+        "#
+        .parse()
+        .unwrap();
+
+        assert_eq!(definition.len(), 5);
+        assert_eq!(transform(&definition, "tuwowis"), "tưới");
+        assert_eq!(transform(&definition, "caas"), "cấ");
+        assert_eq!(transform(&definition, "t["), "tơ");
+        assert_eq!(transform(&definition, "a\\s"), "as");
+    }
+
+    #[test]
+    fn parse_keymap_uppercase_key() {
+        let definition: OwnedDefinition = "S = AddTonemark(Acute)".parse().unwrap();
+        assert_eq!(transform(&definition, "as"), "á");
+        assert_eq!(transform(&definition, "aS"), "á");
+    }
+
+    #[test]
+    fn parse_keymap_unknown_action() {
+        let result =
+            "s = AddTonemark(Acute)\n\nf = AddTonemark(Falling)".parse::<OwnedDefinition>();
+        assert!(matches!(
+            result,
+            Err(KeymapError::UnknownAction { line: 3, ref action }) if action == "AddTonemark(Falling)"
+        ));
+    }
+
+    #[test]
+    fn parse_keymap_empty_action_list() {
+        let result = "# empty\ns = []".parse::<OwnedDefinition>();
+        assert!(matches!(
+            result,
+            Err(KeymapError::EmptyActionList { line: 2 })
+        ));
+
+        let result = "s =".parse::<OwnedDefinition>();
+        assert!(matches!(
+            result,
+            Err(KeymapError::EmptyActionList { line: 1 })
+        ));
+    }
+
+    #[test]
+    fn parse_keymap_invalid_lines() {
+        let result = "ss = RemoveToneMark".parse::<OwnedDefinition>();
+        assert!(matches!(
+            result,
+            Err(KeymapError::InvalidKey { line: 1, .. })
+        ));
+
+        let result = "s RemoveToneMark".parse::<OwnedDefinition>();
+        assert!(matches!(result, Err(KeymapError::InvalidLine { line: 1 })));
+
+        let result = "s = RemoveToneMark\nS = EscapeNext".parse::<OwnedDefinition>();
+        assert!(matches!(
+            result,
+            Err(KeymapError::DuplicateKey { line: 2, key: 's' })
+        ));
+    }
+
+    #[test]
+    fn parse_unikey_keymap() {
+        let definition = OwnedDefinition::from_unikey_keymap(
+            ";UniKey user-defined key mapping\n\
+             s = Tone1\nf = Tone2\nr = Tone3\nx = Tone4\nj = Tone5\nz = Tone0\n\
+             a = Roof-A\ne = Roof-E\no = Roof-O\nw = Telex-W\nd = D-Mark\n\
+             [ = o+\n] = u+\n",
+        )
+        .unwrap();
+
+        assert_eq!(transform(&definition, "vieetj"), "việt");
+        assert_eq!(transform(&definition, "nguwowif"), "người");
+        assert_eq!(transform(&definition, "th]["), "thươ");
+    }
+
+    #[test]
+    fn parse_unikey_keymap_unknown_event() {
+        let result = OwnedDefinition::from_unikey_keymap("s = Tone1\nq = Tone9");
+        assert!(matches!(
+            result,
+            Err(KeymapError::UnknownAction { line: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn owned_definition_from_static() {
+        let definition = OwnedDefinition::from(&TELEX);
+        assert_eq!(definition.len(), TELEX.len());
+        assert_eq!(transform(&definition, "vieetj"), "việt");
+    }
+
+    #[test]
+    fn owned_definition_empty_actions() {
+        let mut definition = OwnedDefinition::new();
+        definition.insert('s', Vec::new());
+        assert_eq!(transform(&definition, "as"), "as");
+    }
+}
@@ -0,0 +1,397 @@
+//! Generating the keystrokes that type a Vietnamese text.
+//!
+//! This is the reverse of [`transform_buffer`]: given a typing definition and a Vietnamese text, it finds the
+//! keys to press to type the text, e.g. `việt` is typed `vieetj` in Telex and `vie6t5` in VNI. This is useful to
+//! build test corpora, to type text automatically in UI tests, or to show the keystrokes in a typing tutor.
+//!
+//! Every character is decomposed into a base letter, a letter modification and a tone mark using the maps of
+//! the [`maps`](crate::maps) module, and the keys of these actions are looked up in the definition. The
+//! keystrokes of every word are checked by transforming them back with [`transform_buffer`].
+//!
+//! ## Example
+//!
+//! ```
+//! use vi::keystrokes::{to_keystrokes, KeystrokeMode};
+//!
+//! let keystrokes = to_keystrokes(&vi::TELEX, "Tiếng Việt", KeystrokeMode::Canonical)?;
+//! assert_eq!(keystrokes, "Tieengs Vieetj");
+//!
+//! let keystrokes = to_keystrokes(&vi::VNI, "Tiếng Việt", KeystrokeMode::Minimal)?;
+//! assert_eq!(keystrokes, "Tieng61 Viet65");
+//! # Ok::<(), vi::keystrokes::KeystrokeError>(())
+//! ```
+use std::fmt::Display;
+
+use crate::{
+    maps::{
+        ACCUTE_MAP, BREVE_MAP, CIRCUMFLEX_MAP, DOT_MAP, DYET_MAP, GRAVE_MAP, HOOK_ABOVE_MAP,
+        HORN_MAP, TILDE_MAP,
+    },
+    methods::{transform_buffer_with_style, Action, TypingDefinition},
+    parsing::extract_tone_char,
+    processor::{AccentStyle, LetterModification, ToneMark},
+};
+
+/// Determines which keystrokes are generated when there's more than one way to type a word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum KeystrokeMode {
+    /// Every letter modification is typed right after its letter, and the tone mark at the end of the word,
+    /// e.g. `nguwowif` for `người` in Telex.
+    #[default]
+    Canonical,
+    /// The shortest keystrokes that type the word, e.g. `ngwoif` for `người` in Telex. Letter modifications
+    /// and tone marks are typed at the end of the word when it's as short.
+    Minimal,
+}
+
+/// An error that happened while generating keystrokes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum KeystrokeError {
+    /// The typing definition has no key for an action needed to type a word.
+    MissingKey {
+        /// The word that cannot be typed.
+        word: String,
+        /// The action that has no key.
+        action: Action,
+    },
+    /// None of the generated keystrokes are transformed back into the word.
+    Untypeable {
+        /// The word that cannot be typed.
+        word: String,
+    },
+}
+
+impl Display for KeystrokeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KeystrokeError::MissingKey { word, action } => {
+                write!(f, "Cannot type `{word}`: no key for {action:?}")
+            }
+            KeystrokeError::Untypeable { word } => {
+                write!(f, "Cannot type `{word}` with this typing definition")
+            }
+        }
+    }
+}
+
+impl std::error::Error for KeystrokeError {}
+
+/// Generates the keystrokes that type a text using a typing definition.
+///
+/// Words are typed one by one, and the characters between them are kept as is. If a character between words
+/// is a key of the definition and the definition has an [`Action::EscapeNext`] key, it's escaped.
+///
+/// # Errors
+///
+/// Returns a [`KeystrokeError`] if a word of the text cannot be typed with the definition.
+///
+/// # Examples
+///
+/// ```
+/// use vi::keystrokes::{to_keystrokes, KeystrokeMode};
+///
+/// let keystrokes = to_keystrokes(&vi::VIQR, "Người ta.", KeystrokeMode::Canonical)?;
+/// assert_eq!(keystrokes, "Ngu+o+i` ta\\.");
+/// # Ok::<(), vi::keystrokes::KeystrokeError>(())
+/// ```
+pub fn to_keystrokes<D: TypingDefinition>(
+    definition: &D,
+    text: &str,
+    mode: KeystrokeMode,
+) -> Result<String, KeystrokeError> {
+    to_keystrokes_with_style(definition, AccentStyle::default(), text, mode)
+}
+
+/// Generates the keystrokes that type a text using a typing definition, with the accent style the text is
+/// written in.
+///
+/// # Errors
+///
+/// Returns a [`KeystrokeError`] if a word of the text cannot be typed with the definition.
+///
+/// # Examples
+///
+/// ```
+/// use vi::{keystrokes::{to_keystrokes_with_style, KeystrokeMode}, processor::AccentStyle};
+///
+/// let keystrokes = to_keystrokes_with_style(&vi::TELEX, AccentStyle::Old, "hòa", KeystrokeMode::Canonical)?;
+/// assert_eq!(keystrokes, "hoaf");
+/// # Ok::<(), vi::keystrokes::KeystrokeError>(())
+/// ```
+pub fn to_keystrokes_with_style<D: TypingDefinition>(
+    definition: &D,
+    accent_style: AccentStyle,
+    text: &str,
+    mode: KeystrokeMode,
+) -> Result<String, KeystrokeError> {
+    let escape_key = find_key(definition, |action| *action == Action::EscapeNext);
+
+    let mut keystrokes = String::with_capacity(text.len() * 2);
+    let mut word = String::new();
+    for ch in text.chars() {
+        if ch.is_alphabetic() {
+            word.push(ch);
+            continue;
+        }
+        if !word.is_empty() {
+            keystrokes.push_str(&word_to_keystrokes_with_style(
+                definition,
+                accent_style.clone(),
+                &word,
+                mode,
+            )?);
+            word.clear();
+        }
+        if let Some(escape_key) = escape_key.filter(|_| is_key(definition, ch)) {
+            keystrokes.push(escape_key);
+        }
+        keystrokes.push(ch);
+    }
+    if !word.is_empty() {
+        keystrokes.push_str(&word_to_keystrokes_with_style(
+            definition,
+            accent_style,
+            &word,
+            mode,
+        )?);
+    }
+    Ok(keystrokes)
+}
+
+/// Generates the keystrokes that type a single word using a typing definition.
+///
+/// # Errors
+///
+/// Returns a [`KeystrokeError`] if the word cannot be typed with the definition.
+///
+/// # Examples
+///
+/// ```
+/// use vi::keystrokes::{word_to_keystrokes, KeystrokeMode};
+///
+/// assert_eq!(word_to_keystrokes(&vi::TELEX, "người", KeystrokeMode::Canonical)?, "nguwowif");
+/// assert_eq!(word_to_keystrokes(&vi::TELEX, "người", KeystrokeMode::Minimal)?, "ngwoif");
+/// assert_eq!(word_to_keystrokes(&vi::VNI, "người", KeystrokeMode::Minimal)?, "nguoi72");
+/// # Ok::<(), vi::keystrokes::KeystrokeError>(())
+/// ```
+pub fn word_to_keystrokes<D: TypingDefinition>(
+    definition: &D,
+    word: &str,
+    mode: KeystrokeMode,
+) -> Result<String, KeystrokeError> {
+    word_to_keystrokes_with_style(definition, AccentStyle::default(), word, mode)
+}
+
+fn word_to_keystrokes_with_style<D: TypingDefinition>(
+    definition: &D,
+    accent_style: AccentStyle,
+    word: &str,
+    mode: KeystrokeMode,
+) -> Result<String, KeystrokeError> {
+    let inline = keystrokes_for(definition, word, true)?;
+    let deferred = keystrokes_for(definition, word, false)?;
+    let types_word = move |keystrokes: &[char]| {
+        let mut output = String::new();
+        transform_buffer_with_style(
+            definition,
+            accent_style.clone(),
+            keystrokes.iter().copied(),
+            &mut output,
+        );
+        output == word
+    };
+
+    let keystrokes = match mode {
+        KeystrokeMode::Canonical => [inline, deferred]
+            .into_iter()
+            .find(|keystrokes| types_word(keystrokes)),
+        KeystrokeMode::Minimal => [deferred, inline]
+            .into_iter()
+            .filter(|keystrokes| types_word(keystrokes))
+            .map(|keystrokes| shorten(keystrokes, &types_word))
+            .min_by_key(Vec::len),
+    };
+
+    keystrokes
+        .map(|keystrokes| keystrokes.into_iter().collect())
+        .ok_or_else(|| KeystrokeError::Untypeable {
+            word: word.to_owned(),
+        })
+}
+
+/// Builds the keystrokes of a word, with the letter modifications typed either right after their letter
+/// or at the end of the word. The tone mark is always typed last.
+fn keystrokes_for<D: TypingDefinition>(
+    definition: &D,
+    word: &str,
+    inline: bool,
+) -> Result<Vec<char>, KeystrokeError> {
+    let missing_key = |action: Action| KeystrokeError::MissingKey {
+        word: word.to_owned(),
+        action,
+    };
+
+    let mut keystrokes = Vec::with_capacity(word.len() + 2);
+    let mut modification_keys = Vec::new();
+    let mut tone_key = None;
+
+    for ch in word.chars() {
+        let (letter, modification, tone_mark) = decompose(ch);
+        keystrokes.push(letter);
+
+        if let Some(modification) = modification {
+            let key = modification_key(definition, letter, modification)
+                .ok_or_else(|| missing_key(Action::ModifyLetter(modification)))?;
+            let key = match_case(key, letter);
+            if inline {
+                keystrokes.push(key);
+            } else {
+                modification_keys.push(key);
+            }
+        }
+
+        if let Some(tone_mark) = tone_mark {
+            let key = find_key(definition, |action| {
+                *action == Action::AddTonemark(tone_mark)
+            })
+            .ok_or_else(|| missing_key(Action::AddTonemark(tone_mark)))?;
+            tone_key = Some(match_case(key, letter));
+        }
+    }
+
+    // A single key applies the same modification to every letter that needs it, e.g. `ươ`
+    modification_keys.dedup();
+    keystrokes.extend(modification_keys);
+    keystrokes.extend(tone_key);
+    Ok(keystrokes)
+}
+
+/// Removes keystrokes one by one as long as the remaining ones still type the word.
+fn shorten(mut keystrokes: Vec<char>, types_word: impl Fn(&[char]) -> bool) -> Vec<char> {
+    let mut index = 0;
+    while index < keystrokes.len() {
+        let removed = keystrokes.remove(index);
+        if types_word(&keystrokes) {
+            continue;
+        }
+        keystrokes.insert(index, removed);
+        index += 1;
+    }
+    keystrokes
+}
+
+/// Splits a character into its base letter, letter modification and tone mark.
+fn decompose(ch: char) -> (char, Option<LetterModification>, Option<ToneMark>) {
+    let tone_mark = extract_tone_char(ch);
+    let untoned = tone_mark.map_or(ch, |tone_mark| {
+        let tone_mark_map = match tone_mark {
+            ToneMark::Acute => &ACCUTE_MAP,
+            ToneMark::Grave => &GRAVE_MAP,
+            ToneMark::HookAbove => &HOOK_ABOVE_MAP,
+            ToneMark::Tilde => &TILDE_MAP,
+            ToneMark::Underdot => &DOT_MAP,
+        };
+        tone_mark_map
+            .entries()
+            .find(|(_, toned)| **toned == ch)
+            .map_or(ch, |(untoned, _)| *untoned)
+    });
+
+    [
+        (&CIRCUMFLEX_MAP, LetterModification::Circumflex),
+        (&HORN_MAP, LetterModification::Horn),
+        (&BREVE_MAP, LetterModification::Breve),
+        (&DYET_MAP, LetterModification::Dyet),
+    ]
+    .into_iter()
+    .find_map(|(map, modification)| {
+        map.entries()
+            .find(|(_, modified)| **modified == untoned)
+            .map(|(letter, _)| (*letter, Some(modification), tone_mark))
+    })
+    .unwrap_or((untoned, None, tone_mark))
+}
+
+/// Finds the key of a letter modification, preferring a key that only applies to the family of the letter.
+fn modification_key<D: TypingDefinition>(
+    definition: &D,
+    letter: char,
+    modification: LetterModification,
+) -> Option<char> {
+    let family = letter.to_ascii_lowercase();
+    find_key(definition, |action| {
+        *action == Action::ModifyLetterOnCharacterFamily(modification, family)
+    })
+    .or_else(|| {
+        find_key(definition, |action| {
+            *action == Action::ModifyLetter(modification)
+        })
+    })
+}
+
+/// Finds the first printable ASCII key that triggers a matching action.
+fn find_key<D: TypingDefinition>(
+    definition: &D,
+    matches: impl Fn(&Action) -> bool,
+) -> Option<char> {
+    (' '..='~').find(|key| {
+        definition
+            .actions(*key)
+            .is_some_and(|actions| actions.iter().any(&matches))
+    })
+}
+
+fn is_key<D: TypingDefinition>(definition: &D, ch: char) -> bool {
+    definition
+        .actions(ch.to_ascii_lowercase())
+        .is_some_and(|actions| !actions.is_empty())
+}
+
+/// Types a letter key in the same case as the letter it applies to.
+fn match_case(key: char, letter: char) -> char {
+    if letter.is_uppercase() {
+        key.to_ascii_uppercase()
+    } else {
+        key
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decompose_characters() {
+        assert_eq!(decompose('a'), ('a', None, None));
+        assert_eq!(
+            decompose('ệ'),
+            (
+                'e',
+                Some(LetterModification::Circumflex),
+                Some(ToneMark::Underdot)
+            )
+        );
+        assert_eq!(
+            decompose('Ử'),
+            (
+                'U',
+                Some(LetterModification::Horn),
+                Some(ToneMark::HookAbove)
+            )
+        );
+        assert_eq!(
+            decompose('ẵ'),
+            ('a', Some(LetterModification::Breve), Some(ToneMark::Tilde))
+        );
+        assert_eq!(decompose('đ'), ('d', Some(LetterModification::Dyet), None));
+        assert_eq!(decompose('ý'), ('y', None, Some(ToneMark::Acute)));
+    }
+
+    #[test]
+    fn shorten_keystrokes() {
+        let keystrokes: Vec<char> = "aaxb".chars().collect();
+        let shortened = shorten(keystrokes, |keystrokes| keystrokes.contains(&'b'));
+        assert_eq!(shortened, vec!['b']);
+    }
+}
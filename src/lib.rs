@@ -163,11 +163,27 @@
 //! - Security-focused configurations
 
 // Core modules
+pub mod abbreviation;
+pub mod accent;
+pub mod case;
+pub mod collation;
+pub mod composer;
 pub mod editing;
+pub mod encoding;
+pub mod english;
+pub mod keymap;
+pub mod keystrokes;
 pub mod maps;
 pub mod methods;
+pub mod normalize;
 pub mod parsing;
 pub mod processor;
+pub mod restoration;
+pub mod search;
+pub mod segmentation;
+pub mod slug;
+pub mod spelling;
+pub mod stream;
 pub mod syllable;
 /// Telex input method implementation for Vietnamese text processing.
 pub mod telex;
@@ -182,6 +198,7 @@ pub mod async_safety;
 pub mod optimization_selector;
 pub mod runtime_detection;
 pub mod safety;
+pub mod simd;
 pub mod vietnamese_processor;
 
 // Assembly integration
@@ -192,10 +209,12 @@ pub mod asm;
 pub mod memory_profiling;
 
 // Re-exports for convenience
+pub use abbreviation::{AbbreviationError, AbbreviationTable};
 pub use asm::{get_assembly_info, is_assembly_available};
+pub use keymap::{KeymapError, OwnedDefinition};
 pub use methods::*;
 pub use syllable::{ComplexSyllable, SimpleSyllable, Syllable};
-pub use util::{clean_char, clean_string, is_vowel};
+pub use util::{clean_char, clean_str, clean_string, is_vowel};
 
 // Assembly functions with error handling
 pub use safety::AssemblyError;
@@ -302,7 +321,5 @@ pub fn asm_clean_char_unsafe(ch: char) -> char {
 #[cfg(any(test, feature = "unsafe_performance"))]
 #[must_use]
 pub fn asm_clean_string_unsafe(input: &str) -> String {
-    let chars: Vec<char> = input.chars().collect();
-    let result = asm::direct_asm::process_chars_bulk_unsafe(&chars);
-    result.into_iter().collect()
+    asm::direct_asm::process_utf8_bulk_unsafe(input)
 }
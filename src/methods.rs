@@ -4,6 +4,7 @@
 //! - [`transform_buffer`] function to transfer your sequence of character into a syllable using a typing definition.
 //! - [`TELEX`] typing definition that you can use to pass in [`transform_buffer`] to transform character sequence using telex method.
 //! - [`VNI`] typing defnition that you can use to pass in [`transform_buffer`] to trasnform character sequence using vni method.
+//! - [`VIQR`] typing definition that you can use to pass in [`transform_buffer`] to transform character sequence using viqr method.
 //!
 //! ## Example
 //!
@@ -50,19 +51,30 @@
 //! transform_buffer(&MY_VNI, "chza".chars(), &mut result);
 //! assert_eq!(result, "chưa".to_owned());
 //! ```
+//!
+//! If the key bindings are only known at runtime, e.g. they come from a configuration file, use an
+//! [`OwnedDefinition`](crate::keymap::OwnedDefinition) from the [`keymap`](crate::keymap) module instead.
 use phf::{phf_map, Map};
 
 use crate::{
+    abbreviation::AbbreviationTable,
+    case::{is_all_caps, to_lower, to_upper_char},
+    editing::add_modification_char,
+    english::is_english_word,
     processor::{
         add_tone, modify_letter, remove_tone, AccentStyle, LetterModification, ToneMark,
         Transformation,
     },
     syllable::Syllable,
-    validation::is_valid_syllable,
+    util::is_vowel,
+    validation::{is_valid_syllable, is_valid_syllable_with_mode, ValidationMode},
 };
 
 /// An action to be listed as part of a typing definition.
+///
+/// This enum may be extended with additional actions in future versions.
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum Action {
     /// Add a tonemark
     AddTonemark(ToneMark),
@@ -78,6 +90,11 @@ pub enum Action {
     ResetInsertedƯ,
     /// Remove the tonemark from the syllable.
     RemoveToneMark,
+    /// Insert the given letter (`u`, `o`, `U` or `O`) with a horn at the end of the syllable.
+    /// Triggering the same action twice in a row replaces the inserted letter with the typed character.
+    InsertWithHorn(char),
+    /// Insert the next character as-is, bypassing the definition. The escape character itself is not inserted.
+    EscapeNext,
 }
 
 /// A definition of a typing method.
@@ -103,13 +120,40 @@ pub enum Action {
 /// in the list will be ignored.
 pub type Definition = Map<char, &'static [Action]>;
 
+/// A typing definition that can be used to transform a buffer.
+///
+/// This is implemented for the static [`Definition`] maps such as [`TELEX`] and [`VNI`], as well as
+/// for [`OwnedDefinition`](crate::keymap::OwnedDefinition) which can be built at runtime.
+///
+/// Definitions are shared by reference, so they must be [`Send`] & [`Sync`] for buffers to be moved across
+/// threads.
+pub trait TypingDefinition: std::fmt::Debug + Send + Sync {
+    /// Returns the list of actions that can be triggered by a character, if there is any.
+    fn actions(&self, key: char) -> Option<&[Action]>;
+}
+
+impl TypingDefinition for Definition {
+    #[inline]
+    fn actions(&self, key: char) -> Option<&[Action]> {
+        self.get(&key).copied()
+    }
+}
+
 /// A result of a buffer transformation.
-#[derive(Debug, Clone)]
+///
+/// This struct may be extended with additional fields in future versions.
+#[derive(Debug, Clone, Default)]
+#[allow(clippy::struct_excessive_bools)]
+#[non_exhaustive]
 pub struct TransformResult {
     /// Indicates whether a tone mark has been removed after the transformation.
     pub tone_mark_removed: bool,
     /// Indicates whether a letter modification has been removed after the transformation.
     pub letter_modification_removed: bool,
+    /// Indicates whether the transformations have been undone because the keystrokes spell an English word.
+    pub english_restored: bool,
+    /// Indicates whether the keystrokes have been replaced by the expansion of an abbreviation.
+    pub abbreviation_expanded: bool,
 }
 
 /// A definition for the VNI typing method with these configuration:
@@ -169,14 +213,65 @@ pub static TELEX: Definition = phf_map! {
     'z' => &[Action::RemoveToneMark],
 };
 
-/// Transforms a buffer of characters using a typing method definition with a given accent style.
+/// A definition for the extended Telex typing method, which is the same as [`TELEX`] except:
 ///
-/// This is the customizable version of [`transform_buffer`] that lets you choose how accents are applied.
+/// - `[` -> Insert ơ (thêm chữ ơ)
+/// - `]` -> Insert ư (thêm chữ ư)
+/// - `{` -> Insert Ơ (thêm chữ Ơ)
+/// - `}` -> Insert Ư (thêm chữ Ư)
+/// - `w` -> Horn for ư/ơ or Breve for a, but never inserted as `ư` by itself.
 ///
-/// # Panics
+/// **Note:**
+/// - Typing a bracket twice in a row will replace the inserted letter with the bracket, e.g. `[[` -> `[`.
+pub static TELEX_EXTENDED: Definition = phf_map! {
+    's' => &[Action::AddTonemark(ToneMark::Acute)],
+    'f' => &[Action::AddTonemark(ToneMark::Grave)],
+    'r' => &[Action::AddTonemark(ToneMark::HookAbove)],
+    'x' => &[Action::AddTonemark(ToneMark::Tilde)],
+    'j' => &[Action::AddTonemark(ToneMark::Underdot)],
+    'a' => &[Action::ModifyLetterOnCharacterFamily(LetterModification::Circumflex, 'a')],
+    'e' => &[Action::ModifyLetterOnCharacterFamily(LetterModification::Circumflex, 'e')],
+    'o' => &[Action::ModifyLetterOnCharacterFamily(LetterModification::Circumflex, 'o')],
+    'w' => &[Action::ModifyLetter(LetterModification::Horn), Action::ModifyLetter(LetterModification::Breve)],
+    'd' => &[Action::ModifyLetter(LetterModification::Dyet)],
+    'z' => &[Action::RemoveToneMark],
+    '[' => &[Action::InsertWithHorn('o')],
+    ']' => &[Action::InsertWithHorn('u')],
+    '{' => &[Action::InsertWithHorn('O')],
+    '}' => &[Action::InsertWithHorn('U')],
+};
+
+/// A definition for the VIQR typing method with these configuration:
+///
+/// - `'` -> Acute (thêm dấu sắc)
+/// - `` ` `` -> Grave (thêm dấu huyền)
+/// - `?` -> `HookAbove` (thêm dấu hỏi)
+/// - `~` -> Tilde (thêm dấu ngã)
+/// - `.` -> Underdot (thêm dấu nặng)
+/// - `^` -> Circumflex (thêm dấu ^)
+/// - `(` -> Breve (thêm dấu cho a thành ă)
+/// - `+` -> Horn (thêm dấu móc cho ư hoặc ơ)
+/// - `d` -> Dyet (thêm dấu gạch cho d thành đ)
+/// - `\` -> `EscapeNext` giữ nguyên ký tự tiếp theo
 ///
-/// Panics if the definition contains a character key that maps to an empty action list.
-/// This should not happen with well-formed definitions like [`TELEX`] or [`VNI`].
+/// **Note:**
+/// - Since `.` and `?` are also punctuation marks, type `\` before them to keep them as is, e.g. `a\.` -> `a.`.
+pub static VIQR: Definition = phf_map! {
+    '\'' => &[Action::AddTonemark(ToneMark::Acute)],
+    '`' => &[Action::AddTonemark(ToneMark::Grave)],
+    '?' => &[Action::AddTonemark(ToneMark::HookAbove)],
+    '~' => &[Action::AddTonemark(ToneMark::Tilde)],
+    '.' => &[Action::AddTonemark(ToneMark::Underdot)],
+    '^' => &[Action::ModifyLetter(LetterModification::Circumflex)],
+    '(' => &[Action::ModifyLetter(LetterModification::Breve)],
+    '+' => &[Action::ModifyLetter(LetterModification::Horn)],
+    'd' => &[Action::ModifyLetter(LetterModification::Dyet)],
+    '\\' => &[Action::EscapeNext],
+};
+
+/// Transforms a buffer of characters using a typing method definition with a given accent style.
+///
+/// This is the customizable version of [`transform_buffer`] that lets you choose how accents are applied.
 ///
 /// # Example
 ///
@@ -190,13 +285,14 @@ pub static TELEX: Definition = phf_map! {
 /// transform_buffer_with_style(&vi::TELEX, AccentStyle::Old, "hoas".chars(), &mut result);
 /// assert_eq!(result, "hóa".to_owned());
 /// ```
-pub fn transform_buffer_with_style<I>(
-    definition: &Definition,
+pub fn transform_buffer_with_style<D, I>(
+    definition: &D,
     accent_style: AccentStyle,
     buffer: I,
     output: &mut String,
 ) -> TransformResult
 where
+    D: TypingDefinition,
     I: IntoIterator<Item = char>,
 {
     let mut incremental_buffer = IncrementalBuffer::new_with_style(definition, accent_style);
@@ -220,17 +316,141 @@ where
 /// transform_buffer(&vi::VNI, "viet65".chars(), &mut result);
 /// assert_eq!(result, "việt".to_owned());
 /// ```
-pub fn transform_buffer<I>(
-    definition: &Definition,
-    buffer: I,
-    output: &mut String,
-) -> TransformResult
+pub fn transform_buffer<D, I>(definition: &D, buffer: I, output: &mut String) -> TransformResult
 where
+    D: TypingDefinition,
     I: IntoIterator<Item = char>,
 {
     transform_buffer_with_style(definition, AccentStyle::default(), buffer, output)
 }
 
+/// Determines what is removed by [`IncrementalBuffer::backspace`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BackspaceMode {
+    /// Remove the last keystroke, as if it was never typed.
+    #[default]
+    Keystroke,
+    /// Remove the last visible character together with its diacritics, so that a tone mark on that character
+    /// is not moved to another one.
+    Character,
+}
+
+/// Determines what happens when the key of an action that has already been applied is typed again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UndoBehavior {
+    /// The action is undone and the key is inserted as a letter, e.g. `ass` -> `as` in Telex. Typing the
+    /// key once more inserts it again, e.g. `asss` -> `ass`.
+    #[default]
+    Literal,
+    /// Like [`UndoBehavior::Literal`], but typing the key once more removes the letter and applies the action
+    /// again, e.g. `asss` -> `á`.
+    Toggle,
+    /// Like [`UndoBehavior::Literal`], but only if the previous keystroke applied the action. Otherwise the
+    /// action is kept and the key is inserted as a letter, e.g. `asts` -> `ásts`.
+    LastActionOnly,
+}
+
+/// Determines how an [`IncrementalBuffer`] undoes each kind of [`Action`] when its key is typed again.
+///
+/// # Examples
+///
+/// ```
+/// use vi::methods::{transform_buffer_incremental, UndoPolicy};
+///
+/// let mut buffer = transform_buffer_incremental(&vi::TELEX).with_undo_policy(UndoPolicy::TOGGLE);
+/// for ch in "casss".chars() {
+///     buffer.push(ch);
+/// }
+/// assert_eq!(buffer.view(), "cá");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct UndoPolicy {
+    /// Applies to [`Action::AddTonemark`].
+    pub tone_mark: UndoBehavior,
+    /// Applies to [`Action::ModifyLetter`] and [`Action::ModifyLetterOnCharacterFamily`].
+    pub letter_modification: UndoBehavior,
+    /// Applies to [`Action::InsertƯ`] and [`Action::InsertWithHorn`]. An inserted letter can only be
+    /// undone right after it was inserted, so [`UndoBehavior::LastActionOnly`] is the same as
+    /// [`UndoBehavior::Literal`] here.
+    pub letter_insertion: UndoBehavior,
+}
+
+impl UndoPolicy {
+    /// Every action is undone with [`UndoBehavior::Literal`]. This is the default policy.
+    pub const LITERAL: Self = Self::uniform(UndoBehavior::Literal);
+    /// Every action is undone with [`UndoBehavior::Toggle`].
+    pub const TOGGLE: Self = Self::uniform(UndoBehavior::Toggle);
+    /// Every action is undone with [`UndoBehavior::LastActionOnly`].
+    pub const LAST_ACTION_ONLY: Self = Self::uniform(UndoBehavior::LastActionOnly);
+
+    /// Creates a policy with the same behavior for every kind of action.
+    #[must_use]
+    pub const fn uniform(behavior: UndoBehavior) -> Self {
+        Self {
+            tone_mark: behavior,
+            letter_modification: behavior,
+            letter_insertion: behavior,
+        }
+    }
+
+    /// Returns the behavior for an action, or `None` if the action can't be undone by typing its key again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vi::{
+    ///     methods::{UndoBehavior, UndoPolicy},
+    ///     processor::ToneMark,
+    ///     Action,
+    /// };
+    ///
+    /// let policy = UndoPolicy {
+    ///     tone_mark: UndoBehavior::Toggle,
+    ///     ..UndoPolicy::LITERAL
+    /// };
+    /// assert_eq!(policy.behavior(&Action::AddTonemark(ToneMark::Acute)), Some(UndoBehavior::Toggle));
+    /// assert_eq!(policy.behavior(&Action::RemoveToneMark), None);
+    /// ```
+    #[must_use]
+    pub const fn behavior(&self, action: &Action) -> Option<UndoBehavior> {
+        match action {
+            Action::AddTonemark(_) => Some(self.tone_mark),
+            Action::ModifyLetter(_) | Action::ModifyLetterOnCharacterFamily(..) => {
+                Some(self.letter_modification)
+            }
+            Action::InsertƯ | Action::ResetInsertedƯ | Action::InsertWithHorn(_) => {
+                Some(self.letter_insertion)
+            }
+            Action::RemoveToneMark | Action::EscapeNext => None,
+        }
+    }
+}
+
+/// Determines when a tone key typed into an [`IncrementalBuffer`] is applied as a tone mark.
+///
+/// # Examples
+///
+/// ```
+/// use vi::methods::{transform_buffer_incremental, ToneEntryPolicy};
+///
+/// let mut buffer = transform_buffer_incremental(&vi::TELEX).with_tone_entry_policy(ToneEntryPolicy::Strict);
+/// for ch in "taxi".chars() {
+///     buffer.push(ch);
+/// }
+/// assert_eq!(buffer.view(), "taxi");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ToneEntryPolicy {
+    /// A tone key is applied as soon as the syllable has a vowel, and the tone mark is moved to the right
+    /// vowel as more letters are typed.
+    #[default]
+    Free,
+    /// A tone key must be typed after the vowels of the syllable. A tone key typed before any vowel, or
+    /// followed by another vowel, stays a literal letter, and so does every other tone key of the syllable.
+    /// A tone key that makes the keystrokes a known English word, like the `s` of `this`, stays a letter too.
+    Strict,
+}
+
 /// An incremental buffer for character-by-character Vietnamese text transformation.
 ///
 /// This structure allows for incremental processing of Vietnamese input, where characters
@@ -268,7 +488,7 @@ where
 #[derive(Debug, Clone)]
 pub struct IncrementalBuffer<'def> {
     /// Reference to the typing method definition
-    definition: &'def Definition,
+    definition: &'def dyn TypingDefinition,
     /// The current syllable state
     syllable: Syllable,
     /// Input characters received so far
@@ -279,8 +499,34 @@ pub struct IncrementalBuffer<'def> {
     result: TransformResult,
     /// The last executed action for state tracking
     last_executed_action: Option<Action>,
+    /// When tone keys are applied as tone marks
+    tone_entry_policy: ToneEntryPolicy,
+    /// Whether tone keys are inserted as literal letters for the rest of the syllable
+    literal_tone_keys: bool,
+    /// The tone keys applied to the syllable, with the number of characters typed before each of them
+    tone_keys: Vec<(usize, char)>,
+    /// Whether [`IncrementalBuffer::finish`] restores the keystrokes of English words
+    english_restoration: bool,
+    /// How actions are undone when their key is typed again
+    undo_policy: UndoPolicy,
+    /// The rules a syllable must follow for an action to be applied
+    validation_mode: ValidationMode,
+    /// The action applied by the previous keystroke
+    previous_action: Option<Action>,
+    /// The action undone by the previous keystroke
+    undone_action: Option<Action>,
+    /// Abbreviations expanded by [`IncrementalBuffer::finish`]
+    abbreviations: Option<&'def AbbreviationTable>,
+    /// Whether the output is the expansion of an abbreviation
+    expanded: bool,
 }
 
+// Buffers can be moved across threads, like when they held a `&'static Definition`
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<IncrementalBuffer<'static>>();
+};
+
 impl<'def> IncrementalBuffer<'def> {
     /// Creates a new incremental buffer with the specified typing definition.
     ///
@@ -294,7 +540,7 @@ impl<'def> IncrementalBuffer<'def> {
     /// ```
     #[inline]
     #[must_use]
-    pub fn new(definition: &'def Definition) -> Self {
+    pub fn new<D: TypingDefinition>(definition: &'def D) -> Self {
         Self::new_with_style(definition, AccentStyle::default())
     }
 
@@ -310,7 +556,10 @@ impl<'def> IncrementalBuffer<'def> {
     /// ```
     #[inline]
     #[must_use]
-    pub fn new_with_style(definition: &'def Definition, accent_style: AccentStyle) -> Self {
+    pub fn new_with_style<D: TypingDefinition>(
+        definition: &'def D,
+        accent_style: AccentStyle,
+    ) -> Self {
         Self {
             definition,
             syllable: Syllable {
@@ -319,22 +568,157 @@ impl<'def> IncrementalBuffer<'def> {
             },
             input: Vec::new(),
             output: String::new(),
-            result: TransformResult {
-                tone_mark_removed: false,
-                letter_modification_removed: false,
-            },
+            result: TransformResult::default(),
             last_executed_action: None,
+            tone_entry_policy: ToneEntryPolicy::default(),
+            literal_tone_keys: false,
+            tone_keys: Vec::new(),
+            english_restoration: false,
+            undo_policy: UndoPolicy::default(),
+            validation_mode: ValidationMode::default(),
+            previous_action: None,
+            undone_action: None,
+            abbreviations: None,
+            expanded: false,
         }
     }
 
+    /// Sets how actions are undone when their key is typed again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vi::methods::{IncrementalBuffer, UndoPolicy};
+    ///
+    /// let mut buffer = IncrementalBuffer::new(&vi::TELEX).with_undo_policy(UndoPolicy::LAST_ACTION_ONLY);
+    /// for ch in "toasns".chars() {
+    ///     buffer.push(ch);
+    /// }
+    /// assert_eq!(buffer.view(), "toáns");
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn with_undo_policy(mut self, policy: UndoPolicy) -> Self {
+        self.undo_policy = policy;
+        self
+    }
+
+    /// Returns how actions are undone when their key is typed again.
+    #[inline]
+    #[must_use]
+    pub const fn undo_policy(&self) -> UndoPolicy {
+        self.undo_policy
+    }
+
+    /// Sets the rules a syllable must follow for an action to be applied. When an action makes the syllable
+    /// invalid, it's reverted and its key is inserted as a letter.
+    ///
+    /// With [`ValidationMode::Strict`], a syllable without a tone mark is still checked with the basic rules,
+    /// since the tone mark is usually typed last.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vi::{methods::IncrementalBuffer, validation::ValidationMode};
+    ///
+    /// let mut buffer = IncrementalBuffer::new(&vi::TELEX).with_validation_mode(ValidationMode::Strict);
+    /// for ch in "hopr".chars() {
+    ///     buffer.push(ch);
+    /// }
+    /// assert_eq!(buffer.view(), "hopr");
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn with_validation_mode(mut self, mode: ValidationMode) -> Self {
+        self.validation_mode = mode;
+        self
+    }
+
+    /// Returns the rules a syllable must follow for an action to be applied.
+    #[inline]
+    #[must_use]
+    pub const fn validation_mode(&self) -> ValidationMode {
+        self.validation_mode
+    }
+
+    /// Sets the policy deciding when tone keys are applied as tone marks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vi::methods::{IncrementalBuffer, ToneEntryPolicy};
+    ///
+    /// let mut buffer = IncrementalBuffer::new(&vi::TELEX).with_tone_entry_policy(ToneEntryPolicy::Strict);
+    /// for ch in "tosan".chars() {
+    ///     buffer.push(ch);
+    /// }
+    /// assert_eq!(buffer.view(), "tosan");
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn with_tone_entry_policy(mut self, policy: ToneEntryPolicy) -> Self {
+        self.tone_entry_policy = policy;
+        self
+    }
+
+    /// Returns the policy deciding when tone keys are applied as tone marks.
+    #[inline]
+    #[must_use]
+    pub const fn tone_entry_policy(&self) -> ToneEntryPolicy {
+        self.tone_entry_policy
+    }
+
+    /// Enables or disables restoring the keystrokes of English words in [`IncrementalBuffer::finish`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vi::methods::IncrementalBuffer;
+    ///
+    /// let mut buffer = IncrementalBuffer::new(&vi::TELEX).with_english_restoration(true);
+    /// for ch in "those".chars() {
+    ///     buffer.push(ch);
+    /// }
+    /// assert_eq!(buffer.view(), "thoé");
+    ///
+    /// assert!(buffer.finish().english_restored);
+    /// assert_eq!(buffer.view(), "those");
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn with_english_restoration(mut self, enable: bool) -> Self {
+        self.english_restoration = enable;
+        self
+    }
+
+    /// Sets the abbreviations expanded by [`IncrementalBuffer::finish`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vi::{abbreviation::AbbreviationTable, methods::IncrementalBuffer};
+    ///
+    /// let mut table = AbbreviationTable::new();
+    /// table.insert("ko", "không");
+    ///
+    /// let mut buffer = IncrementalBuffer::new(&vi::TELEX).with_abbreviations(&table);
+    /// buffer.push('k');
+    /// buffer.push('o');
+    /// buffer.finish();
+    /// assert_eq!(buffer.view(), "không");
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn with_abbreviations(mut self, abbreviations: &'def AbbreviationTable) -> Self {
+        self.abbreviations = Some(abbreviations);
+        self
+    }
+
     /// Adds a character to the buffer and updates the transformation result.
     ///
     /// Returns the transformation result for this character addition.
     ///
-    /// # Panics
-    ///
-    /// Panics if the definition contains a character key that maps to an empty action list.
-    /// This should not happen with well-formed definitions like [`TELEX`] or [`VNI`].
+    /// A character that maps to an empty action list is treated as a regular character.
     ///
     /// # Examples
     ///
@@ -346,74 +730,61 @@ impl<'def> IncrementalBuffer<'def> {
     /// assert_eq!(buffer.view(), "v");
     /// ```
     pub fn push(&mut self, ch: char) -> TransformResult {
+        let _ = self.undo_expansion();
         self.input.push(ch);
+        let previous_action = self.previous_action.take();
+        let undone_action = self.undone_action.take();
 
         let lowercase_ch = ch.to_ascii_lowercase();
 
-        // If a character is not recognised as a transformation character in definition. Skip it.
-        if !self.definition.contains_key(&lowercase_ch) {
+        // If a character is escaped by the previous character, insert it as is.
+        if matches!(self.last_executed_action, Some(Action::EscapeNext)) {
+            self.last_executed_action = None;
             self.syllable.push(ch);
             self.update_output();
-            return TransformResult {
-                tone_mark_removed: false,
-                letter_modification_removed: false,
-            };
+            return TransformResult::default();
         }
 
-        let fallback = format!("{}{ch}", self.syllable);
-        // SAFETY: We already checked contains_key above, so this key exists
-        let actions = self
+        // If a character is not recognised as a transformation character in definition. Skip it.
+        let Some((first_action, rest_actions)) = self
             .definition
-            .get(&lowercase_ch)
-            .expect("Key should exist after contains_key check");
-
-        let mut action_iter = actions.iter();
-        // SAFETY: Action slices in definitions are never empty
-        let mut action = action_iter
-            .next()
-            .expect("Action slice should never be empty");
-
-        let mut char_result = TransformResult {
-            tone_mark_removed: false,
-            letter_modification_removed: false,
+            .actions(lowercase_ch)
+            .and_then(<[Action]>::split_first)
+        else {
+            self.push_literal(ch);
+            self.update_output();
+            return TransformResult::default();
         };
 
+        // The escape character is never inserted, it only marks the next character as literal.
+        if *first_action == Action::EscapeNext {
+            self.last_executed_action = Some(Action::EscapeNext);
+            return TransformResult::default();
+        }
+
+        // Typing the key of an undone action again may apply it again.
+        if let Some(action) = undone_action.filter(|action| {
+            self.undo_policy.behavior(action) == Some(UndoBehavior::Toggle)
+                && (first_action == action || rest_actions.contains(action))
+        }) {
+            self.redo(action, ch);
+            self.update_output();
+            return TransformResult::default();
+        }
+
+        let fallback = format!("{}{ch}", self.syllable);
+
+        let position = self.syllable.len();
+        let mut action = first_action;
+        let mut action_iter = rest_actions.iter();
+
+        let mut char_result = TransformResult::default();
+
         loop {
-            let transformation = match action {
-                Action::AddTonemark(tonemark) => add_tone(&mut self.syllable, tonemark),
-                Action::ModifyLetter(modification) => {
-                    modify_letter(&mut self.syllable, modification)
-                }
-                Action::ModifyLetterOnCharacterFamily(modification, family_char)
-                    if self
-                        .syllable
-                        .vowel
-                        .to_ascii_lowercase()
-                        .contains(*family_char) =>
-                {
-                    modify_letter(&mut self.syllable, modification)
-                }
-                Action::RemoveToneMark => remove_tone(&mut self.syllable),
-                Action::InsertƯ => {
-                    if self.syllable.vowel.is_empty() || self.syllable.to_string() == "gi" {
-                        self.syllable
-                            .push(if ch.is_lowercase() { 'u' } else { 'U' });
-                        let last_index = self.syllable.len() - 1;
-                        self.syllable
-                            .letter_modifications
-                            .push((last_index, LetterModification::Horn));
-                        Transformation::LetterModificationAdded
-                    } else {
-                        Transformation::Ignored
-                    }
-                }
-                Action::ResetInsertedƯ
-                    if matches!(self.last_executed_action, Some(Action::InsertƯ)) =>
-                {
-                    self.syllable.replace_last_char(ch);
-                    Transformation::LetterModificationRemoved
-                }
-                _ => Transformation::Ignored,
+            let transformation = if self.may_undo(action, previous_action.as_ref()) {
+                self.apply_action(action, ch)
+            } else {
+                Transformation::Ignored
             };
 
             // If the transformation cannot be applied, try the next action if there's one.
@@ -424,15 +795,7 @@ impl<'def> IncrementalBuffer<'def> {
                 }
             }
 
-            if transformation == Transformation::ToneMarkRemoved {
-                char_result.tone_mark_removed = true;
-                self.result.tone_mark_removed = true;
-            }
-
-            if transformation == Transformation::LetterModificationRemoved {
-                char_result.letter_modification_removed = true;
-                self.result.letter_modification_removed = true;
-            }
+            self.record_removal(action, transformation, &mut char_result);
 
             let action_performed = match transformation {
                 Transformation::Ignored | Transformation::LetterModificationRemoved => false,
@@ -447,14 +810,24 @@ impl<'def> IncrementalBuffer<'def> {
                 break;
             }
 
+            // Same for reverting an inserted horn letter, which already put the typed character in its place
+            if matches!(action, Action::InsertWithHorn(_)) && !action_performed {
+                self.last_executed_action = None;
+                break;
+            }
+
             if !action_performed {
-                self.syllable.push(ch);
+                self.push_literal(ch);
                 self.last_executed_action = None;
-            } else if !is_valid_syllable(&self.syllable.to_string()) {
+            } else if !self.is_syllable_valid() {
                 self.syllable.set(fallback);
                 self.last_executed_action = None;
             } else {
+                if matches!(action, Action::AddTonemark(_)) {
+                    self.tone_keys.push((position, ch));
+                }
                 self.last_executed_action = Some(action.clone());
+                self.previous_action = Some(action.clone());
             }
             break;
         }
@@ -463,6 +836,257 @@ impl<'def> IncrementalBuffer<'def> {
         char_result
     }
 
+    /// Checks the current syllable with the validation mode, ignoring the strict rules until a tone mark is added.
+    fn is_syllable_valid(&self) -> bool {
+        let mode = if self.syllable.tone_mark.is_some() {
+            self.validation_mode
+        } else {
+            ValidationMode::Basic
+        };
+        is_valid_syllable_with_mode(&self.syllable.to_string(), mode)
+    }
+
+    /// Applies a single action of a definition to the current syllable.
+    fn apply_action(&mut self, action: &Action, ch: char) -> Transformation {
+        match action {
+            Action::AddTonemark(_)
+                if self.tone_entry_policy == ToneEntryPolicy::Strict
+                    && (self.literal_tone_keys
+                        || self.syllable.vowel.is_empty()
+                        || is_english_word(&self.input.iter().collect::<String>())) =>
+            {
+                self.literal_tone_keys = true;
+                Transformation::Ignored
+            }
+            Action::AddTonemark(tonemark) => add_tone(&mut self.syllable, tonemark),
+            Action::ModifyLetter(modification) => modify_letter(&mut self.syllable, modification),
+            Action::ModifyLetterOnCharacterFamily(modification, family_char)
+                if self
+                    .syllable
+                    .vowel
+                    .to_ascii_lowercase()
+                    .contains(*family_char) =>
+            {
+                modify_letter(&mut self.syllable, modification)
+            }
+            Action::RemoveToneMark => remove_tone(&mut self.syllable),
+            Action::InsertƯ => {
+                if self.syllable.vowel.is_empty() || to_lower(&self.syllable.to_string()) == "gi" {
+                    self.syllable
+                        .push(if ch.is_lowercase() { 'u' } else { 'U' });
+                    let last_index = self.syllable.len() - 1;
+                    self.syllable
+                        .letter_modifications
+                        .push((last_index, LetterModification::Horn));
+                    Transformation::LetterModificationAdded
+                } else {
+                    Transformation::Ignored
+                }
+            }
+            Action::ResetInsertedƯ
+                if matches!(self.last_executed_action, Some(Action::InsertƯ)) =>
+            {
+                self.syllable.replace_last_char(ch);
+                Transformation::LetterModificationRemoved
+            }
+            Action::InsertWithHorn(_) if self.last_executed_action.as_ref() == Some(action) => {
+                self.syllable.replace_last_char(ch);
+                Transformation::LetterModificationRemoved
+            }
+            Action::InsertWithHorn(letter) => {
+                // Set from the rendered string so existing horns keep their positions
+                let mut raw = self.syllable.to_string();
+                // Keys without case like `[` follow the case of a syllable typed in all caps
+                let letter = if is_all_caps(&raw) {
+                    to_upper_char(*letter)
+                } else {
+                    *letter
+                };
+                raw.push(add_modification_char(letter, &LetterModification::Horn));
+                self.syllable.set(raw);
+                Transformation::LetterModificationAdded
+            }
+            _ => Transformation::Ignored,
+        }
+    }
+
+    /// Indicates whether an action may be applied under the undo policy, given the action applied by the
+    /// previous keystroke.
+    fn may_undo(&self, action: &Action, previous_action: Option<&Action>) -> bool {
+        self.undo_policy.behavior(action) != Some(UndoBehavior::LastActionOnly)
+            || previous_action == Some(action)
+            || !self.would_undo(action)
+    }
+
+    /// Updates the transformation results and remembers the undone action if a transformation removed
+    /// something.
+    fn record_removal(
+        &mut self,
+        action: &Action,
+        transformation: Transformation,
+        char_result: &mut TransformResult,
+    ) {
+        match transformation {
+            Transformation::ToneMarkRemoved => {
+                self.tone_keys.clear();
+                char_result.tone_mark_removed = true;
+                self.result.tone_mark_removed = true;
+                if *action != Action::RemoveToneMark {
+                    self.undone_action = Some(action.clone());
+                }
+            }
+            Transformation::LetterModificationRemoved => {
+                char_result.letter_modification_removed = true;
+                self.result.letter_modification_removed = true;
+                self.undone_action = Some(if *action == Action::ResetInsertedƯ {
+                    Action::InsertƯ
+                } else {
+                    action.clone()
+                });
+            }
+            _ => {}
+        }
+    }
+
+    /// Indicates whether applying an action would undo it.
+    fn would_undo(&self, action: &Action) -> bool {
+        match action {
+            Action::AddTonemark(tonemark) => self.syllable.tone_mark == Some(*tonemark),
+            Action::ModifyLetter(modification)
+            | Action::ModifyLetterOnCharacterFamily(modification, _) => {
+                let mut syllable = self.syllable.clone();
+                modify_letter(&mut syllable, modification)
+                    == Transformation::LetterModificationRemoved
+            }
+            _ => false,
+        }
+    }
+
+    /// Replaces the letter inserted when an action was undone by the action itself.
+    fn redo(&mut self, action: Action, ch: char) {
+        let mut raw = self.syllable.to_string();
+        raw.pop();
+        self.syllable.set(raw);
+        if matches!(action, Action::AddTonemark(_)) {
+            self.tone_keys.push((self.syllable.len(), ch));
+        }
+        let _ = self.apply_action(&action, ch);
+        self.last_executed_action = Some(action.clone());
+        self.previous_action = Some(action);
+    }
+
+    /// Pushes a character that isn't transformed by the definition.
+    ///
+    /// In strict mode, a vowel typed after a tone mark turns the tone keys of the syllable back into letters.
+    fn push_literal(&mut self, ch: char) {
+        if self.tone_entry_policy == ToneEntryPolicy::Strict
+            && self.syllable.tone_mark.is_some()
+            && is_vowel(ch)
+        {
+            let _ = remove_tone(&mut self.syllable);
+            let mut raw: Vec<char> = self.syllable.to_string().chars().collect();
+            for (inserted, (position, key)) in self.tone_keys.drain(..).enumerate() {
+                raw.insert((position + inserted).min(raw.len()), key);
+            }
+            self.syllable.set(raw.into_iter().collect());
+            self.literal_tone_keys = true;
+        }
+        self.syllable.push(ch);
+    }
+
+    /// Marks the end of the word being typed and returns the cumulative transformation result.
+    ///
+    /// An escape character typed last, with nothing after it to escape, is added to the output.
+    ///
+    /// If abbreviations are set with [`IncrementalBuffer::with_abbreviations`] and the keystrokes or the output
+    /// are an enabled abbreviation, the output is replaced by its expansion, which is reported by
+    /// [`TransformResult::abbreviation_expanded`]. The expansion can be undone with
+    /// [`IncrementalBuffer::undo_expansion`].
+    ///
+    /// Otherwise, if English restoration is enabled with [`IncrementalBuffer::with_english_restoration`], the raw
+    /// keystrokes replace the output when the output isn't a valid Vietnamese syllable or the keystrokes
+    /// spell a known English word, which is reported by [`TransformResult::english_restored`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vi::methods::IncrementalBuffer;
+    ///
+    /// let mut buffer = IncrementalBuffer::new(&vi::TELEX).with_english_restoration(true);
+    /// for ch in "vieetj".chars() {
+    ///     buffer.push(ch);
+    /// }
+    /// assert!(!buffer.finish().english_restored);
+    /// assert_eq!(buffer.view(), "việt");
+    /// ```
+    pub fn finish(&mut self) -> TransformResult {
+        if self.expanded || self.result.english_restored {
+            return self.result.clone();
+        }
+
+        // A trailing escape character has nothing to escape, so it's kept as a letter
+        if matches!(self.last_executed_action, Some(Action::EscapeNext)) {
+            self.last_executed_action = None;
+            if let Some(&escape) = self.input.last() {
+                self.syllable.push(escape);
+                self.update_output();
+            }
+        }
+
+        let raw: String = self.input.iter().collect();
+        let expansion = self.abbreviations.and_then(|abbreviations| {
+            abbreviations
+                .expand(&raw)
+                .or_else(|| abbreviations.expand(&self.output))
+        });
+
+        if let Some(expansion) = expansion {
+            self.output = expansion;
+            self.expanded = true;
+            self.result.abbreviation_expanded = true;
+        } else if self.english_restoration
+            && raw != self.output
+            && (!is_valid_syllable(&self.output) || is_english_word(&raw))
+        {
+            self.syllable.set(raw);
+            self.result.english_restored = true;
+            self.last_executed_action = None;
+            self.update_output();
+        }
+        self.result.clone()
+    }
+
+    /// Replaces the expansion of an abbreviation with the output it replaced, keeping the keystrokes. Returns
+    /// whether the output was an expansion.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vi::{abbreviation::AbbreviationTable, methods::transform_buffer_incremental};
+    ///
+    /// let table: AbbreviationTable = "ko = không".parse()?;
+    ///
+    /// let mut buffer = transform_buffer_incremental(&vi::TELEX).with_abbreviations(&table);
+    /// buffer.push('k');
+    /// buffer.push('o');
+    /// assert!(buffer.finish().abbreviation_expanded);
+    /// assert_eq!(buffer.view(), "không");
+    ///
+    /// assert!(buffer.undo_expansion());
+    /// assert_eq!(buffer.view(), "ko");
+    /// assert!(!buffer.undo_expansion());
+    /// # Ok::<(), vi::abbreviation::AbbreviationError>(())
+    /// ```
+    pub fn undo_expansion(&mut self) -> bool {
+        if !self.expanded {
+            return false;
+        }
+        self.expanded = false;
+        self.result.abbreviation_expanded = false;
+        self.update_output();
+        true
+    }
+
     /// Returns the current transformed output as a string slice.
     ///
     /// This provides immediate access to the current state of the transformation
@@ -536,6 +1160,133 @@ impl<'def> IncrementalBuffer<'def> {
         &self.input
     }
 
+    /// Removes the last keystroke from the buffer and returns it, or `None` if the buffer is empty.
+    ///
+    /// The remaining keystrokes are replayed, so the output and the transformation result are the same as if
+    /// the last keystroke was never typed.
+    ///
+    /// If the output is the expansion of an abbreviation, the expansion is undone before the keystroke is removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vi::methods::transform_buffer_incremental;
+    ///
+    /// let mut buffer = transform_buffer_incremental(&vi::TELEX);
+    /// for ch in "vieetj".chars() {
+    ///     buffer.push(ch);
+    /// }
+    /// assert_eq!(buffer.view(), "việt");
+    ///
+    /// assert_eq!(buffer.pop(), Some('j'));
+    /// assert_eq!(buffer.view(), "viêt");
+    /// assert_eq!(buffer.input(), &['v', 'i', 'e', 'e', 't']);
+    /// ```
+    pub fn pop(&mut self) -> Option<char> {
+        let mut input = std::mem::take(&mut self.input);
+        let last = input.pop();
+        self.clear();
+        for ch in input {
+            let _ = self.push(ch);
+        }
+        last
+    }
+
+    /// Removes either the last keystroke or the last visible character from the buffer, depending on the mode.
+    ///
+    /// Returns the removed keystroke or character, or `None` if the buffer is empty.
+    ///
+    /// With [`BackspaceMode::Character`], the keystrokes that typed the last character are removed from the input,
+    /// together with the keys that only modified it, and the remaining keystrokes are replayed. If no keystrokes
+    /// can be removed to type the remaining text, the input is replaced with the remaining visible characters.
+    ///
+    /// The expansion of an abbreviation is undone before anything is removed, like [`IncrementalBuffer::pop`] does.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vi::methods::{transform_buffer_incremental, BackspaceMode};
+    ///
+    /// let mut buffer = transform_buffer_incremental(&vi::TELEX);
+    /// for ch in "tieesn".chars() {
+    ///     buffer.push(ch);
+    /// }
+    /// assert_eq!(buffer.view(), "tiến");
+    ///
+    /// assert_eq!(buffer.backspace(BackspaceMode::Character), Some('n'));
+    /// assert_eq!(buffer.view(), "tiế");
+    /// assert_eq!(buffer.input(), &['t', 'i', 'e', 'e', 's']);
+    ///
+    /// // The tone mark goes away with the character it's on
+    /// assert_eq!(buffer.backspace(BackspaceMode::Character), Some('ế'));
+    /// assert_eq!(buffer.view(), "ti");
+    /// assert_eq!(buffer.input(), &['t', 'i']);
+    /// ```
+    pub fn backspace(&mut self, mode: BackspaceMode) -> Option<char> {
+        if mode == BackspaceMode::Keystroke {
+            return self.pop();
+        }
+        let _ = self.undo_expansion();
+
+        let mut remaining = self.output.clone();
+        let removed = remaining.pop()?;
+
+        if let Some(buffer) = self.without_last_character(&remaining) {
+            *self = buffer;
+            return Some(removed);
+        }
+
+        // The visible characters are replayed as keystrokes, so the input and the result stay in sync
+        let mut buffer = self.clone();
+        buffer.clear();
+        for ch in remaining.chars() {
+            let _ = buffer.push(ch);
+        }
+        if buffer.output != remaining {
+            buffer.syllable.set(remaining);
+            buffer.input = buffer.syllable.to_string().chars().collect();
+            buffer.last_executed_action = None;
+            buffer.previous_action = None;
+            buffer.undone_action = None;
+            buffer.update_output();
+        }
+        *self = buffer;
+        Some(removed)
+    }
+
+    /// Finds the keystrokes to remove for the output to become the remaining text, and returns the buffer with
+    /// the other keystrokes replayed.
+    ///
+    /// The last character was typed by a key, and maybe modified by some of the keys typed after it, so the
+    /// keys are tried from the last one, each with the fewest of the keys after it.
+    fn without_last_character(&self, remaining: &str) -> Option<Self> {
+        // Bounds the number of replays when a letter is followed by many keys
+        const MAX_KEYS_AFTER: usize = 8;
+
+        let keys = self.input.len();
+        for index in (keys.saturating_sub(MAX_KEYS_AFTER + 1)..keys).rev() {
+            let after = self.input.get(index + 1..).unwrap_or_default();
+            let mut masks: Vec<u32> = (0..1_u32 << after.len()).collect();
+            masks.sort_by_key(|mask| mask.count_ones());
+            for mask in masks {
+                let mut buffer = self.clone();
+                buffer.clear();
+                let kept = after
+                    .iter()
+                    .enumerate()
+                    .filter(|(position, _)| mask & (1 << position) == 0)
+                    .map(|(_, ch)| *ch);
+                for ch in self.input.iter().take(index).copied().chain(kept) {
+                    let _ = buffer.push(ch);
+                }
+                if buffer.output == remaining {
+                    return Some(buffer);
+                }
+            }
+        }
+        None
+    }
+
     /// Clears the buffer, resetting it to an empty state.
     ///
     /// This removes all input characters, resets the syllable state, and clears
@@ -566,11 +1317,13 @@ impl<'def> IncrementalBuffer<'def> {
         };
         self.input.clear();
         self.output.clear();
-        self.result = TransformResult {
-            tone_mark_removed: false,
-            letter_modification_removed: false,
-        };
+        self.result = TransformResult::default();
         self.last_executed_action = None;
+        self.literal_tone_keys = false;
+        self.tone_keys.clear();
+        self.previous_action = None;
+        self.undone_action = None;
+        self.expanded = false;
     }
 
     /// Returns whether the buffer is empty (contains no input characters).
@@ -648,7 +1401,7 @@ impl<'def> IncrementalBuffer<'def> {
 /// ```
 #[inline]
 #[must_use]
-pub fn transform_buffer_incremental(definition: &Definition) -> IncrementalBuffer<'_> {
+pub fn transform_buffer_incremental<D: TypingDefinition>(definition: &D) -> IncrementalBuffer<'_> {
     IncrementalBuffer::new(definition)
 }
 
@@ -670,8 +1423,8 @@ pub fn transform_buffer_incremental(definition: &Definition) -> IncrementalBuffe
 /// ```
 #[inline]
 #[must_use]
-pub fn transform_buffer_incremental_with_style(
-    definition: &Definition,
+pub fn transform_buffer_incremental_with_style<D: TypingDefinition>(
+    definition: &D,
     accent_style: AccentStyle,
 ) -> IncrementalBuffer<'_> {
     IncrementalBuffer::new_with_style(definition, accent_style)
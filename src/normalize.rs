@@ -0,0 +1,272 @@
+//! Composition & decomposition of Vietnamese letters.
+//!
+//! Most of this crate works on precomposed (NFC) characters such as `ệ`. Text coming from macOS file names or some
+//! web forms is often decomposed (NFD) instead: the base letter is followed by combining marks, e.g. `e` +
+//! `U+0323` (dot below) + `U+0302` (circumflex). This module converts between the two forms for the Vietnamese
+//! repertoire, without depending on the full Unicode normalization tables.
+//!
+//! The combining marks handled are the five tone marks (`U+0300`, `U+0301`, `U+0303`, `U+0309` & `U+0323`) and the
+//! three letter modifications (`U+0302`, `U+0306` & `U+031B`). Other characters are kept as is.
+//!
+//! ## Example
+//!
+//! ```
+//! use vi::normalize::{compose, decompose};
+//!
+//! let decomposed = "Vie\u{323}\u{302}t";
+//! assert_eq!(compose(decomposed), "Việt");
+//! assert_eq!(decompose("Việt"), decomposed);
+//! ```
+use std::borrow::Cow;
+
+use crate::{
+    editing::{add_modification_char, add_tone_char},
+    maps::{BREVE_MAP, CIRCUMFLEX_MAP, HORN_MAP},
+    parsing::extract_tone_char,
+    processor::{LetterModification, ToneMark},
+    util::clean_char,
+};
+
+/// The combining marks used to write Vietnamese, with the tone mark or letter modification they stand for.
+const MARKS: [(char, Mark); 8] = [
+    ('\u{300}', Mark::Tone(ToneMark::Grave)),
+    ('\u{301}', Mark::Tone(ToneMark::Acute)),
+    ('\u{303}', Mark::Tone(ToneMark::Tilde)),
+    ('\u{309}', Mark::Tone(ToneMark::HookAbove)),
+    ('\u{323}', Mark::Tone(ToneMark::Underdot)),
+    (
+        '\u{302}',
+        Mark::Modification(LetterModification::Circumflex),
+    ),
+    ('\u{306}', Mark::Modification(LetterModification::Breve)),
+    ('\u{31B}', Mark::Modification(LetterModification::Horn)),
+];
+
+/// A combining mark, either a tone mark or a letter modification.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Mark {
+    Tone(ToneMark),
+    Modification(LetterModification),
+}
+
+impl Mark {
+    fn from_char(ch: char) -> Option<Self> {
+        MARKS
+            .iter()
+            .find(|(mark_ch, _)| *mark_ch == ch)
+            .map(|(_, mark)| *mark)
+    }
+
+    fn to_char(self) -> Option<char> {
+        MARKS
+            .iter()
+            .find(|(_, mark)| *mark == self)
+            .map(|(ch, _)| *ch)
+    }
+
+    /// The Unicode canonical combining class of the mark, which orders the marks of a decomposed letter.
+    const fn combining_class(self) -> u8 {
+        match self {
+            Self::Modification(LetterModification::Horn) => 216,
+            Self::Tone(ToneMark::Underdot) => 220,
+            _ => 230,
+        }
+    }
+}
+
+/// Check if a character is one of the combining marks used to write Vietnamese.
+///
+/// # Examples
+///
+/// ```
+/// use vi::normalize::is_combining_mark;
+///
+/// assert!(is_combining_mark('\u{301}'));
+/// assert!(is_combining_mark('\u{31B}'));
+/// assert!(!is_combining_mark('á'));
+/// ```
+#[must_use]
+pub const fn is_combining_mark(ch: char) -> bool {
+    matches!(
+        ch,
+        '\u{300}'
+            | '\u{301}'
+            | '\u{302}'
+            | '\u{303}'
+            | '\u{306}'
+            | '\u{309}'
+            | '\u{31B}'
+            | '\u{323}'
+    )
+}
+
+/// Compose the decomposed Vietnamese letters of a string into precomposed characters.
+///
+/// The marks following a letter can be in any order. Marks that can't be combined with the letter, like a second
+/// tone mark, are kept as combining marks. The input is borrowed when it doesn't contain any combining mark.
+///
+/// # Examples
+///
+/// ```
+/// use vi::normalize::compose;
+///
+/// assert_eq!(compose("Tie\u{302}\u{301}ng Vie\u{323}\u{302}t"), "Tiếng Việt");
+/// assert_eq!(compose("ngu\u{31B}o\u{31B}\u{300}i"), "người");
+/// assert_eq!(compose("xin chào"), "xin chào");
+/// ```
+#[must_use]
+pub fn compose(input: &str) -> Cow<'_, str> {
+    if !input.chars().any(is_combining_mark) {
+        return Cow::Borrowed(input);
+    }
+
+    let mut result = String::with_capacity(input.len());
+    let mut chars = input.chars().peekable();
+    while let Some(ch) = chars.next() {
+        let mut letter = ch;
+        let mut has_tone = extract_tone_char(ch).is_some();
+        let mut rest = Vec::new();
+        while let Some(mark) = chars.peek().copied().and_then(Mark::from_char) {
+            chars.next();
+            let combined = match mark {
+                Mark::Tone(_) if has_tone => letter,
+                Mark::Tone(tone_mark) => add_tone_char(letter, &tone_mark),
+                Mark::Modification(modification) => add_modification_char(letter, &modification),
+            };
+            if combined == letter {
+                rest.extend(mark.to_char());
+            } else {
+                has_tone |= matches!(mark, Mark::Tone(_));
+                letter = combined;
+            }
+        }
+        result.push(letter);
+        result.extend(rest);
+    }
+    Cow::Owned(result)
+}
+
+/// Decompose the precomposed Vietnamese letters of a string into base letters followed by combining marks.
+///
+/// The marks are written in the Unicode canonical order, so the result matches the NFD form of the input. `đ` has
+/// no decomposition and is kept as is. The input is borrowed when it doesn't contain any letter to decompose.
+///
+/// # Examples
+///
+/// ```
+/// use vi::normalize::decompose;
+///
+/// assert_eq!(decompose("ấ"), "a\u{302}\u{301}");
+/// assert_eq!(decompose("ợ"), "o\u{31B}\u{323}");
+/// assert_eq!(decompose("đi"), "đi");
+/// ```
+#[must_use]
+pub fn decompose(input: &str) -> Cow<'_, str> {
+    if input.chars().all(|ch| decompose_char(ch).is_none()) {
+        return Cow::Borrowed(input);
+    }
+
+    let mut result = String::with_capacity(input.len() * 2);
+    for ch in input.chars() {
+        match decompose_char(ch) {
+            Some((base, marks)) => {
+                result.push(base);
+                result.extend(marks.into_iter().flatten().filter_map(Mark::to_char));
+            }
+            None => result.push(ch),
+        }
+    }
+    Cow::Owned(result)
+}
+
+/// Split a precomposed letter into its base letter and its marks in canonical order.
+fn decompose_char(ch: char) -> Option<(char, [Option<Mark>; 2])> {
+    let modification = if HORN_MAP.values().any(|c| *c == ch) {
+        Some(Mark::Modification(LetterModification::Horn))
+    } else if BREVE_MAP.values().any(|c| *c == ch) {
+        Some(Mark::Modification(LetterModification::Breve))
+    } else if CIRCUMFLEX_MAP.values().any(|c| *c == ch) {
+        Some(Mark::Modification(LetterModification::Circumflex))
+    } else {
+        None
+    };
+    let tone = extract_tone_char(ch).map(Mark::Tone);
+
+    let marks = match (modification, tone) {
+        (None, None) => return None,
+        (Some(modification), Some(tone))
+            if tone.combining_class() < modification.combining_class() =>
+        {
+            [Some(tone), Some(modification)]
+        }
+        (modification, tone) => [modification, tone],
+    };
+    Some((clean_char(ch), marks))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compose_in_any_mark_order() {
+        assert_eq!(compose("e\u{323}\u{302}"), "ệ");
+        assert_eq!(compose("e\u{302}\u{323}"), "ệ");
+        assert_eq!(compose("ê\u{323}"), "ệ");
+        assert_eq!(compose("U\u{31B}\u{309}"), "Ử");
+    }
+
+    #[test]
+    fn compose_keeps_unknown_marks() {
+        assert_eq!(compose("a\u{301}\u{300}"), "á\u{300}");
+        assert_eq!(compose("b\u{301}"), "b\u{301}");
+        assert_eq!(compose("\u{301}a"), "\u{301}a");
+        assert!(matches!(compose("việt"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn decompose_canonical_order() {
+        assert_eq!(decompose("ệ"), "e\u{323}\u{302}");
+        assert_eq!(decompose("Ử"), "U\u{31B}\u{309}");
+        assert_eq!(decompose("ặ"), "a\u{323}\u{306}");
+        assert_eq!(decompose("ờ"), "o\u{31B}\u{300}");
+        assert_eq!(decompose("Ê"), "E\u{302}");
+        assert!(matches!(decompose("đường"), Cow::Owned(_)));
+        assert!(matches!(decompose("đi"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn round_trip_every_letter() {
+        use crate::maps::{ACCUTE_MAP, DOT_MAP, GRAVE_MAP, HOOK_ABOVE_MAP, TILDE_MAP};
+
+        let maps = [
+            &ACCUTE_MAP,
+            &GRAVE_MAP,
+            &HOOK_ABOVE_MAP,
+            &TILDE_MAP,
+            &DOT_MAP,
+            &CIRCUMFLEX_MAP,
+            &BREVE_MAP,
+            &HORN_MAP,
+        ];
+        for ch in maps.iter().flat_map(|map| map.values()) {
+            let letter = ch.to_string();
+            let decomposed = decompose(&letter);
+            assert!(decomposed.chars().skip(1).all(is_combining_mark), "{ch}");
+            assert_eq!(compose(&decomposed), letter);
+        }
+    }
+
+    #[test]
+    fn parse_decomposed_syllables() {
+        use crate::{parsing::extract_tone, syllable::Syllable, validation::is_valid_syllable};
+
+        let word = "ngu\u{31B}o\u{31B}\u{300}i";
+        assert!(is_valid_syllable(word));
+        assert_eq!(extract_tone(word), Some(ToneMark::Grave));
+
+        let mut syllable = Syllable::default();
+        syllable.set(word.to_owned());
+        assert_eq!(syllable.to_string(), "người");
+    }
+}
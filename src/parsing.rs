@@ -4,6 +4,7 @@ use crate::{
         ACCUTE_MAP, BREVE_MAP, CIRCUMFLEX_MAP, DOT_MAP, DYET_MAP, GRAVE_MAP, HOOK_ABOVE_MAP,
         HORN_MAP, TILDE_MAP,
     },
+    normalize::compose,
     processor::{LetterModification, ToneMark},
     util::is_vowel,
 };
@@ -66,31 +67,37 @@ pub fn parse_syllable(input: &str) -> IResult<&str, SyllableComponents<'_>> {
 /// Note: In some cases, there might be more than 1 modification. E.g đươc has 3 modifications.
 #[must_use]
 pub fn extract_letter_modifications(input: &str) -> Vec<(usize, LetterModification)> {
-    input
+    compose(input)
         .chars()
         .enumerate()
         .filter_map(|(index, ch)| {
-            if HORN_MAP.values().any(|c| *c == ch) {
-                return Some((index, LetterModification::Horn));
-            }
-            if BREVE_MAP.values().any(|c| *c == ch) {
-                return Some((index, LetterModification::Breve));
-            }
-            if CIRCUMFLEX_MAP.values().any(|c| *c == ch) {
-                return Some((index, LetterModification::Circumflex));
-            }
-            if DYET_MAP.values().any(|c| *c == ch) {
-                return Some((index, LetterModification::Dyet));
-            }
-            None
+            extract_letter_modification_char(ch).map(|modification| (index, modification))
         })
         .collect()
 }
 
+/// Extract a letter modification from an input char.
+#[must_use]
+pub fn extract_letter_modification_char(ch: char) -> Option<LetterModification> {
+    if HORN_MAP.values().any(|c| *c == ch) {
+        return Some(LetterModification::Horn);
+    }
+    if BREVE_MAP.values().any(|c| *c == ch) {
+        return Some(LetterModification::Breve);
+    }
+    if CIRCUMFLEX_MAP.values().any(|c| *c == ch) {
+        return Some(LetterModification::Circumflex);
+    }
+    if DYET_MAP.values().any(|c| *c == ch) {
+        return Some(LetterModification::Dyet);
+    }
+    None
+}
+
 /// Extract a tone mark from an input string. There can only be one tone mark.
 #[must_use]
 pub fn extract_tone(input: &str) -> Option<ToneMark> {
-    for ch in input.chars() {
+    for ch in compose(input).chars() {
         let Some(tone_mark) = extract_tone_char(ch) else {
             continue;
         };
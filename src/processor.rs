@@ -6,7 +6,7 @@ use super::maps::{BREVE_MAP, CIRCUMFLEX_MAP, DYET_MAP, HORN_MAP};
 use crate::{editing::get_modification_positions, syllable::Syllable};
 
 /// Maximum length of a Vietnamese "syllable" is 7 letters long (nghiêng)
-const MAX_WORD_LENGTH: usize = 7;
+pub(crate) const MAX_WORD_LENGTH: usize = 7;
 
 /// Vietnamese tone marks.
 ///
@@ -0,0 +1,799 @@
+//! Restoring the diacritics of text typed without them.
+//!
+//! Text such as `tieng viet rat hay` is easy to read for a Vietnamese speaker, but every unaccented syllable
+//! can stand for several syllables: `viet` can be `viết` or `việt`. [`restore_diacritics`] picks the most likely
+//! accented syllable from its neighbours using an [`NgramModel`], a syllable bigram model trained from a corpus
+//! of accented text.
+//!
+//! The candidates of an unaccented syllable are the valid syllables it can be written as (see [`candidates`]).
+//! Every restored syllable comes with the probability of each candidate, so that callers can tell which
+//! syllables the model is unsure about. Words that already have diacritics are kept as is and help restoring
+//! their neighbours. Syllables are restored in the new accent style, use
+//! [`convert_accent_style`](crate::accent::convert_accent_style) to get the old style.
+//!
+//! ## Model format
+//!
+//! A model is saved with [`NgramModel::to_bytes`] in a compact binary format. All integers are LEB128 varints:
+//!
+//! ```text
+//! "VIRM" version
+//! syllable-count  (byte-length utf-8-bytes count)*
+//! bigram-count    (first-syllable-delta second-syllable count)*
+//! ```
+//!
+//! Syllables are referred to by their position in the syllable list. Bigrams are sorted by their first syllable,
+//! whose position is written as the difference with the one of the previous bigram.
+//!
+//! ## Example
+//!
+//! ```
+//! use vi::restoration::{restore_diacritics, NgramModel};
+//!
+//! let mut model = NgramModel::new();
+//! model.train("Tiếng Việt rất hay. Tôi viết tiếng Việt.");
+//!
+//! let restoration = restore_diacritics("tieng viet rat hay", &model);
+//! assert_eq!(restoration.text, "tiếng việt rất hay");
+//! assert!(restoration.tokens[1].confidence > 0.5);
+//! ```
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    io::{BufRead, BufReader},
+    iter,
+    path::Path,
+};
+
+use crate::{
+    abbreviation::match_case,
+    accent::convert_accent_style,
+    normalize::compose,
+    processor::{AccentStyle, ToneMark, MAX_WORD_LENGTH},
+    spelling::{is_correctly_spelled, VOWELS},
+    syllable::Syllable,
+    util::{clean_char, clean_string, word_ranges},
+};
+
+const MAGIC: &[u8; 4] = b"VIRM";
+const VERSION: u8 = 1;
+
+/// The weight of the bigram probability against the unigram probability of a syllable.
+const BIGRAM_WEIGHT: f64 = 0.9;
+
+const TONE_MARKS: [Option<ToneMark>; 6] = [
+    None,
+    Some(ToneMark::Acute),
+    Some(ToneMark::Grave),
+    Some(ToneMark::HookAbove),
+    Some(ToneMark::Tilde),
+    Some(ToneMark::Underdot),
+];
+
+/// An error that happened while loading or saving an n-gram model.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ModelError {
+    /// The model or corpus file cannot be read or written.
+    Io(std::io::Error),
+    /// The data doesn't start with the model header.
+    InvalidHeader,
+    /// The model was saved in a format version that isn't supported.
+    UnsupportedVersion(u8),
+    /// The model data is truncated or corrupted.
+    InvalidData {
+        /// The byte index where the data stops making sense.
+        index: usize,
+    },
+}
+
+impl Display for ModelError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ModelError::Io(error) => write!(f, "Cannot access the model: {error}"),
+            ModelError::InvalidHeader => write!(f, "The data is not an n-gram model"),
+            ModelError::UnsupportedVersion(version) => {
+                write!(f, "Unsupported model version {version}")
+            }
+            ModelError::InvalidData { index } => write!(f, "Invalid model data at byte {index}"),
+        }
+    }
+}
+
+impl std::error::Error for ModelError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ModelError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for ModelError {
+    fn from(error: std::io::Error) -> Self {
+        ModelError::Io(error)
+    }
+}
+
+/// A syllable bigram model trained from accented Vietnamese text.
+///
+/// Syllables are counted in lowercase and in the new accent style. Two syllables form a bigram when they're only
+/// separated by whitespace, so punctuation and words that aren't Vietnamese syllables break the sequence.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct NgramModel {
+    syllables: Vec<String>,
+    indexes: HashMap<String, u32>,
+    counts: Vec<u32>,
+    bigrams: HashMap<(u32, u32), u32>,
+    total: u64,
+}
+
+impl NgramModel {
+    /// Creates an empty model.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Trains a model from a corpus file of accented text.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ModelError::Io`] if the file cannot be read or isn't valid UTF-8.
+    pub fn train_file<P: AsRef<Path>>(path: P) -> Result<Self, ModelError> {
+        let mut model = Self::new();
+        for line in BufReader::new(std::fs::File::open(path)?).lines() {
+            model.train(&line?);
+        }
+        Ok(model)
+    }
+
+    /// Counts the syllables & bigrams of an accented text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vi::restoration::NgramModel;
+    ///
+    /// let mut model = NgramModel::new();
+    /// model.train("Hòa bình, hoà hợp");
+    /// assert_eq!(model.count("hoà"), 2);
+    /// assert_eq!(model.bigram_count("hoà", "bình"), 1);
+    /// assert_eq!(model.bigram_count("bình", "hoà"), 0);
+    /// ```
+    pub fn train(&mut self, text: &str) {
+        let text = compose(text);
+        let mut previous: Option<(u32, usize)> = None;
+        for (start, end) in word_ranges(&text) {
+            let Some(syllable) = normalized_syllable(&text[start..end]) else {
+                previous = None;
+                continue;
+            };
+            let index = self.intern(syllable);
+            if let Some(count) = self.counts.get_mut(index as usize) {
+                *count = count.saturating_add(1);
+            }
+            self.total += 1;
+            if let Some((previous_index, previous_end)) = previous {
+                if text[previous_end..start].chars().all(char::is_whitespace) {
+                    let count = self.bigrams.entry((previous_index, index)).or_default();
+                    *count = count.saturating_add(1);
+                }
+            }
+            previous = Some((index, end));
+        }
+    }
+
+    /// The number of distinct syllables of the model.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.syllables.len()
+    }
+
+    /// Indicates whether the model has no syllable.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.syllables.is_empty()
+    }
+
+    /// The number of times a lowercase syllable was seen while training.
+    #[must_use]
+    pub fn count(&self, syllable: &str) -> u32 {
+        self.index(syllable)
+            .and_then(|index| self.counts.get(index as usize))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// The number of times a lowercase syllable was seen right after another one while training.
+    #[must_use]
+    pub fn bigram_count(&self, first: &str, second: &str) -> u32 {
+        self.index(first)
+            .zip(self.index(second))
+            .and_then(|bigram| self.bigrams.get(&bigram))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Serializes the model in its compact binary format.
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(VERSION);
+
+        write_varint(&mut bytes, self.syllables.len() as u64);
+        for (syllable, count) in self.syllables.iter().zip(&self.counts) {
+            write_varint(&mut bytes, syllable.len() as u64);
+            bytes.extend_from_slice(syllable.as_bytes());
+            write_varint(&mut bytes, u64::from(*count));
+        }
+
+        let mut bigrams: Vec<_> = self.bigrams.iter().collect();
+        bigrams.sort_unstable();
+        write_varint(&mut bytes, bigrams.len() as u64);
+        let mut previous_first = 0;
+        for ((first, second), count) in bigrams {
+            write_varint(&mut bytes, u64::from(first - previous_first));
+            write_varint(&mut bytes, u64::from(*second));
+            write_varint(&mut bytes, u64::from(*count));
+            previous_first = *first;
+        }
+        bytes
+    }
+
+    /// Deserializes a model saved with [`NgramModel::to_bytes`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ModelError::InvalidHeader`] or [`ModelError::UnsupportedVersion`] if the data isn't a model
+    /// this version can read, and [`ModelError::InvalidData`] if the model is truncated or corrupted.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ModelError> {
+        let mut reader = Reader { bytes, index: 0 };
+        if reader.take(MAGIC.len())? != MAGIC {
+            return Err(ModelError::InvalidHeader);
+        }
+        match reader.take(1)? {
+            [VERSION] => {}
+            [version] => return Err(ModelError::UnsupportedVersion(*version)),
+            _ => return Err(reader.error()),
+        }
+
+        let mut model = Self::new();
+        for _ in 0..reader.varint()? {
+            let length = reader.usize()?;
+            let syllable = std::str::from_utf8(reader.take(length)?)
+                .map_err(|_| reader.error())?
+                .to_owned();
+            let count = reader.u32()?;
+            if model.indexes.contains_key(&syllable) {
+                return Err(reader.error());
+            }
+            model.intern(syllable);
+            if let Some(last) = model.counts.last_mut() {
+                *last = count;
+            }
+            model.total += u64::from(count);
+        }
+
+        let mut first = 0u32;
+        for _ in 0..reader.varint()? {
+            first = first
+                .checked_add(reader.u32()?)
+                .ok_or_else(|| reader.error())?;
+            let second = reader.u32()?;
+            let count = reader.u32()?;
+            if first as usize >= model.len() || second as usize >= model.len() {
+                return Err(reader.error());
+            }
+            if model.bigrams.insert((first, second), count).is_some() {
+                return Err(reader.error());
+            }
+        }
+
+        if reader.index != bytes.len() {
+            return Err(reader.error());
+        }
+        Ok(model)
+    }
+
+    /// Saves the model to a file in its compact binary format.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ModelError::Io`] if the file cannot be written.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), ModelError> {
+        Ok(std::fs::write(path, self.to_bytes())?)
+    }
+
+    /// Loads a model saved with [`NgramModel::save`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ModelError::Io`] if the file cannot be read, or any other [`ModelError`] if it isn't a valid
+    /// model.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, ModelError> {
+        Self::from_bytes(&std::fs::read(path)?)
+    }
+
+    fn index(&self, syllable: &str) -> Option<u32> {
+        self.indexes.get(syllable).copied()
+    }
+
+    fn intern(&mut self, syllable: String) -> u32 {
+        if let Some(index) = self.index(&syllable) {
+            return index;
+        }
+        let index = u32::try_from(self.syllables.len()).unwrap_or(u32::MAX);
+        self.indexes.insert(syllable.clone(), index);
+        self.syllables.push(syllable);
+        self.counts.push(0);
+        index
+    }
+
+    /// The probability of a syllable following another one, interpolated with the add-one smoothed unigram
+    /// probability so that unseen syllables & bigrams keep a small probability.
+    #[allow(clippy::cast_precision_loss)]
+    fn probability(&self, previous: Option<&str>, syllable: &str) -> f64 {
+        let unigram = (f64::from(self.count(syllable)) + 1.0)
+            / (self.total as f64 + self.syllables.len() as f64 + 1.0);
+        let Some(previous) = previous.filter(|previous| self.count(previous) > 0) else {
+            return unigram;
+        };
+        let bigram =
+            f64::from(self.bigram_count(previous, syllable)) / f64::from(self.count(previous));
+        BIGRAM_WEIGHT * bigram + (1.0 - BIGRAM_WEIGHT) * unigram
+    }
+}
+
+/// An accented syllable that an unaccented syllable may stand for.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Candidate {
+    /// The accented syllable, in the case of the original syllable.
+    pub syllable: String,
+    /// The probability of the candidate given the rest of the text, between 0 and 1.
+    pub probability: f64,
+}
+
+/// An unaccented syllable of a text, with the accented syllables it may stand for.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RestoredToken {
+    /// The byte index of the first character of the syllable in the text.
+    pub start: usize,
+    /// The byte index after the last character of the syllable in the text.
+    pub end: usize,
+    /// The most likely accented syllable.
+    pub restored: String,
+    /// The probability of the restored syllable, between 0 and 1.
+    pub confidence: f64,
+    /// The candidates of the syllable, from the most to the least likely.
+    pub candidates: Vec<Candidate>,
+}
+
+/// The result of [`restore_diacritics`].
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Restoration {
+    /// The text with the diacritics of its unaccented syllables restored.
+    pub text: String,
+    /// The unaccented syllables of the text, in the order of the text.
+    pub tokens: Vec<RestoredToken>,
+}
+
+/// Lists the syllables an unaccented syllable can be written as once its diacritics are added.
+///
+/// Only the syllables that pass [`is_valid_syllable`](crate::validation::is_valid_syllable) and are correctly
+/// spelled (see [`is_correctly_spelled`]) are listed, in the case of the input. The syllable itself is listed
+/// if it's valid without diacritics.
+///
+/// # Examples
+///
+/// ```
+/// use vi::restoration::candidates;
+///
+/// let viet = candidates("viet");
+/// assert!(viet.contains(&"việt".to_owned()));
+/// assert!(viet.contains(&"viết".to_owned()));
+/// assert!(!viet.contains(&"viet".to_owned()));
+///
+/// assert_eq!(candidates("Do")[0], "Do");
+/// assert!(candidates("coffee").is_empty());
+/// ```
+#[must_use]
+pub fn candidates(syllable: &str) -> Vec<String> {
+    let lowercase = syllable.to_lowercase();
+    if clean_string(&lowercase) != lowercase || lowercase.chars().count() > MAX_WORD_LENGTH {
+        return Vec::new();
+    }
+
+    let mut bases = vec![String::new()];
+    for (index, ch) in lowercase.chars().enumerate() {
+        let variants = match ch {
+            'a' => vec!['a', 'ă', 'â'],
+            'e' => vec!['e', 'ê'],
+            'o' => vec!['o', 'ô', 'ơ'],
+            'u' => vec!['u', 'ư'],
+            'd' if index == 0 => vec!['d', 'đ'],
+            _ => vec![ch],
+        };
+        bases = bases
+            .iter()
+            .flat_map(|base| {
+                variants
+                    .iter()
+                    .map(move |variant| format!("{base}{variant}"))
+            })
+            .filter(|base| can_start_syllable(base))
+            .collect();
+    }
+
+    let mut result: Vec<String> = Vec::new();
+    for base in bases {
+        let mut syllable = Syllable::default();
+        syllable.set(base);
+        for tone_mark in TONE_MARKS {
+            syllable.tone_mark = tone_mark;
+            let candidate = syllable.to_string();
+            if is_correctly_spelled(&candidate)
+                && clean_string(&candidate) == lowercase
+                && !result.contains(&candidate)
+            {
+                result.push(candidate);
+            }
+        }
+    }
+    result
+        .into_iter()
+        .map(|candidate| match_case(syllable, &candidate))
+        .collect()
+}
+
+/// Checks whether the vowels of the start of a syllable, without tone mark, can start the vowels of a valid
+/// syllable.
+fn can_start_syllable(prefix: &str) -> bool {
+    let is_vowel = |ch: &char| matches!(clean_char(*ch), 'a' | 'e' | 'i' | 'o' | 'u' | 'y');
+    let initial: String = prefix.chars().take_while(|ch| !is_vowel(ch)).collect();
+    let vowel: String = prefix
+        .chars()
+        .skip_while(|ch| !is_vowel(ch))
+        .take_while(is_vowel)
+        .collect();
+    let starts_vowel = |vowel: &str| VOWELS.iter().any(|valid| valid.starts_with(vowel));
+    // The `i` of `gi` belongs to the initial consonant, like in `giữ`
+    starts_vowel(&vowel) || (initial == "g" && vowel.strip_prefix('i').is_some_and(starts_vowel))
+}
+
+/// Restores the diacritics of the unaccented syllables of a text with an n-gram model.
+///
+/// Every unaccented syllable with at least one candidate is replaced by the candidate with the highest
+/// probability given the whole sequence of syllables around it. Other words, like words that already have
+/// diacritics or that aren't Vietnamese, are kept as is.
+///
+/// # Examples
+///
+/// ```
+/// use vi::restoration::{restore_diacritics, NgramModel};
+///
+/// let mut model = NgramModel::new();
+/// model.train("Tôi viết thư. Thư viện Quốc gia.");
+///
+/// let restoration = restore_diacritics("Toi viet thu, thu vien", &model);
+/// assert_eq!(restoration.text, "Tôi viết thư, thư viện");
+/// assert_eq!(restoration.tokens[3].start, 14);
+/// assert_eq!(restoration.tokens[3].candidates[0].syllable, "thư");
+/// ```
+#[must_use]
+pub fn restore_diacritics(text: &str, model: &NgramModel) -> Restoration {
+    let mut chains: Vec<Vec<Position>> = Vec::new();
+    let mut chain: Vec<Position> = Vec::new();
+    let mut last_end = 0;
+    for (start, end) in word_ranges(text) {
+        if !text[last_end..start].chars().all(char::is_whitespace) && !chain.is_empty() {
+            chains.push(std::mem::take(&mut chain));
+        }
+        last_end = end;
+
+        let word = &text[start..end];
+        let restorable = candidates(word);
+        if !restorable.is_empty() {
+            chain.push(Position {
+                start,
+                end,
+                states: restorable
+                    .iter()
+                    .map(|state| state.to_lowercase())
+                    .collect(),
+                restorable: true,
+            });
+        } else if let Some(syllable) = normalized_syllable(word) {
+            chain.push(Position {
+                start,
+                end,
+                states: vec![syllable],
+                restorable: false,
+            });
+        } else if !chain.is_empty() {
+            chains.push(std::mem::take(&mut chain));
+        }
+    }
+    chains.push(chain);
+
+    let mut restoration = Restoration::default();
+    let mut last_end = 0;
+    for chain in chains {
+        for (position, probabilities) in chain.iter().zip(posteriors(model, &chain)) {
+            if !position.restorable {
+                continue;
+            }
+            let word = &text[position.start..position.end];
+            let mut candidates: Vec<Candidate> = position
+                .states
+                .iter()
+                .zip(probabilities)
+                .map(|(state, probability)| Candidate {
+                    syllable: match_case(word, state),
+                    probability,
+                })
+                .collect();
+            candidates.sort_by(|a, b| b.probability.total_cmp(&a.probability));
+            let Some(best) = candidates.first() else {
+                continue;
+            };
+
+            restoration.text.push_str(&text[last_end..position.start]);
+            restoration.text.push_str(&best.syllable);
+            last_end = position.end;
+            restoration.tokens.push(RestoredToken {
+                start: position.start,
+                end: position.end,
+                restored: best.syllable.clone(),
+                confidence: best.probability,
+                candidates,
+            });
+        }
+    }
+    restoration.text.push_str(&text[last_end..]);
+    restoration
+}
+
+/// A word of a sequence of syllables, with the lowercase syllables it may stand for.
+struct Position {
+    start: usize,
+    end: usize,
+    states: Vec<String>,
+    restorable: bool,
+}
+
+/// Computes the probability of every state of every position given the whole chain, with the forward-backward
+/// algorithm.
+fn posteriors(model: &NgramModel, chain: &[Position]) -> Vec<Vec<f64>> {
+    let mut forward: Vec<Vec<f64>> = Vec::with_capacity(chain.len());
+    let previous_positions = iter::once(None).chain(chain.iter().map(Some));
+    for (position, previous) in chain.iter().zip(previous_positions) {
+        let scores = position
+            .states
+            .iter()
+            .map(|state| match (previous, forward.last()) {
+                (Some(previous), Some(weights)) => previous
+                    .states
+                    .iter()
+                    .zip(weights)
+                    .map(|(previous, weight)| weight * model.probability(Some(previous), state))
+                    .sum(),
+                _ => model.probability(None, state),
+            })
+            .collect();
+        forward.push(normalized(scores));
+    }
+
+    let mut backward: Vec<Vec<f64>> = Vec::with_capacity(chain.len());
+    let next_positions = iter::once(None).chain(chain.iter().rev().map(Some));
+    for (position, next) in chain.iter().rev().zip(next_positions) {
+        let scores = position
+            .states
+            .iter()
+            .map(|state| match (next, backward.last()) {
+                (Some(next), Some(weights)) => next
+                    .states
+                    .iter()
+                    .zip(weights)
+                    .map(|(next, weight)| weight * model.probability(Some(state), next))
+                    .sum(),
+                _ => 1.0,
+            })
+            .collect();
+        backward.push(normalized(scores));
+    }
+    backward.reverse();
+
+    forward
+        .into_iter()
+        .zip(backward)
+        .map(|(forward, backward)| {
+            normalized(forward.iter().zip(backward).map(|(f, b)| f * b).collect())
+        })
+        .collect()
+}
+
+/// Scales scores so that they sum to 1, keeping the scores of an all-zero list equal.
+#[allow(clippy::cast_precision_loss)]
+fn normalized(scores: Vec<f64>) -> Vec<f64> {
+    let sum: f64 = scores.iter().sum();
+    if sum > 0.0 {
+        scores.into_iter().map(|score| score / sum).collect()
+    } else {
+        let uniform = 1.0 / scores.len() as f64;
+        scores.into_iter().map(|_| uniform).collect()
+    }
+}
+
+/// Returns the word as a lowercase syllable in the new accent style, if it's a correctly spelled syllable.
+fn normalized_syllable(word: &str) -> Option<String> {
+    let syllable = convert_accent_style(&compose(&word.to_lowercase()), &AccentStyle::New);
+    is_correctly_spelled(&syllable).then_some(syllable)
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn write_varint(bytes: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        bytes.push((value as u8 & 0x7F) | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+/// Reads the fields of a serialized model.
+struct Reader<'a> {
+    bytes: &'a [u8],
+    index: usize,
+}
+
+impl<'a> Reader<'a> {
+    const fn error(&self) -> ModelError {
+        ModelError::InvalidData { index: self.index }
+    }
+
+    fn take(&mut self, length: usize) -> Result<&'a [u8], ModelError> {
+        let bytes = self
+            .index
+            .checked_add(length)
+            .and_then(|end| self.bytes.get(self.index..end))
+            .ok_or_else(|| self.error())?;
+        self.index += length;
+        Ok(bytes)
+    }
+
+    fn varint(&mut self) -> Result<u64, ModelError> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let [byte] = self.take(1)? else {
+                return Err(self.error());
+            };
+            value |= u64::from(byte & 0x7F) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(self.error())
+    }
+
+    fn u32(&mut self) -> Result<u32, ModelError> {
+        u32::try_from(self.varint()?).map_err(|_| self.error())
+    }
+
+    fn usize(&mut self) -> Result<usize, ModelError> {
+        usize::try_from(self.varint()?).map_err(|_| self.error())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model() -> NgramModel {
+        let mut model = NgramModel::new();
+        model.train("Tiếng Việt rất hay. Tôi viết tiếng Việt, anh viết thư.");
+        model.train("Thư viện ở Hà Nội. Tôi đi thư viện.");
+        model
+    }
+
+    #[test]
+    fn candidates_are_valid_syllables() {
+        let candidates = candidates("duong");
+        for expected in ["đường", "dương", "đương", "dưỡng", "đuông"] {
+            assert!(candidates.contains(&expected.to_owned()), "{expected}");
+        }
+        assert!(
+            candidates
+                .iter()
+                .all(|candidate| is_correctly_spelled(candidate)
+                    && clean_string(candidate) == "duong")
+        );
+        assert!(super::candidates("VIET").contains(&"VIỆT".to_owned()));
+        assert!(super::candidates("việt").is_empty());
+        assert!(super::candidates("giu").contains(&"giữ".to_owned()));
+    }
+
+    #[test]
+    fn candidates_of_long_words() {
+        assert!(candidates("looooooooooooool").is_empty());
+        assert!(candidates("aaaaaaa").is_empty());
+        assert!(candidates("nghieng").contains(&"nghiêng".to_owned()));
+        assert!(candidates("khuyu").contains(&"khuỵu".to_owned()));
+    }
+
+    #[test]
+    fn train_counts() {
+        let model = model();
+        assert_eq!(model.count("tiếng"), 2);
+        assert_eq!(model.count("việt"), 2);
+        assert_eq!(model.bigram_count("tiếng", "việt"), 2);
+        // Punctuation breaks the sequence
+        assert_eq!(model.bigram_count("hay", "tôi"), 0);
+        assert_eq!(model.bigram_count("việt", "anh"), 0);
+    }
+
+    #[test]
+    fn restore_with_context() {
+        let model = model();
+        let restoration = restore_diacritics("Toi di thu vien o Ha Noi", &model);
+        assert_eq!(restoration.text, "Tôi đi thư viện ở Hà Nội");
+        assert_eq!(restoration.tokens.len(), 7);
+
+        let restoration = restore_diacritics("anh viet thu, tieng Việt", &model);
+        assert_eq!(restoration.text, "anh viết thư, tiếng Việt");
+        assert_eq!(restoration.tokens.len(), 4);
+        for token in &restoration.tokens {
+            let sum: f64 = token
+                .candidates
+                .iter()
+                .map(|candidate| candidate.probability)
+                .sum();
+            assert!((sum - 1.0).abs() < 1e-9);
+            assert!(token.confidence >= token.candidates.last().map_or(0.0, |c| c.probability));
+        }
+    }
+
+    #[test]
+    fn restore_decomposed_text() {
+        let model = model();
+        let restoration =
+            restore_diacritics("tieng Vie\u{323}\u{302}t, toi vie\u{302}\u{301}t", &model);
+        assert_eq!(
+            restoration.text,
+            "tiếng Vie\u{323}\u{302}t, tôi vie\u{302}\u{301}t"
+        );
+        assert_eq!(restoration.tokens.len(), 2);
+        assert_eq!(
+            normalized_syllable("Vie\u{323}\u{302}t").as_deref(),
+            Some("việt")
+        );
+    }
+
+    #[test]
+    fn serialize_round_trip() {
+        let model = model();
+        let bytes = model.to_bytes();
+        assert_eq!(NgramModel::from_bytes(&bytes).ok(), Some(model));
+
+        assert!(matches!(
+            NgramModel::from_bytes(b"VIRX\x01"),
+            Err(ModelError::InvalidHeader)
+        ));
+        assert!(matches!(
+            NgramModel::from_bytes(b"VIRM\x07"),
+            Err(ModelError::UnsupportedVersion(7))
+        ));
+        assert!(matches!(
+            NgramModel::from_bytes(bytes.get(..bytes.len() - 1).unwrap_or_default()),
+            Err(ModelError::InvalidData { .. })
+        ));
+
+        // The syllables `a` & `b`, then the bigram `a b` twice
+        let duplicate = b"VIRM\x01\x02\x01a\x01\x01b\x01\x02\x00\x01\x01\x00\x01\x01";
+        assert!(matches!(
+            NgramModel::from_bytes(duplicate),
+            Err(ModelError::InvalidData { .. })
+        ));
+        let distinct = b"VIRM\x01\x02\x01a\x01\x01b\x01\x02\x00\x01\x01\x00\x00\x01";
+        assert!(NgramModel::from_bytes(distinct).is_ok());
+    }
+}
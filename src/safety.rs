@@ -729,11 +729,47 @@ impl SafeAssemblyProcessor {
         }
     }
 
+    /// Process UTF-8 text in place safely, returning the length of the processed text
+    ///
+    /// The text is processed by the byte-level kernels, without converting it to characters.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AssemblyError` if the buffer isn't valid UTF-8, or if processing is cancelled or times out.
+    pub fn process_bytes_safe(&self, bytes: &mut [u8]) -> Result<usize, AssemblyError> {
+        if bytes.is_empty() {
+            return Ok(0);
+        }
+
+        let start_time = Instant::now();
+        self.metrics.record_start();
+
+        let assembly_interface = crate::asm::direct_asm::get_assembly_interface();
+        let written = match assembly_interface.process_utf8_bulk_safe(bytes, &self.control) {
+            Err(AssemblyError::Cancelled) => {
+                self.metrics.record_cancellation();
+                if self.control.timed_out() {
+                    return Err(AssemblyError::Timeout);
+                }
+                return Err(AssemblyError::Cancelled);
+            }
+            result => result?,
+        };
+
+        let overhead_ns = u64::try_from(start_time.elapsed().as_nanos()).unwrap_or(u64::MAX);
+        self.metrics.record_completion(overhead_ns);
+
+        Ok(written)
+    }
+
     /// Process string safely
     pub fn process_string_safe(&self, input: &str) -> Result<String, AssemblyError> {
-        let chars: Vec<char> = input.chars().collect();
-        let processed = self.process_chars_safe(&chars)?;
-        Ok(processed.into_iter().collect())
+        let mut bytes = input.as_bytes().to_vec();
+        let written = self.process_bytes_safe(&mut bytes)?;
+        bytes.truncate(written);
+        String::from_utf8(bytes).map_err(|_| {
+            AssemblyError::ExecutionError("Assembly returned invalid UTF-8".to_string())
+        })
     }
 
     /// Get safety metrics
@@ -0,0 +1,364 @@
+//! Searching text regardless of its diacritics.
+//!
+//! [`clean_string`](crate::util::clean_string) makes text easy to match, but the positions in the cleaned text
+//! don't tell where a match is in the original text. A [`SearchIndex`] keeps the folded text together with a map
+//! of offsets back to the original, so matches are reported as byte & character ranges of the original text.
+//!
+//! How much of the text is folded is chosen with a [`Sensitivity`]. Runs of whitespace are always folded into a
+//! single space, and decomposed letters match their precomposed form.
+//!
+//! ## Example
+//!
+//! ```
+//! use vi::search::SearchIndex;
+//!
+//! let text = "Tôi yêu Việt Nam";
+//! let index = SearchIndex::new(text);
+//!
+//! let found = index.find("viet nam").unwrap();
+//! assert_eq!(&text[found.start..found.end], "Việt Nam");
+//! assert_eq!((found.char_start, found.char_end), (8, 16));
+//! ```
+use crate::{
+    case::to_lower_char,
+    editing::add_modification_char,
+    normalize::{compose, is_combining_mark},
+    parsing::extract_letter_modification_char,
+    util::clean_char,
+};
+
+/// What differences between the query & the text are ignored when searching. Every level also ignores the
+/// differences ignored by the levels before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum Sensitivity {
+    /// Nothing is ignored.
+    Exact,
+    /// The case is ignored, e.g. `việt` matches `Việt` but not `viết` or `viet`.
+    CaseInsensitive,
+    /// The case & the tone marks are ignored, e.g. `viêt` matches `Việt` & `viết` but not `viet`.
+    IgnoreTones,
+    /// The case, the tone marks & the letter modifications are ignored, e.g. `viet` matches `Việt` (default).
+    #[default]
+    IgnoreDiacritics,
+}
+
+/// A match of a query in a text, with its ranges in the original text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Match {
+    /// The byte index of the first character of the match.
+    pub start: usize,
+    /// The byte index after the last character of the match.
+    pub end: usize,
+    /// The character index of the first character of the match.
+    pub char_start: usize,
+    /// The character index after the last character of the match.
+    pub char_end: usize,
+}
+
+/// The position in the original text of a byte of the folded text.
+#[derive(Debug, Clone, Copy)]
+struct Offset {
+    byte: usize,
+    char: usize,
+}
+
+/// A text folded for searching, with the map of offsets back to the original text.
+#[derive(Debug, Clone)]
+pub struct SearchIndex<'a> {
+    text: &'a str,
+    sensitivity: Sensitivity,
+    folded: String,
+    /// The position in the original text of every byte of the folded text.
+    offsets: Vec<Offset>,
+    /// The position in the original text of the end of the letter & marks, or the whitespace, every byte of the
+    /// folded text comes from.
+    ends: Vec<Offset>,
+}
+
+impl<'a> SearchIndex<'a> {
+    /// Creates an index of a text that ignores its diacritics & case.
+    #[must_use]
+    pub fn new(text: &'a str) -> Self {
+        Self::new_with_sensitivity(text, Sensitivity::default())
+    }
+
+    /// Creates an index of a text with a sensitivity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vi::search::{SearchIndex, Sensitivity};
+    ///
+    /// let index = SearchIndex::new_with_sensitivity("Việt Nam, viết văn", Sensitivity::IgnoreTones);
+    /// assert_eq!(index.find_all("viêt").len(), 2);
+    /// assert!(index.find("van").is_none());
+    /// ```
+    #[must_use]
+    pub fn new_with_sensitivity(text: &'a str, sensitivity: Sensitivity) -> Self {
+        let mut folded = String::with_capacity(text.len());
+        let mut offsets = Vec::with_capacity(text.len());
+        let mut ends = Vec::with_capacity(text.len());
+        let mut chars = text.char_indices().enumerate().peekable();
+        let mut cluster = String::new();
+        while let Some((char_index, (byte_index, ch))) = chars.next() {
+            let offset = Offset {
+                byte: byte_index,
+                char: char_index,
+            };
+            if ch.is_whitespace() {
+                while chars.next_if(|(_, (_, ch))| ch.is_whitespace()).is_some() {}
+                folded.push(' ');
+                offsets.push(offset);
+            } else {
+                // A letter is folded together with the combining marks that follow it
+                cluster.clear();
+                cluster.push(ch);
+                while let Some((_, (_, mark))) =
+                    chars.next_if(|(_, (_, ch))| is_combining_mark(*ch))
+                {
+                    cluster.push(mark);
+                }
+                for ch in compose(&cluster)
+                    .chars()
+                    .filter_map(|ch| fold_char(ch, sensitivity))
+                {
+                    folded.push(ch);
+                    offsets.extend(std::iter::repeat(offset).take(ch.len_utf8()));
+                }
+            }
+
+            // A match ending inside the folded letter & marks ends after all of them
+            let end = chars.peek().map_or(
+                Offset {
+                    byte: text.len(),
+                    char: text.chars().count(),
+                },
+                |(char_index, (byte_index, _))| Offset {
+                    byte: *byte_index,
+                    char: *char_index,
+                },
+            );
+            ends.resize(offsets.len(), end);
+        }
+
+        Self {
+            text,
+            sensitivity,
+            folded,
+            offsets,
+            ends,
+        }
+    }
+
+    /// The original text of the index.
+    #[must_use]
+    pub const fn text(&self) -> &'a str {
+        self.text
+    }
+
+    /// The folded text that queries are matched against.
+    #[must_use]
+    pub fn folded(&self) -> &str {
+        &self.folded
+    }
+
+    /// Finds the first match of a query in the text.
+    #[must_use]
+    pub fn find(&self, query: &str) -> Option<Match> {
+        self.matches(query).next()
+    }
+
+    /// Finds all the non-overlapping matches of a query in the text, in the order of the text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vi::search::SearchIndex;
+    ///
+    /// let text = "Đà Nẵng, da nang, ĐÀ  NẴNG";
+    /// let matches: Vec<&str> = SearchIndex::new(text)
+    ///     .find_all("Da Nang")
+    ///     .iter()
+    ///     .map(|found| &text[found.start..found.end])
+    ///     .collect();
+    /// assert_eq!(matches, ["Đà Nẵng", "da nang", "ĐÀ  NẴNG"]);
+    /// ```
+    #[must_use]
+    pub fn find_all(&self, query: &str) -> Vec<Match> {
+        self.matches(query).collect()
+    }
+
+    fn matches<'q>(&'q self, query: &str) -> impl Iterator<Item = Match> + 'q {
+        let query = fold(query, self.sensitivity);
+        let matches = if query.is_empty() {
+            Vec::new()
+        } else {
+            self.folded
+                .match_indices(&query)
+                .map(|(start, found)| (start, start + found.len()))
+                .collect()
+        };
+        matches.into_iter().filter_map(|(start, end)| {
+            let start = self.offsets.get(start)?;
+            let end = self.ends.get(end.checked_sub(1)?)?;
+            Some(Match {
+                start: start.byte,
+                end: end.byte,
+                char_start: start.char,
+                char_end: end.char,
+            })
+        })
+    }
+}
+
+/// Folds a text like a [`SearchIndex`] with the same sensitivity does.
+///
+/// # Examples
+///
+/// ```
+/// use vi::search::{fold, Sensitivity};
+///
+/// assert_eq!(fold("Tiếng  Việt", Sensitivity::IgnoreDiacritics), "tieng viet");
+/// assert_eq!(fold("Tiếng  Việt", Sensitivity::IgnoreTones), "tiêng viêt");
+/// assert_eq!(fold("Tiếng  Việt", Sensitivity::CaseInsensitive), "tiếng việt");
+/// ```
+#[must_use]
+pub fn fold(text: &str, sensitivity: Sensitivity) -> String {
+    SearchIndex::new_with_sensitivity(text, sensitivity).folded
+}
+
+/// Finds all the non-overlapping matches of a query in a text. See [`SearchIndex::find_all`].
+#[must_use]
+pub fn find_all(text: &str, query: &str, sensitivity: Sensitivity) -> Vec<Match> {
+    SearchIndex::new_with_sensitivity(text, sensitivity).find_all(query)
+}
+
+/// Folds a character of the text, or returns `None` if it's ignored.
+fn fold_char(ch: char, sensitivity: Sensitivity) -> Option<char> {
+    if sensitivity == Sensitivity::Exact {
+        return Some(ch);
+    }
+
+    let lowercase = match to_lower_char(ch) {
+        lowercase if lowercase != ch => lowercase,
+        _ => {
+            let mut lowercase = ch.to_lowercase();
+            lowercase
+                .next()
+                .filter(|_| lowercase.next().is_none())
+                .unwrap_or(ch)
+        }
+    };
+    match sensitivity {
+        Sensitivity::Exact | Sensitivity::CaseInsensitive => Some(lowercase),
+        // Marks that couldn't be combined with their letter are dropped with the other diacritics
+        _ if is_combining_mark(lowercase) => None,
+        Sensitivity::IgnoreTones => Some(
+            extract_letter_modification_char(lowercase)
+                .map_or(clean_char(lowercase), |modification| {
+                    add_modification_char(clean_char(lowercase), &modification)
+                }),
+        ),
+        Sensitivity::IgnoreDiacritics => Some(clean_char(lowercase)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn found<'a>(text: &'a str, query: &str, sensitivity: Sensitivity) -> Vec<&'a str> {
+        find_all(text, query, sensitivity)
+            .iter()
+            .map(|found| &text[found.start..found.end])
+            .collect()
+    }
+
+    #[test]
+    fn sensitivity_levels() {
+        let text = "Việt, viết, viet, VIỆT, Viêt";
+        assert_eq!(found(text, "Việt", Sensitivity::Exact), ["Việt"]);
+        assert_eq!(
+            found(text, "việt", Sensitivity::CaseInsensitive),
+            ["Việt", "VIỆT"]
+        );
+        assert_eq!(
+            found(text, "viêt", Sensitivity::IgnoreTones),
+            ["Việt", "viết", "VIỆT", "Viêt"]
+        );
+        // The query is folded too
+        assert_eq!(
+            found(text, "VIẾT", Sensitivity::IgnoreTones),
+            ["Việt", "viết", "VIỆT", "Viêt"]
+        );
+        assert_eq!(found(text, "viet", Sensitivity::IgnoreDiacritics).len(), 5);
+        assert_eq!(found(text, "đ", Sensitivity::IgnoreDiacritics).len(), 0);
+    }
+
+    #[test]
+    fn ranges_in_original_text() {
+        let text = "Tôi yêu Vie\u{323}\u{302}t  Nam!";
+        let index = SearchIndex::new(text);
+        assert_eq!(index.folded(), "toi yeu viet nam!");
+
+        let found = index.find("viet nam");
+        assert_eq!(
+            found,
+            Some(Match {
+                start: 10,
+                end: 23,
+                char_start: 8,
+                char_end: 19,
+            })
+        );
+        assert_eq!(
+            found.map(|found| &text[found.start..found.end]),
+            Some("Vie\u{323}\u{302}t  Nam")
+        );
+
+        let found = index.find("nam!");
+        assert_eq!(
+            found.map(|found| (found.end, found.char_end)),
+            Some((text.len(), text.chars().count()))
+        );
+    }
+
+    #[test]
+    fn uncomposable_clusters() {
+        let matches = find_all("x\u{301}y", "x", Sensitivity::CaseInsensitive);
+        assert_eq!(
+            matches,
+            [Match {
+                start: 0,
+                end: 3,
+                char_start: 0,
+                char_end: 2,
+            }]
+        );
+        assert_eq!(found("q\u{309}a", "q", Sensitivity::Exact), ["q\u{309}"]);
+        assert_eq!(
+            find_all("ax\u{301}", "x", Sensitivity::Exact)
+                .iter()
+                .map(|found| (found.end, found.char_end))
+                .collect::<Vec<_>>(),
+            [(4, 3)]
+        );
+        assert_eq!(
+            found("ba\u{301}\u{301}c", "bá", Sensitivity::CaseInsensitive),
+            ["ba\u{301}\u{301}"]
+        );
+        assert_eq!(
+            found("ba\u{301}\u{301}c", "bac", Sensitivity::IgnoreDiacritics),
+            ["ba\u{301}\u{301}c"]
+        );
+    }
+
+    #[test]
+    fn queries_without_match() {
+        let index = SearchIndex::new("Hà Nội");
+        assert_eq!(index.find(""), None);
+        assert_eq!(index.find("ha noi "), None);
+        assert_eq!(index.find_all("sài gòn"), []);
+        assert_eq!(index.text(), "Hà Nội");
+    }
+}
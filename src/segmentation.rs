@@ -0,0 +1,490 @@
+//! Splitting Vietnamese text into words.
+//!
+//! Vietnamese words are made of one or more syllables separated by spaces, like `học sinh` (student) or
+//! `Việt Nam`, so spaces don't tell where words end. [`segment`] finds the words of a text with a [`Dictionary`]:
+//! among all the ways to split a sequence of syllables into dictionary words, it picks the most probable one
+//! given the frequency of each word.
+//!
+//! Text without diacritics, like the output of [`clean_string`], is segmented too: an unaccented syllable matches
+//! the dictionary words it can stand for, so `hoc sinh` is found like `học sinh`.
+//!
+//! ## File format
+//!
+//! A dictionary can be loaded from a file with one word per line, optionally followed by its frequency:
+//!
+//! ```text
+//! # Comments start with `#` or `;`.
+//! học sinh 120
+//! Việt Nam 85
+//! # Words without frequency are counted once.
+//! cà phê
+//! ```
+//!
+//! ## Example
+//!
+//! ```
+//! use vi::segmentation::{segment, Dictionary, SegmentKind};
+//!
+//! let dictionary: Dictionary = "học sinh\nhọc\nsinh viên\nViệt Nam".parse()?;
+//!
+//! let text = "Học sinh Việt Nam học iPhone";
+//! let words: Vec<(&str, SegmentKind)> = segment(text, &dictionary)
+//!     .iter()
+//!     .map(|segment| (&text[segment.start..segment.end], segment.kind))
+//!     .collect();
+//! assert_eq!(
+//!     words,
+//!     [
+//!         ("Học sinh", SegmentKind::Word),
+//!         ("Việt Nam", SegmentKind::Word),
+//!         ("học", SegmentKind::Word),
+//!         ("iPhone", SegmentKind::Foreign),
+//!     ]
+//! );
+//! # Ok::<(), vi::segmentation::DictionaryError>(())
+//! ```
+use std::{collections::HashMap, fmt::Display, path::Path, str::FromStr};
+
+use crate::{
+    accent::convert_accent_style,
+    normalize::{compose, is_combining_mark},
+    processor::AccentStyle,
+    util::clean_string,
+    validation::is_valid_syllable,
+};
+
+/// An error that happened while loading a dictionary.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum DictionaryError {
+    /// The dictionary file cannot be read.
+    Io(std::io::Error),
+    /// The frequency of a word is not a number that fits in a `u32`.
+    InvalidFrequency {
+        /// The line number, starting from 1.
+        line: usize,
+    },
+}
+
+impl Display for DictionaryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DictionaryError::Io(error) => write!(f, "Cannot read the dictionary: {error}"),
+            DictionaryError::InvalidFrequency { line } => {
+                write!(f, "Invalid word frequency on line {line}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DictionaryError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DictionaryError::Io(error) => Some(error),
+            DictionaryError::InvalidFrequency { .. } => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for DictionaryError {
+    fn from(error: std::io::Error) -> Self {
+        DictionaryError::Io(error)
+    }
+}
+
+/// A set of words with their frequencies, used by [`segment`].
+///
+/// Words are matched regardless of case, accent style & composition, and their unaccented form is matched by
+/// unaccented text.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Dictionary {
+    words: HashMap<String, u32>,
+    clean_words: HashMap<String, u32>,
+    total: u64,
+    max_syllables: usize,
+}
+
+impl Dictionary {
+    /// Creates an empty dictionary.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a word to the dictionary, or adds to its frequency if it's already there.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vi::segmentation::Dictionary;
+    ///
+    /// let mut dictionary = Dictionary::new();
+    /// dictionary.insert("Việt  Nam", 3);
+    /// dictionary.insert("việt nam", 2);
+    /// assert_eq!(dictionary.frequency("VIỆT NAM"), Some(5));
+    /// assert_eq!(dictionary.frequency("viet nam"), Some(5));
+    /// assert_eq!(dictionary.frequency("việt"), None);
+    /// ```
+    pub fn insert(&mut self, word: &str, frequency: u32) {
+        let syllables: Vec<String> = word.split_whitespace().map(normalized).collect();
+        if syllables.is_empty() {
+            return;
+        }
+        self.max_syllables = self.max_syllables.max(syllables.len());
+        self.total += u64::from(frequency);
+
+        let key = syllables.join(" ");
+        let clean_key = clean_string(&key);
+        let count = self.words.entry(key).or_default();
+        *count = count.saturating_add(frequency);
+        let count = self.clean_words.entry(clean_key).or_default();
+        *count = count.saturating_add(frequency);
+    }
+
+    /// The frequency of a word, or `None` if it's not in the dictionary.
+    ///
+    /// A word without diacritics also matches the words it can stand for, with the sum of their frequencies.
+    #[must_use]
+    pub fn frequency(&self, word: &str) -> Option<u32> {
+        let syllables: Vec<String> = word.split_whitespace().map(normalized).collect();
+        self.lookup(&syllables)
+    }
+
+    /// Indicates whether a word is in the dictionary.
+    #[must_use]
+    pub fn contains(&self, word: &str) -> bool {
+        self.frequency(word).is_some()
+    }
+
+    /// The number of words of the dictionary.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    /// Indicates whether the dictionary has no word.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Loads a dictionary from a file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DictionaryError::Io`] if the file cannot be read, or [`DictionaryError::InvalidFrequency`] if a
+    /// frequency is too large.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, DictionaryError> {
+        std::fs::read_to_string(path)?.parse()
+    }
+
+    fn lookup(&self, syllables: &[String]) -> Option<u32> {
+        let key = syllables.join(" ");
+        if let Some(frequency) = self.words.get(&key) {
+            return Some(*frequency);
+        }
+        let clean_key = clean_string(&key);
+        if clean_key == key {
+            self.clean_words.get(&clean_key).copied()
+        } else {
+            None
+        }
+    }
+
+    /// The log probability of a sequence of syllables forming a word, or `None` if it's not a dictionary word.
+    #[allow(clippy::cast_precision_loss)]
+    fn log_probability(&self, syllables: &[String]) -> Option<f64> {
+        let frequency = self.lookup(syllables)?;
+        Some(((f64::from(frequency) + 1.0) / (self.total as f64 + 1.0)).ln())
+    }
+
+    /// The log probability of a syllable that is not in the dictionary, lower than the one of any dictionary word.
+    #[allow(clippy::cast_precision_loss)]
+    fn unknown_log_probability(&self) -> f64 {
+        (0.5 / (self.total as f64 + 1.0)).ln()
+    }
+}
+
+impl FromStr for Dictionary {
+    type Err = DictionaryError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut dictionary = Self::new();
+        for (index, line) in input.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+                continue;
+            }
+            let (word, frequency) = match line.rsplit_once(char::is_whitespace) {
+                Some((word, frequency)) if frequency.chars().all(|ch| ch.is_ascii_digit()) => {
+                    let frequency = frequency
+                        .parse()
+                        .map_err(|_| DictionaryError::InvalidFrequency { line: index + 1 })?;
+                    (word, frequency)
+                }
+                _ => (line, 1),
+            };
+            dictionary.insert(word, frequency);
+        }
+        Ok(dictionary)
+    }
+}
+
+/// What a [`Segment`] of a text is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SegmentKind {
+    /// A word of the dictionary.
+    Word,
+    /// A Vietnamese syllable that is not part of any dictionary word.
+    Unknown,
+    /// A token that is not a Vietnamese syllable, like a foreign word or a number.
+    Foreign,
+}
+
+/// A word of a text found by [`segment`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Segment {
+    /// The byte index of the first character of the word in the text.
+    pub start: usize,
+    /// The byte index after the last character of the word in the text.
+    pub end: usize,
+    /// What the word is.
+    pub kind: SegmentKind,
+}
+
+/// A token of a text: a run of letters, digits & combining marks.
+struct Token {
+    start: usize,
+    end: usize,
+    syllable: Option<String>,
+}
+
+/// Splits a text into words, using a dictionary.
+///
+/// The text is split into tokens made of letters, digits & combining marks. Tokens that are Vietnamese
+/// syllables and are only separated by whitespace are grouped into the most probable sequence of dictionary
+/// words. Other tokens are returned as [`SegmentKind::Foreign`] segments on their own. Whitespace &
+/// punctuation are not part of any segment.
+///
+/// # Examples
+///
+/// ```
+/// use vi::segmentation::{segment, Dictionary, SegmentKind};
+///
+/// let dictionary: Dictionary = "học sinh 10\nhọc 5\nsinh viên 8\nviên 2".parse()?;
+///
+/// let text = "hoc sinh vien";
+/// let words: Vec<&str> = segment(text, &dictionary)
+///     .iter()
+///     .map(|segment| &text[segment.start..segment.end])
+///     .collect();
+/// assert_eq!(words, ["hoc", "sinh vien"]);
+/// # Ok::<(), vi::segmentation::DictionaryError>(())
+/// ```
+#[must_use]
+pub fn segment(text: &str, dictionary: &Dictionary) -> Vec<Segment> {
+    let mut segments = Vec::new();
+    let mut run: Vec<Token> = Vec::new();
+    for token in tokens(text) {
+        let separated = run
+            .last()
+            .is_some_and(|last| !text[last.end..token.start].chars().all(char::is_whitespace));
+        if separated || token.syllable.is_none() {
+            segment_run(dictionary, &std::mem::take(&mut run), &mut segments);
+        }
+        if token.syllable.is_some() {
+            run.push(token);
+        } else {
+            segments.push(Segment {
+                start: token.start,
+                end: token.end,
+                kind: SegmentKind::Foreign,
+            });
+        }
+    }
+    segment_run(dictionary, &run, &mut segments);
+    segments
+}
+
+/// Finds the most probable sequence of words of a run of syllables separated by whitespace.
+fn segment_run(dictionary: &Dictionary, run: &[Token], segments: &mut Vec<Segment>) {
+    let syllables: Vec<String> = run
+        .iter()
+        .filter_map(|token| token.syllable.clone())
+        .collect();
+
+    // The best score of the first `end` syllables, and where its last word starts
+    let mut best: Vec<(f64, usize)> = vec![(0.0, 0)];
+    for end in 1..=syllables.len() {
+        let first_start = end.saturating_sub(dictionary.max_syllables.max(1));
+        let word = (first_start..end)
+            .filter_map(|start| {
+                let words = syllables.get(start..end)?;
+                let score = dictionary
+                    .log_probability(words)
+                    .or_else(|| (words.len() == 1).then(|| dictionary.unknown_log_probability()))?;
+                let (previous, _) = best.get(start)?;
+                Some((previous + score, start))
+            })
+            .max_by(|(a, _), (b, _)| a.total_cmp(b));
+        best.push(word.unwrap_or((f64::NEG_INFINITY, end - 1)));
+    }
+
+    let mut words = Vec::new();
+    let mut end = syllables.len();
+    while let Some(&(_, start)) = best.get(end).filter(|_| end > 0) {
+        words.push((start, end));
+        end = start;
+    }
+
+    for (start, end) in words.into_iter().rev() {
+        let (Some(first), Some(last)) = (run.get(start), run.get(end - 1)) else {
+            continue;
+        };
+        let kind = match syllables.get(start..end) {
+            Some(words) if dictionary.lookup(words).is_some() => SegmentKind::Word,
+            _ => SegmentKind::Unknown,
+        };
+        segments.push(Segment {
+            start: first.start,
+            end: last.end,
+            kind,
+        });
+    }
+}
+
+/// Splits a text into tokens, with the normalized syllable of the tokens that are Vietnamese syllables.
+fn tokens(text: &str) -> impl Iterator<Item = Token> + '_ {
+    let is_token_char = |ch: char| ch.is_alphanumeric() || is_combining_mark(ch);
+    let mut chars = text.char_indices().peekable();
+    std::iter::from_fn(move || {
+        let (start, _) = chars.find(|(_, ch)| is_token_char(*ch))?;
+        let mut end = text.len();
+        while let Some(&(index, ch)) = chars.peek() {
+            if !is_token_char(ch) {
+                end = index;
+                break;
+            }
+            chars.next();
+        }
+        let word = &text[start..end];
+        let syllable = is_valid_syllable(word).then(|| normalized(word));
+        Some(Token {
+            start,
+            end,
+            syllable,
+        })
+    })
+}
+
+/// Returns a syllable in lowercase, composed & in the new accent style.
+fn normalized(syllable: &str) -> String {
+    let syllable = compose(&syllable.to_lowercase()).into_owned();
+    convert_accent_style(&syllable, &AccentStyle::New)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words<'a>(text: &'a str, dictionary: &Dictionary) -> Vec<(&'a str, SegmentKind)> {
+        segment(text, dictionary)
+            .iter()
+            .map(|segment| (&text[segment.start..segment.end], segment.kind))
+            .collect()
+    }
+
+    fn dictionary() -> Dictionary {
+        "học sinh 50\nhọc 40\nsinh 10\nsinh viên 30\nviên 5\nđại học 20\nViệt Nam 30\nhòa bình 5\ncà phê 7"
+            .parse()
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn segment_accented_text() {
+        let dictionary = dictionary();
+        assert_eq!(
+            words("Sinh viên đại học Việt Nam", &dictionary),
+            [
+                ("Sinh viên", SegmentKind::Word),
+                ("đại học", SegmentKind::Word),
+                ("Việt Nam", SegmentKind::Word),
+            ]
+        );
+        assert_eq!(
+            words("học sinh học", &dictionary),
+            [("học sinh", SegmentKind::Word), ("học", SegmentKind::Word)]
+        );
+        // Old accent style & decomposed text
+        assert_eq!(
+            words("hoà bình, ho\u{300}a bi\u{300}nh", &dictionary),
+            [
+                ("hoà bình", SegmentKind::Word),
+                ("ho\u{300}a bi\u{300}nh", SegmentKind::Word)
+            ]
+        );
+    }
+
+    #[test]
+    fn segment_clean_text() {
+        let dictionary = dictionary();
+        let text = "Sinh vien dai hoc Viet Nam";
+        assert_eq!(
+            words(&clean_string(text), &dictionary),
+            words(text, &dictionary)
+        );
+        assert_eq!(
+            words("ca phe sua", &dictionary),
+            [("ca phe", SegmentKind::Word), ("sua", SegmentKind::Unknown)]
+        );
+    }
+
+    #[test]
+    fn segment_unknown_and_foreign_tokens() {
+        let dictionary = dictionary();
+        assert_eq!(
+            words("Tôi uống cà phê Starbucks 2 lần", &dictionary),
+            [
+                ("Tôi", SegmentKind::Unknown),
+                ("uống", SegmentKind::Unknown),
+                ("cà phê", SegmentKind::Word),
+                ("Starbucks", SegmentKind::Foreign),
+                ("2", SegmentKind::Foreign),
+                ("lần", SegmentKind::Unknown),
+            ]
+        );
+        // Punctuation & foreign tokens break words
+        assert_eq!(
+            words("học. sinh, học email sinh", &dictionary),
+            [
+                ("học", SegmentKind::Word),
+                ("sinh", SegmentKind::Word),
+                ("học", SegmentKind::Word),
+                ("email", SegmentKind::Foreign),
+                ("sinh", SegmentKind::Word),
+            ]
+        );
+        assert!(segment("", &dictionary).is_empty());
+        assert_eq!(
+            words("học sinh", &Dictionary::new()),
+            [
+                ("học", SegmentKind::Unknown),
+                ("sinh", SegmentKind::Unknown)
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_dictionary() {
+        let dictionary: Dictionary = "# words\n; more words\nhọc sinh 3\n\nhọc  sinh\n2\n"
+            .parse()
+            .unwrap_or_default();
+        assert_eq!(dictionary.len(), 2);
+        assert_eq!(dictionary.frequency("học sinh"), Some(4));
+        assert_eq!(dictionary.frequency("2"), Some(1));
+        assert!(matches!(
+            "học sinh 99999999999".parse::<Dictionary>(),
+            Err(DictionaryError::InvalidFrequency { line: 1 })
+        ));
+    }
+}
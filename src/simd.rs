@@ -0,0 +1,239 @@
+//! Cleaning text with portable SIMD.
+//!
+//! The kernels of [`asm`](crate::asm) are foreign assembly built by the build script, which `no_assembly` builds &
+//! audited deployments leave out. This module gets most of their speed in pure Rust with the `std::arch`
+//! intrinsics: cleaning never changes ASCII, so runs of ASCII bytes are skipped 16 or 32 bytes at a time, and only
+//! the Vietnamese letters between them are cleaned like [`clean_bytes_in_place`](crate::util::clean_bytes_in_place)
+//! does.
+//!
+//! The instructions are chosen at runtime from the [`CpuCapabilities`] of the CPU: AVX2 or SSE2 on `x86_64`, NEON
+//! on `aarch64`. Other CPUs use scalar code, with the same results.
+//!
+//! ## Example
+//!
+//! ```
+//! use vi::simd;
+//!
+//! assert_eq!(simd::clean_string("Tiếng Việt rất đẹp"), "Tieng Viet rat dep");
+//!
+//! let mut buffer = "Đà Lạt".as_bytes().to_vec();
+//! let len = simd::clean_bytes_in_place(&mut buffer)?;
+//! assert_eq!(&buffer[..len], b"Da Lat");
+//! # Ok::<(), std::str::Utf8Error>(())
+//! ```
+use crate::{
+    runtime_detection::{CpuArchitecture, CpuCapabilities},
+    util::clean_bytes_with,
+};
+
+/// The SIMD instructions used to clean text.
+///
+/// This enum may be extended with additional instruction sets in future versions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum SimdLevel {
+    /// No SIMD instructions, the text is cleaned with scalar code.
+    Scalar,
+    /// SSE2 on `x86_64`, 16 bytes at a time.
+    Sse2,
+    /// AVX2 on `x86_64`, 32 bytes at a time.
+    Avx2,
+    /// NEON on `aarch64`, 16 bytes at a time.
+    Neon,
+}
+
+impl SimdLevel {
+    /// The instructions used on the current CPU.
+    #[must_use]
+    pub fn get() -> Self {
+        Self::from_capabilities(CpuCapabilities::get())
+    }
+
+    /// The instructions that would be used on a CPU with some capabilities. Instructions of another architecture
+    /// than the one the crate is built for are never used.
+    #[must_use]
+    pub const fn from_capabilities(capabilities: &CpuCapabilities) -> Self {
+        match &capabilities.architecture {
+            #[cfg(target_arch = "x86_64")]
+            CpuArchitecture::X86_64 { has_avx2: true, .. } => Self::Avx2,
+            #[cfg(target_arch = "x86_64")]
+            CpuArchitecture::X86_64 { .. } => Self::Sse2,
+            #[cfg(target_arch = "aarch64")]
+            CpuArchitecture::AppleSilicon { .. }
+            | CpuArchitecture::GenericArm64 { has_neon: true, .. } => Self::Neon,
+            _ => Self::Scalar,
+        }
+    }
+}
+
+/// Returns the length in bytes of the run of ASCII at the start of some bytes.
+///
+/// # Examples
+///
+/// ```
+/// use vi::simd::ascii_len;
+///
+/// assert_eq!(ascii_len("Xin chào".as_bytes()), 6);
+/// assert_eq!(ascii_len(b"Hello"), 5);
+/// ```
+#[must_use]
+pub fn ascii_len(bytes: &[u8]) -> usize {
+    ascii_len_with(SimdLevel::get(), bytes)
+}
+
+/// Strip off tone marks & modifications from a string.
+///
+/// The result is the same as [`clean_string`](crate::util::clean_string)'s.
+#[must_use]
+pub fn clean_string(input: &str) -> String {
+    let mut bytes = input.as_bytes().to_vec();
+    let len = clean_bytes_in_place(&mut bytes).unwrap_or(bytes.len());
+    bytes.truncate(len);
+    String::from_utf8(bytes)
+        .unwrap_or_else(|error| String::from_utf8_lossy(error.as_bytes()).into_owned())
+}
+
+/// Strip off tone marks & modifications from the UTF-8 text of a buffer in place.
+///
+/// The cleaned text is written at the start of the buffer, and its length is returned, like
+/// [`clean_bytes_in_place`](crate::util::clean_bytes_in_place) does.
+///
+/// # Errors
+///
+/// Returns an error if the buffer isn't valid UTF-8, in which case it's left unchanged.
+pub fn clean_bytes_in_place(bytes: &mut [u8]) -> Result<usize, std::str::Utf8Error> {
+    let level = SimdLevel::get();
+    clean_bytes_with(bytes, |bytes| ascii_len_with(level, bytes))
+}
+
+/// Returns the length of the run of ASCII at the start of some bytes with the instructions of a level, which must
+/// be the level of the current CPU.
+fn ascii_len_with(level: SimdLevel, bytes: &[u8]) -> usize {
+    match level {
+        // SAFETY: the levels of the current CPU only use instructions it supports
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Avx2 => unsafe { ascii_len_avx2(bytes) },
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Sse2 => unsafe { ascii_len_sse2(bytes) },
+        #[cfg(target_arch = "aarch64")]
+        SimdLevel::Neon => unsafe { ascii_len_neon(bytes) },
+        _ => ascii_len_scalar(bytes),
+    }
+}
+
+fn ascii_len_scalar(bytes: &[u8]) -> usize {
+    bytes.iter().take_while(|byte| byte.is_ascii()).count()
+}
+
+/// # Safety
+///
+/// The CPU must support AVX2.
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+unsafe fn ascii_len_avx2(bytes: &[u8]) -> usize {
+    use std::arch::x86_64::{__m256i, _mm256_loadu_si256, _mm256_movemask_epi8};
+
+    let mut index = 0;
+    // The mask has the high bit of every byte, which is only set for the bytes that aren't ASCII
+    while let Some(chunk) = bytes.get(index..index + 32) {
+        #[allow(clippy::cast_ptr_alignment)] // The loads are unaligned
+        if _mm256_movemask_epi8(_mm256_loadu_si256(chunk.as_ptr().cast::<__m256i>())) != 0 {
+            break;
+        }
+        index += 32;
+    }
+    index + ascii_len_scalar(bytes.get(index..).unwrap_or_default())
+}
+
+/// # Safety
+///
+/// The CPU must support SSE2.
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "sse2")]
+unsafe fn ascii_len_sse2(bytes: &[u8]) -> usize {
+    use std::arch::x86_64::{__m128i, _mm_loadu_si128, _mm_movemask_epi8};
+
+    let mut index = 0;
+    while let Some(chunk) = bytes.get(index..index + 16) {
+        #[allow(clippy::cast_ptr_alignment)] // The loads are unaligned
+        if _mm_movemask_epi8(_mm_loadu_si128(chunk.as_ptr().cast::<__m128i>())) != 0 {
+            break;
+        }
+        index += 16;
+    }
+    index + ascii_len_scalar(bytes.get(index..).unwrap_or_default())
+}
+
+/// # Safety
+///
+/// The CPU must support NEON.
+#[cfg(target_arch = "aarch64")]
+#[target_feature(enable = "neon")]
+unsafe fn ascii_len_neon(bytes: &[u8]) -> usize {
+    use std::arch::aarch64::{vld1q_u8, vmaxvq_u8};
+
+    let mut index = 0;
+    // Only the bytes that aren't ASCII are 0x80 or more
+    while let Some(chunk) = bytes.get(index..index + 16) {
+        if vmaxvq_u8(vld1q_u8(chunk.as_ptr())) >= 0x80 {
+            break;
+        }
+        index += 16;
+    }
+    index + ascii_len_scalar(bytes.get(index..).unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The levels the current CPU supports.
+    fn supported_levels() -> Vec<SimdLevel> {
+        match SimdLevel::get() {
+            SimdLevel::Avx2 => vec![SimdLevel::Scalar, SimdLevel::Sse2, SimdLevel::Avx2],
+            level => vec![SimdLevel::Scalar, level],
+        }
+    }
+
+    #[test]
+    fn ascii_runs() {
+        let text = "a".repeat(100);
+        for level in supported_levels() {
+            for len in 0..=text.len() {
+                for suffix in ["", "é", "\u{301}"] {
+                    let bytes = format!("{}{suffix}b", text.get(..len).unwrap_or_default());
+                    let expected = if suffix.is_empty() { len + 1 } else { len };
+                    assert_eq!(
+                        ascii_len_with(level, bytes.as_bytes()),
+                        expected,
+                        "{level:?} {len} {suffix:?}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn same_as_clean_string() {
+        let long = "Tiếng Việt là ngôn ngữ chính thức của Việt Nam. ".repeat(10);
+        for text in [
+            "",
+            "ASCII only, longer than thirty-two bytes for sure",
+            "Đà Lạt",
+            "Vie\u{323}\u{302}t Nam",
+            "a\u{301}bcdefghijklmnopqrstuvwxyz0123456789\u{300}",
+            "\u{301}b\u{301}ư\u{300}\u{301} 日本",
+            &long,
+        ] {
+            assert_eq!(
+                clean_string(text),
+                crate::util::clean_string(text),
+                "{text}"
+            );
+        }
+
+        let mut invalid = *b"vi\xFFet";
+        assert!(clean_bytes_in_place(&mut invalid).is_err());
+        assert_eq!(&invalid, b"vi\xFFet");
+    }
+}
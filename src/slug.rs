@@ -0,0 +1,400 @@
+//! URL slugs & ASCII identifiers from Vietnamese text.
+//!
+//! A [`Slugifier`] turns a text into lowercase ASCII words joined by a separator. Vietnamese letters are cleaned
+//! with [`clean_char`], so `đ` becomes `d`, and everything that isn't a letter or a digit separates words, so
+//! punctuation & runs of whitespace give a single separator. Other Latin letters lose their diacritics too, so
+//! `ñ` becomes `n` & `ß` becomes `ss`, while the letters of other scripts are dropped.
+//!
+//! [`Slugifier::identifier`] generates valid Rust & C identifiers instead, and [`Slugifier::unique`] adds a
+//! numbered suffix to a slug that is already taken.
+//!
+//! ## Example
+//!
+//! ```
+//! use vi::slug::{slugify, Slugifier};
+//!
+//! assert_eq!(slugify("Đường sắt Bắc–Nam: 1.726 km!"), "duong-sat-bac-nam-1-726-km");
+//!
+//! let slugifier = Slugifier::new()
+//!     .with_max_length(20)
+//!     .with_stop_words(&["của", "và"]);
+//! assert_eq!(slugifier.slugify("Lịch sử của Hà Nội và Sài Gòn"), "lich-su-ha-noi-sai");
+//!
+//! assert_eq!(Slugifier::identifier().slugify("Số điện thoại"), "so_dien_thoai");
+//! ```
+use crate::{
+    case::to_lower_char,
+    normalize::{compose, is_combining_mark},
+    util::clean_char,
+};
+
+/// The keywords of Rust & C, which are not valid identifiers.
+const KEYWORDS: [&str; 76] = [
+    "abstract", "alignas", "alignof", "as", "async", "auto", "await", "become", "bool", "box",
+    "break", "case", "char", "const", "continue", "crate", "default", "do", "double", "dyn",
+    "else", "enum", "extern", "false", "final", "float", "fn", "for", "gen", "goto", "if", "impl",
+    "in", "inline", "int", "let", "long", "loop", "macro", "match", "mod", "move", "mut",
+    "nullptr", "override", "priv", "pub", "ref", "register", "restrict", "return", "self", "short",
+    "signed", "sizeof", "static", "struct", "super", "switch", "trait", "true", "try", "type",
+    "typedef", "typeof", "union", "unsafe", "unsigned", "unsized", "use", "virtual", "void",
+    "volatile", "where", "while", "yield",
+];
+
+/// What a [`Slugifier`] generates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Url,
+    Identifier,
+}
+
+/// Generates slugs from text, with a configurable separator, length & stop words.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Slugifier<'a> {
+    mode: Mode,
+    separator: &'a str,
+    max_length: Option<usize>,
+    stop_words: &'a [&'a str],
+}
+
+impl Default for Slugifier<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A word of the text, in lowercase & cleaned.
+struct Word {
+    lowercase: String,
+    clean: String,
+}
+
+impl<'a> Slugifier<'a> {
+    /// Creates a slugifier for URL slugs, with words separated by `-`.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            mode: Mode::Url,
+            separator: "-",
+            max_length: None,
+            stop_words: &[],
+        }
+    }
+
+    /// Creates a slugifier for identifiers that are valid in Rust & C, with words separated by `_`.
+    ///
+    /// An identifier that would start with a digit or be a keyword starts with `_` instead, and a text without
+    /// any word gives `__`. Separators with characters that can't be used in identifiers are replaced by `_`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vi::slug::Slugifier;
+    ///
+    /// let slugifier = Slugifier::identifier();
+    /// assert_eq!(slugifier.slugify("Tên người dùng"), "ten_nguoi_dung");
+    /// assert_eq!(slugifier.slugify("3 tháng"), "_3_thang");
+    /// assert_eq!(slugifier.slugify("Type"), "_type");
+    /// assert_eq!(slugifier.slugify("!!!"), "__");
+    /// ```
+    #[must_use]
+    pub const fn identifier() -> Self {
+        Self {
+            mode: Mode::Identifier,
+            separator: "_",
+            max_length: None,
+            stop_words: &[],
+        }
+    }
+
+    /// Sets the separator put between words.
+    #[must_use]
+    pub const fn with_separator(mut self, separator: &'a str) -> Self {
+        self.separator = separator;
+        self
+    }
+
+    /// Sets the maximum length of slugs, in bytes. Slugs are cut between words, unless the first word is already
+    /// too long.
+    #[must_use]
+    pub const fn with_max_length(mut self, max_length: usize) -> Self {
+        self.max_length = Some(max_length);
+        self
+    }
+
+    /// Sets words that are left out of slugs, unless the text only has stop words.
+    ///
+    /// A stop word with diacritics only matches the same word, e.g. `và` matches `Và` but not `vá`, while a stop
+    /// word without diacritics matches all the words that clean to it.
+    #[must_use]
+    pub const fn with_stop_words(mut self, stop_words: &'a [&'a str]) -> Self {
+        self.stop_words = stop_words;
+        self
+    }
+
+    /// Generates the slug of a text.
+    #[must_use]
+    pub fn slugify(&self, text: &str) -> String {
+        self.slugify_words(&words(text), self.max_length.unwrap_or(usize::MAX))
+    }
+
+    /// Generates the slug of the words of a text, cut between words to a maximum length.
+    fn slugify_words(self, words: &[Word], max_length: usize) -> String {
+        let kept: Vec<&Word> = words
+            .iter()
+            .filter(|word| !self.is_stop_word(word))
+            .collect();
+        let words = if kept.is_empty() {
+            words.iter().collect()
+        } else {
+            kept
+        };
+
+        let separator = self.separator();
+        let slug = join(&words, separator, max_length);
+        if self.mode == Mode::Url || !needs_prefix(&slug) {
+            return slug;
+        }
+        match join(&words, separator, max_length.saturating_sub(1)) {
+            slug if slug.is_empty() => "__".to_owned(),
+            slug => format!("_{slug}"),
+        }
+    }
+
+    /// Generates the slug of a text, followed by the separator & the first number from 2 that makes it unique if
+    /// the slug is already taken.
+    ///
+    /// The slug is shortened between words if needed so that the suffix fits in the maximum length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vi::slug::Slugifier;
+    ///
+    /// let taken = ["ha-noi", "ha-noi-2"];
+    /// let slugifier = Slugifier::new();
+    /// assert_eq!(slugifier.unique("Hà Nội", |slug| taken.contains(&slug)), "ha-noi-3");
+    /// assert_eq!(slugifier.unique("Huế", |slug| taken.contains(&slug)), "hue");
+    /// ```
+    #[must_use]
+    pub fn unique(&self, text: &str, mut is_taken: impl FnMut(&str) -> bool) -> String {
+        let words = words(text);
+        let max_length = self.max_length.unwrap_or(usize::MAX);
+        let slug = self.slugify_words(&words, max_length);
+        if !is_taken(&slug) {
+            return slug;
+        }
+
+        let separator = self.separator();
+        let mut number = 2;
+        loop {
+            let suffix = format!("{separator}{number}");
+            let base = self.slugify_words(&words, max_length.saturating_sub(suffix.len()));
+            let candidate = if base.is_empty() {
+                number.to_string()
+            } else {
+                format!("{base}{suffix}")
+            };
+            if !is_taken(&candidate) {
+                return candidate;
+            }
+            number += 1;
+        }
+    }
+
+    fn separator(self) -> &'a str {
+        let is_valid = |separator: &str| {
+            separator
+                .chars()
+                .all(|ch| ch.is_ascii_alphanumeric() || ch == '_')
+        };
+        match self.mode {
+            Mode::Identifier if !is_valid(self.separator) => "_",
+            _ => self.separator,
+        }
+    }
+
+    fn is_stop_word(self, word: &Word) -> bool {
+        self.stop_words.iter().any(|stop_word| {
+            let stop_word: String = compose(stop_word).chars().map(to_lower_char).collect();
+            stop_word == word.lowercase || stop_word == word.clean
+        })
+    }
+}
+
+/// Generates the URL slug of a text with the default options, see [`Slugifier::new`].
+///
+/// # Examples
+///
+/// ```
+/// use vi::slug::slugify;
+///
+/// assert_eq!(slugify("  Xin chào,   Việt Nam!  "), "xin-chao-viet-nam");
+/// ```
+#[must_use]
+pub fn slugify(text: &str) -> String {
+    Slugifier::new().slugify(text)
+}
+
+/// Splits a text into words of letters & digits. Apostrophes & combining marks don't split words.
+fn words(text: &str) -> Vec<Word> {
+    let mut words = Vec::new();
+    let mut word = Word {
+        lowercase: String::new(),
+        clean: String::new(),
+    };
+    for ch in compose(text).chars() {
+        if ch.is_alphanumeric() {
+            word.lowercase.push(to_lower_char(ch));
+            let clean = clean_char(ch).to_ascii_lowercase();
+            if clean.is_ascii_alphanumeric() {
+                word.clean.push(clean);
+            } else if let Some(latin) = clean_latin(clean) {
+                word.clean.push_str(latin);
+            }
+        } else if !matches!(ch, '\'' | '’') && !is_combining_mark(ch) {
+            if !word.clean.is_empty() {
+                words.push(word);
+            }
+            word = Word {
+                lowercase: String::new(),
+                clean: String::new(),
+            };
+        }
+    }
+    if !word.clean.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+/// Writes a Latin letter that isn't Vietnamese without its diacritics, in lowercase ASCII.
+fn clean_latin(ch: char) -> Option<&'static str> {
+    let clean = match ch.to_lowercase().next().unwrap_or(ch) {
+        'à'..='å' | 'ā' | 'ă' | 'ą' => "a",
+        'æ' => "ae",
+        'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => "c",
+        'ð' | 'ď' | 'đ' => "d",
+        'è'..='ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => "e",
+        'ĝ' | 'ğ' | 'ġ' | 'ģ' => "g",
+        'ĥ' | 'ħ' => "h",
+        'ì'..='ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => "i",
+        'ĳ' => "ij",
+        'ĵ' => "j",
+        'ķ' => "k",
+        'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => "l",
+        'ñ' | 'ń' | 'ņ' | 'ň' => "n",
+        'ò'..='ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => "o",
+        'œ' => "oe",
+        'ŕ' | 'ŗ' | 'ř' => "r",
+        'ś' | 'ŝ' | 'ş' | 'š' => "s",
+        'ß' => "ss",
+        'ţ' | 'ť' | 'ŧ' => "t",
+        'þ' => "th",
+        'ù'..='ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => "u",
+        'ŵ' => "w",
+        'ý' | 'ÿ' | 'ŷ' => "y",
+        'ź' | 'ż' | 'ž' => "z",
+        _ => return None,
+    };
+    Some(clean)
+}
+
+/// Joins the clean words that fit in a maximum length.
+fn join(words: &[&Word], separator: &str, max_length: usize) -> String {
+    let mut slug = String::new();
+    for word in words {
+        let length = if slug.is_empty() {
+            word.clean.len()
+        } else {
+            separator.len() + word.clean.len()
+        };
+        if slug.len() + length > max_length {
+            if slug.is_empty() {
+                slug.push_str(word.clean.get(..max_length).unwrap_or_default());
+            }
+            break;
+        }
+        if !slug.is_empty() {
+            slug.push_str(separator);
+        }
+        slug.push_str(&word.clean);
+    }
+    slug
+}
+
+/// Checks whether an identifier needs a `_` prefix to be valid.
+fn needs_prefix(identifier: &str) -> bool {
+    !identifier.starts_with(|ch: char| ch.is_ascii_alphabetic()) || KEYWORDS.contains(&identifier)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn url_slugs() {
+        assert_eq!(slugify("ĐẠI HỌC Quốc gia"), "dai-hoc-quoc-gia");
+        assert_eq!(slugify("Vie\u{323}\u{302}t Nam"), "viet-nam");
+        assert_eq!(slugify("C'est l’été --- à Huế"), "cest-lete-a-hue");
+        assert_eq!(slugify("Tokyo 東京 ñandú"), "tokyo-nandu");
+        assert_eq!(slugify("ñandú São Paulo"), "nandu-sao-paulo");
+        assert_eq!(slugify("Große Œuvre, Łódź"), "grosse-oeuvre-lodz");
+        assert_eq!(slugify("...\t\n"), "");
+        assert_eq!(
+            Slugifier::new().with_separator("_").slugify("Hà Nội"),
+            "ha_noi"
+        );
+        assert_eq!(
+            Slugifier::new().with_separator("").slugify("Hà Nội"),
+            "hanoi"
+        );
+    }
+
+    #[test]
+    fn max_length() {
+        let slugifier = Slugifier::new().with_max_length(10);
+        assert_eq!(slugifier.slugify("Thành phố Hồ Chí Minh"), "thanh-pho");
+        assert_eq!(slugifier.slugify("Nghiêngnghiêngnghiêng"), "nghiengngh");
+        assert_eq!(Slugifier::new().with_max_length(0).slugify("Huế"), "");
+    }
+
+    #[test]
+    fn stop_words() {
+        let slugifier = Slugifier::new().with_stop_words(&["và", "LA"]);
+        assert_eq!(slugifier.slugify("Cá và cơm, vá áo"), "ca-com-va-ao");
+        assert_eq!(slugifier.slugify("Là lá la"), "la-la-la");
+        assert_eq!(slugifier.slugify("và là"), "va-la");
+    }
+
+    #[test]
+    fn identifiers() {
+        let slugifier = Slugifier::identifier();
+        assert_eq!(slugifier.slugify("Đơn giá (VNĐ)"), "don_gia_vnd");
+        assert_eq!(slugifier.slugify("for"), "_for");
+        assert_eq!(slugifier.slugify("Số 1"), "so_1");
+        assert_eq!(slugifier.with_separator("-").slugify("Số 1"), "so_1");
+        assert_eq!(slugifier.with_max_length(5).slugify("2 ngày"), "_2");
+        assert_eq!(slugifier.slugify(""), "__");
+    }
+
+    #[test]
+    fn unique_slugs() {
+        let taken = ["bai-viet", "bai-viet-2", "bai", "ba-3"];
+        let is_taken = |slug: &str| taken.contains(&slug);
+        assert_eq!(Slugifier::new().unique("Bài viết", is_taken), "bai-viet-3");
+
+        // Words aren't cut to make room for the suffix
+        let slugifier = Slugifier::new().with_max_length(8);
+        assert_eq!(slugifier.unique("Bài viết", is_taken), "bai-2");
+        assert_eq!(
+            slugifier.unique("Bài viết", |slug| slug == "bai-viet" || slug == "bai-2"),
+            "bai-3"
+        );
+        let slugifier = Slugifier::new().with_max_length(5);
+        assert_eq!(slugifier.unique("Bài", is_taken), "bai-2");
+        assert_eq!(
+            Slugifier::identifier().unique("Bài viết", |slug| slug == "bai_viet"),
+            "bai_viet_2"
+        );
+    }
+}
@@ -0,0 +1,422 @@
+//! Spell checking of Vietnamese text.
+//!
+//! Vietnamese is written one syllable at a time, so most misspellings are syllables that cannot exist: a tone mark
+//! on the wrong vowel, a missing horn or circumflex, letters typed in the wrong order, or a spelling that breaks the
+//! rules checked by [`ValidationMode::Strict`]. [`check_spelling`] finds these syllables in a text and
+//! [`suggest_corrections`] ranks the syllables that were most likely meant.
+//!
+//! Since syllables are checked one by one, a valid syllable used in the wrong place, like `sinh` for `xinh`, is
+//! not reported.
+//!
+//! ## Example
+//!
+//! ```
+//! use vi::spelling::check_spelling;
+//!
+//! let misspellings = check_spelling("Tôi thích học tiéng Viet");
+//! let words: Vec<&str> = misspellings.iter().map(|misspelling| misspelling.word.as_str()).collect();
+//! assert_eq!(words, ["tiéng", "Viet"]);
+//!
+//! assert_eq!(misspellings[0].start, 18);
+//! assert_eq!(misspellings[0].suggestions[0].replacement, "tiếng");
+//! ```
+use phf::{phf_set, Set};
+
+use crate::{
+    abbreviation::match_case,
+    accent::is_tone_well_placed,
+    normalize::compose,
+    parsing::parse_syllable,
+    processor::{modify_letter, AccentStyle, LetterModification, ToneMark, Transformation},
+    syllable::Syllable,
+    util::{clean_char, word_ranges},
+    validation::{is_valid_syllable_with_mode, ValidationMode},
+};
+
+/// The vowels of Vietnamese syllables, with their letter modifications but without tone mark. The vowels of
+/// [`crate::validation`] also include vowels that are only complete once a modification is added, like `uo`.
+pub(crate) const VOWELS: Set<&'static str> = phf_set![
+    "a", "ă", "â", "e", "ê", "i", "o", "ô", "ơ", "u", "ư", "y", "ai", "ao", "au", "ay", "âu", "ây",
+    "eo", "êu", "ia", "iu", "iê", "oa", "oă", "oe", "oi", "ôi", "ơi", "oo", "ua", "uâ", "uê", "ui",
+    "uô", "uơ", "uy", "ưa", "ưi", "ưu", "ươ", "yê", "iêu", "yêu", "oai", "oay", "oao", "oeo",
+    "uây", "uôi", "uya", "uyê", "uyu", "ươi", "ươu"
+];
+
+/// Initial consonants that are often confused because they're pronounced the same in some dialects.
+const INITIAL_CONFUSIONS: [(&str, &str); 10] = [
+    ("s", "x"),
+    ("x", "s"),
+    ("ch", "tr"),
+    ("tr", "ch"),
+    ("d", "gi"),
+    ("d", "r"),
+    ("gi", "d"),
+    ("gi", "r"),
+    ("r", "d"),
+    ("r", "gi"),
+];
+
+/// Final consonants that are often confused because they're pronounced the same in some dialects.
+const FINAL_CONFUSIONS: [(&str, &str); 2] = [("n", "ng"), ("ng", "n")];
+
+/// The kind of mistake a [`Suggestion`] corrects. Kinds are listed from the most to the least likely mistake.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum CorrectionKind {
+    /// The tone mark is on the wrong vowel, e.g. `tóan` for `toán`.
+    TonePlacement,
+    /// The stop final consonant is missing the acute or underdot tone mark, the only ones it can take, e.g. `hỏp`
+    /// or `hop` for `họp`.
+    ToneMark,
+    /// A horn, circumflex or breve is missing, e.g. `viet` for `việt`.
+    MissingModification,
+    /// Two letters are swapped, e.g. `nhgĩa` for `nghĩa`.
+    SwappedLetters,
+    /// A consonant is confused with one pronounced the same in some dialects, like `s`/`x`, `ch`/`tr`,
+    /// `d`/`gi`/`r` or the finals `n`/`ng`.
+    DialectConfusion,
+}
+
+/// A correction of a misspelled syllable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suggestion {
+    /// The corrected syllable.
+    pub replacement: String,
+    /// The kind of mistake corrected.
+    pub kind: CorrectionKind,
+}
+
+/// A misspelled syllable of a text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Misspelling {
+    /// The byte index of the first character of the syllable in the text.
+    pub start: usize,
+    /// The byte index after the last character of the syllable in the text.
+    pub end: usize,
+    /// The misspelled syllable.
+    pub word: String,
+    /// The corrections of the syllable, most likely first. It's empty when no correction is found, e.g. for
+    /// foreign words.
+    pub suggestions: Vec<Suggestion>,
+}
+
+/// Checks whether a syllable is spelled correctly.
+///
+/// A syllable is correct when it's valid with [`ValidationMode::Strict`], its vowel is complete and its tone mark
+/// is placed following either accent style.
+///
+/// # Examples
+///
+/// ```
+/// use vi::spelling::is_correctly_spelled;
+///
+/// assert!(is_correctly_spelled("người"));
+/// assert!(is_correctly_spelled("hòa"));
+/// assert!(is_correctly_spelled("hoà"));
+/// assert!(!is_correctly_spelled("nguoi"));
+/// assert!(!is_correctly_spelled("hòan"));
+/// assert!(!is_correctly_spelled("hỏp"));
+/// ```
+#[must_use]
+pub fn is_correctly_spelled(word: &str) -> bool {
+    is_valid_syllable_with_mode(word, ValidationMode::Strict)
+        && has_complete_vowel(word)
+        && is_tone_well_placed(word)
+}
+
+/// Finds the misspelled syllables of a text, with their byte ranges & suggested corrections.
+///
+/// # Examples
+///
+/// ```
+/// use vi::spelling::check_spelling;
+///
+/// let misspellings = check_spelling("xin chào, nguoi");
+/// assert_eq!(misspellings.len(), 1);
+/// assert_eq!(misspellings[0].start..misspellings[0].end, 11..16);
+/// assert_eq!(misspellings[0].suggestions[0].replacement, "nguôi");
+/// assert_eq!(misspellings[0].suggestions[1].replacement, "ngươi");
+/// ```
+#[must_use]
+pub fn check_spelling(text: &str) -> Vec<Misspelling> {
+    word_ranges(text)
+        .filter_map(|(start, end)| {
+            let word = &text[start..end];
+            let composed = compose(word);
+            if is_correctly_spelled(&composed) {
+                return None;
+            }
+            Some(Misspelling {
+                start,
+                end,
+                word: word.to_owned(),
+                suggestions: suggest_corrections(&composed),
+            })
+        })
+        .collect()
+}
+
+/// Suggests corrections for a misspelled syllable, most likely first.
+///
+/// Every suggestion is a correctly spelled syllable one mistake away from the word. Suggestions are ranked by
+/// [`CorrectionKind`], then by how common the mistake is within the kind.
+///
+/// # Examples
+///
+/// ```
+/// use vi::spelling::{suggest_corrections, CorrectionKind};
+///
+/// let suggestions = suggest_corrections("tóan");
+/// assert_eq!(suggestions[0].replacement, "toán");
+/// assert_eq!(suggestions[0].kind, CorrectionKind::TonePlacement);
+///
+/// let suggestions = suggest_corrections("thôgn");
+/// assert_eq!(suggestions[0].replacement, "thông");
+/// assert_eq!(suggestions[0].kind, CorrectionKind::SwappedLetters);
+/// ```
+#[must_use]
+pub fn suggest_corrections(word: &str) -> Vec<Suggestion> {
+    let mut syllable = Syllable::default();
+    syllable.set(word.to_owned());
+
+    let mut candidates = Vec::new();
+    tone_placement_candidates(word, &syllable, &mut candidates);
+    modification_candidates(&syllable, &mut candidates);
+    swapped_letter_candidates(word, &mut candidates);
+    dialect_candidates(word, &syllable, &mut candidates);
+
+    candidates.push(Suggestion {
+        replacement: word.to_owned(),
+        kind: CorrectionKind::ToneMark,
+    });
+
+    candidates.sort_by_key(|suggestion| suggestion.kind);
+    let mut suggestions: Vec<Suggestion> = Vec::new();
+    for candidate in candidates.into_iter().flat_map(with_stop_final_tones) {
+        if candidate.replacement != word
+            && !suggestions
+                .iter()
+                .any(|suggestion| suggestion.replacement == candidate.replacement)
+            && is_correctly_spelled(&candidate.replacement)
+        {
+            suggestions.push(candidate);
+        }
+    }
+    suggestions
+}
+
+/// Returns the candidate, or if it ends with a stop final consonant without the acute or underdot tone mark, the
+/// candidate with each of these tone marks instead. Unaccented words are often missing both a modification & a
+/// tone mark, e.g. `viet` for `việt`.
+fn with_stop_final_tones(candidate: Suggestion) -> Vec<Suggestion> {
+    let mut syllable = Syllable::default();
+    syllable.set(candidate.replacement.clone());
+    let is_stop_final = matches!(
+        syllable.final_consonant.to_lowercase().as_str(),
+        "c" | "ch" | "p" | "t"
+    );
+    if !is_stop_final
+        || matches!(
+            syllable.tone_mark,
+            Some(ToneMark::Acute | ToneMark::Underdot)
+        )
+    {
+        return vec![candidate];
+    }
+    [ToneMark::Acute, ToneMark::Underdot]
+        .into_iter()
+        .map(|tone_mark| {
+            syllable.tone_mark = Some(tone_mark);
+            Suggestion {
+                replacement: syllable.to_string(),
+                kind: candidate.kind,
+            }
+        })
+        .collect()
+}
+
+/// Checks whether the vowel of a syllable, once its tone mark is removed, is a complete Vietnamese vowel.
+fn has_complete_vowel(word: &str) -> bool {
+    let mut syllable = Syllable::default();
+    syllable.set(word.to_owned());
+    syllable.tone_mark = None;
+    let toneless = syllable.to_string();
+    let Ok((_, components)) = parse_syllable(&toneless) else {
+        return false;
+    };
+    components.vowel.is_empty() || VOWELS.contains(components.vowel.to_lowercase().as_str())
+}
+
+fn tone_placement_candidates(word: &str, syllable: &Syllable, candidates: &mut Vec<Suggestion>) {
+    for accent_style in [AccentStyle::New, AccentStyle::Old] {
+        let mut syllable = syllable.clone();
+        syllable.accent_style = accent_style;
+        let replacement = syllable.to_string();
+        if replacement != word {
+            candidates.push(Suggestion {
+                replacement,
+                kind: CorrectionKind::TonePlacement,
+            });
+        }
+    }
+}
+
+fn modification_candidates(syllable: &Syllable, candidates: &mut Vec<Suggestion>) {
+    for modification in [
+        LetterModification::Circumflex,
+        LetterModification::Horn,
+        LetterModification::Breve,
+    ] {
+        let mut syllable = syllable.clone();
+        // A second horn can be added to `uo`, e.g. `ươ`
+        for _ in 0..2 {
+            if !matches!(
+                modify_letter(&mut syllable, &modification),
+                Transformation::LetterModificationAdded
+                    | Transformation::LetterModificationReplaced
+            ) {
+                break;
+            }
+            candidates.push(Suggestion {
+                replacement: syllable.to_string(),
+                kind: CorrectionKind::MissingModification,
+            });
+        }
+    }
+}
+
+fn swapped_letter_candidates(word: &str, candidates: &mut Vec<Suggestion>) {
+    let chars: Vec<char> = word.chars().collect();
+    for index in 1..chars.len() {
+        let mut swapped = chars.clone();
+        swapped.swap(index - 1, index);
+        if swapped == chars {
+            continue;
+        }
+        candidates.push(Suggestion {
+            replacement: rendered(swapped.into_iter().collect()),
+            kind: CorrectionKind::SwappedLetters,
+        });
+    }
+}
+
+fn dialect_candidates(word: &str, syllable: &Syllable, candidates: &mut Vec<Suggestion>) {
+    let initial_length = syllable.initial_consonant.chars().count();
+    let initial: String = word.chars().take(initial_length).collect();
+    let rest: String = word.chars().skip(initial_length).collect();
+    for (from, to) in INITIAL_CONFUSIONS {
+        if initial.to_lowercase() != from {
+            continue;
+        }
+        // `gi` is written `g` before `i`, e.g. `gì`
+        let to = if to == "gi" && rest.chars().next().map(clean_char) == Some('i') {
+            "g"
+        } else {
+            to
+        };
+        candidates.push(Suggestion {
+            replacement: rendered(format!("{}{rest}", match_case(&initial, to))),
+            kind: CorrectionKind::DialectConfusion,
+        });
+    }
+
+    let final_consonant = &syllable.final_consonant;
+    let Some(stem) = word.strip_suffix(final_consonant.as_str()) else {
+        return;
+    };
+    for (from, to) in FINAL_CONFUSIONS {
+        if final_consonant.to_lowercase() == from {
+            let to = if final_consonant.chars().all(char::is_uppercase) {
+                to.to_uppercase()
+            } else {
+                to.to_owned()
+            };
+            candidates.push(Suggestion {
+                replacement: rendered(format!("{stem}{to}")),
+                kind: CorrectionKind::DialectConfusion,
+            });
+        }
+    }
+}
+
+/// Parses a syllable and renders it again, placing its tone mark following the new accent style.
+fn rendered(word: String) -> String {
+    let mut syllable = Syllable::default();
+    syllable.set(word);
+    syllable.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn replacements(word: &str) -> Vec<String> {
+        suggest_corrections(word)
+            .into_iter()
+            .map(|suggestion| suggestion.replacement)
+            .collect()
+    }
+
+    #[test]
+    fn complete_vowels() {
+        for word in [
+            "việt",
+            "người",
+            "khuya",
+            "quốc",
+            "giường",
+            "gì",
+            "tuần",
+            "ngoẹo",
+        ] {
+            assert!(is_correctly_spelled(word), "{word}");
+        }
+        for word in ["viet", "nguoi", "tién", "cuă", "dừong"] {
+            assert!(!is_correctly_spelled(word), "{word}");
+        }
+    }
+
+    #[test]
+    fn ranked_suggestions() {
+        assert_eq!(replacements("viet"), ["viết", "việt"]);
+        assert_eq!(replacements("truong"), ["truông", "trương"]);
+        assert_eq!(replacements("dừong"), ["duồng", "dường"]);
+        assert_eq!(replacements("nhgĩa"), ["nghĩa"]);
+        assert_eq!(
+            replacements("hỏp"),
+            ["hóp", "họp", "hốp", "hộp", "hớp", "hợp"]
+        );
+        assert!(replacements("hello").is_empty());
+    }
+
+    #[test]
+    fn suggestions_keep_case() {
+        assert_eq!(replacements("Viet"), ["Viết", "Việt"]);
+        assert_eq!(replacements("TÓAN"), ["TOÁN", "TOẮN", "TOÁNG"]);
+    }
+
+    #[test]
+    fn decomposed_text() {
+        assert!(check_spelling("Tie\u{302}\u{301}ng Vie\u{323}\u{302}t").is_empty());
+
+        let misspellings: Vec<(usize, usize, String)> =
+            check_spelling("Vie\u{323}\u{302}t ho\u{300}an")
+                .into_iter()
+                .map(|misspelling| (misspelling.start, misspelling.end, misspelling.word))
+                .collect();
+        assert_eq!(misspellings, [(9, 15, "ho\u{300}an".to_owned())]);
+    }
+
+    #[test]
+    fn dialect_suggestions() {
+        let dialect_replacements = |word: &str| -> Vec<String> {
+            suggest_corrections(word)
+                .into_iter()
+                .filter(|suggestion| suggestion.kind == CorrectionKind::DialectConfusion)
+                .map(|suggestion| suggestion.replacement)
+                .collect()
+        };
+        assert_eq!(dialect_replacements("hòan"), ["hoàng"]);
+        assert_eq!(dialect_replacements("Sóan"), ["Xoán", "Soáng"]);
+    }
+}
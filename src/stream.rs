@@ -0,0 +1,462 @@
+//! Cleaning streams of text without loading them in memory.
+//!
+//! [`clean_string`](crate::util::clean_string) needs the whole text at once. The adapters of this module clean
+//! UTF-8 text chunk by chunk instead: [`CleanReader`] wraps a [`Read`] and yields the cleaned text, [`CleanWriter`]
+//! wraps a [`Write`] and writes the cleaned text to it.
+//!
+//! A chunk can end in the middle of a code point, or between a letter and its combining marks. The adapters keep
+//! the incomplete end of a chunk, up to the start of its last syllable, and clean it with the next chunk, so the
+//! output is the same as cleaning the whole text at once. Chunks can be cleaned with the assembly bulk kernels of
+//! [`asm::direct_asm`](crate::asm::direct_asm), see [`CleanReader::with_assembly`].
+//!
+//! ## Example
+//!
+//! ```
+//! use std::io::{Read, Write};
+//! use vi::stream::{CleanReader, CleanWriter};
+//!
+//! let mut reader = CleanReader::new("Tiếng Việt".as_bytes()).with_chunk_size(4);
+//! let mut cleaned = String::new();
+//! reader.read_to_string(&mut cleaned)?;
+//! assert_eq!(cleaned, "Tieng Viet");
+//!
+//! let mut writer = CleanWriter::new(Vec::new());
+//! writer.write_all("Hà ".as_bytes())?;
+//! writer.write_all("Nội".as_bytes())?;
+//! assert_eq!(writer.finish()?, b"Ha Noi");
+//! # Ok::<(), std::io::Error>(())
+//! ```
+use std::{
+    fmt,
+    io::{self, Read, Write},
+};
+
+use crate::{
+    asm::direct_asm::get_assembly_interface,
+    normalize::{compose, is_combining_mark},
+    safety::AssemblyControl,
+    util::clean_char,
+};
+
+/// The number of bytes read from the inner reader, or buffered before writing to the inner writer, by default.
+pub const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+
+/// How the chunks are cleaned.
+enum Kernel {
+    Rust,
+    Assembly(AssemblyControl),
+}
+
+impl Kernel {
+    fn assembly() -> Self {
+        Self::Assembly(AssemblyControl::new())
+    }
+
+    fn clean(&mut self, text: &str, cleaned: &mut Vec<u8>) -> io::Result<()> {
+        let text = compose(text);
+        match self {
+            Self::Rust => {
+                let mut buffer = [0; 4];
+                for ch in text.chars().map(clean_char) {
+                    cleaned.extend_from_slice(ch.encode_utf8(&mut buffer).as_bytes());
+                }
+            }
+            Self::Assembly(control) => {
+                // The kernel cleans the chunk in place, after the text already cleaned
+                let start = cleaned.len();
+                cleaned.extend_from_slice(text.as_bytes());
+                let written = get_assembly_interface()
+                    .process_utf8_bulk_safe(cleaned.get_mut(start..).unwrap_or_default(), control)
+                    .map_err(io::Error::other)?;
+                cleaned.truncate(start + written);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Kernel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Rust => f.write_str("Rust"),
+            Self::Assembly(_) => f.write_str("Assembly"),
+        }
+    }
+}
+
+/// The state shared by the adapters: the bytes not cleaned yet and the kernel cleaning them.
+#[derive(Debug)]
+struct Cleaner {
+    kernel: Kernel,
+    chunk_size: usize,
+    pending: Vec<u8>,
+}
+
+impl Cleaner {
+    const fn new() -> Self {
+        Self {
+            kernel: Kernel::Rust,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Cleans the pending bytes to `cleaned`, keeping the incomplete end of the text unless it's the last chunk.
+    fn clean(&mut self, cleaned: &mut Vec<u8>, last: bool) -> io::Result<()> {
+        let text = match std::str::from_utf8(&self.pending) {
+            Ok(text) => text,
+            Err(error) if error.error_len().is_none() && !last => {
+                // The chunk ends in the middle of a code point
+                std::str::from_utf8(self.pending.get(..error.valid_up_to()).unwrap_or_default())
+                    .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?
+            }
+            Err(error) => return Err(io::Error::new(io::ErrorKind::InvalidData, error)),
+        };
+        let end = if last { text.len() } else { split_point(text) };
+        if end == 0 {
+            return Ok(());
+        }
+
+        self.kernel
+            .clean(text.get(..end).unwrap_or_default(), cleaned)?;
+        self.pending.drain(..end);
+        Ok(())
+    }
+}
+
+/// Finds where the complete part of a chunk ends: before its last syllable, or before its last letter & its
+/// combining marks if the chunk is a single syllable.
+fn split_point(text: &str) -> usize {
+    let is_syllable_char = |ch: char| ch.is_alphanumeric() || is_combining_mark(ch);
+    let syllable_start = text
+        .char_indices()
+        .rev()
+        .take_while(|(_, ch)| is_syllable_char(*ch))
+        .last()
+        .map_or(text.len(), |(index, _)| index);
+    if syllable_start > 0 {
+        return syllable_start;
+    }
+
+    text.char_indices()
+        .rev()
+        .find(|(_, ch)| !is_combining_mark(*ch))
+        .map_or(0, |(index, _)| index)
+}
+
+/// A reader that cleans the UTF-8 text read from another reader, like
+/// [`clean_string`](crate::util::clean_string) does.
+///
+/// Reading fails with [`io::ErrorKind::InvalidData`] if the text isn't valid UTF-8.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::{BufRead, BufReader};
+/// use vi::stream::CleanReader;
+///
+/// let text = "Xin chào\nViệt Nam\n";
+/// let lines: Vec<String> = BufReader::new(CleanReader::new(text.as_bytes()))
+///     .lines()
+///     .collect::<Result<_, _>>()?;
+/// assert_eq!(lines, ["Xin chao", "Viet Nam"]);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct CleanReader<R> {
+    inner: R,
+    cleaner: Cleaner,
+    /// The buffer chunks of the inner reader are read into, kept between fills.
+    chunk: Vec<u8>,
+    /// The cleaned text not read yet, from `position`.
+    cleaned: Vec<u8>,
+    position: usize,
+    done: bool,
+}
+
+impl<R: Read> CleanReader<R> {
+    /// Creates a reader cleaning the text of another reader in chunks of [`DEFAULT_CHUNK_SIZE`] bytes.
+    #[must_use]
+    pub const fn new(inner: R) -> Self {
+        Self {
+            inner,
+            cleaner: Cleaner::new(),
+            chunk: Vec::new(),
+            cleaned: Vec::new(),
+            position: 0,
+            done: false,
+        }
+    }
+
+    /// Sets the number of bytes read from the inner reader at once. A chunk size of 0 is treated as 1.
+    #[must_use]
+    pub const fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.cleaner.chunk_size = chunk_size;
+        self
+    }
+
+    /// Sets whether the chunks are cleaned with the assembly bulk kernels of the current platform. The kernels fall
+    /// back to Rust when no assembly is available, see [`is_assembly_available`](crate::is_assembly_available).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Read;
+    /// use vi::stream::CleanReader;
+    ///
+    /// let mut cleaned = String::new();
+    /// CleanReader::new("Đà Nẵng".as_bytes())
+    ///     .with_assembly(true)
+    ///     .read_to_string(&mut cleaned)?;
+    /// assert_eq!(cleaned, "Da Nang");
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[must_use]
+    pub fn with_assembly(mut self, enabled: bool) -> Self {
+        self.cleaner.kernel = if enabled {
+            Kernel::assembly()
+        } else {
+            Kernel::Rust
+        };
+        self
+    }
+
+    /// Returns a reference to the inner reader.
+    pub const fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner reader. Reading from it directly skips its text.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Returns the inner reader, discarding the text read from it but not cleaned or read yet.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Reads & cleans chunks until some cleaned text is available or the inner reader is exhausted.
+    fn fill(&mut self) -> io::Result<()> {
+        self.cleaned.clear();
+        self.position = 0;
+        self.chunk.resize(self.cleaner.chunk_size.max(1), 0);
+        while self.cleaned.is_empty() && !self.done {
+            let read = match self.inner.read(&mut self.chunk) {
+                Ok(read) => read,
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+                Err(error) => return Err(error),
+            };
+            self.cleaner
+                .pending
+                .extend_from_slice(self.chunk.get(..read).unwrap_or_default());
+            self.done = read == 0;
+            self.cleaner.clean(&mut self.cleaned, self.done)?;
+        }
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for CleanReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.position >= self.cleaned.len() {
+            self.fill()?;
+        }
+        let available = self.cleaned.get(self.position..).unwrap_or_default();
+        let read = available.len().min(buf.len());
+        for (byte, cleaned) in buf.iter_mut().zip(available) {
+            *byte = *cleaned;
+        }
+        self.position += read;
+        Ok(read)
+    }
+}
+
+/// A writer that cleans UTF-8 text, like [`clean_string`](crate::util::clean_string) does, before writing it to
+/// another writer.
+///
+/// The text is buffered until a chunk is complete or the writer is flushed. The end of the text, which could be
+/// followed by combining marks, is only written by [`CleanWriter::finish`]: dropping the writer without finishing
+/// it discards the end of the text. Writing fails with [`io::ErrorKind::InvalidData`] if the text isn't valid UTF-8.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Write;
+/// use vi::stream::CleanWriter;
+///
+/// let mut writer = CleanWriter::new(Vec::new()).with_chunk_size(1);
+/// write!(writer, "Tie\u{302}\u{301}ng ")?;
+/// writer.write_all(&"Việt".as_bytes()[..4])?;
+/// writer.write_all(&"Việt".as_bytes()[4..])?;
+/// assert_eq!(writer.finish()?, b"Tieng Viet");
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct CleanWriter<W: Write> {
+    inner: W,
+    cleaner: Cleaner,
+    cleaned: Vec<u8>,
+}
+
+impl<W: Write> CleanWriter<W> {
+    /// Creates a writer cleaning text in chunks of [`DEFAULT_CHUNK_SIZE`] bytes before writing it to another
+    /// writer.
+    #[must_use]
+    pub const fn new(inner: W) -> Self {
+        Self {
+            inner,
+            cleaner: Cleaner::new(),
+            cleaned: Vec::new(),
+        }
+    }
+
+    /// Sets the number of bytes buffered before they are cleaned & written to the inner writer.
+    #[must_use]
+    pub const fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.cleaner.chunk_size = chunk_size;
+        self
+    }
+
+    /// Sets whether the chunks are cleaned with the assembly bulk kernels of the current platform. See
+    /// [`CleanReader::with_assembly`].
+    #[must_use]
+    pub fn with_assembly(mut self, enabled: bool) -> Self {
+        self.cleaner.kernel = if enabled {
+            Kernel::assembly()
+        } else {
+            Kernel::Rust
+        };
+        self
+    }
+
+    /// Returns a reference to the inner writer.
+    pub const fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner writer. Writing to it directly puts the text before the text
+    /// buffered by this writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Cleans & writes the end of the text, flushes the inner writer and returns it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the buffered text isn't valid UTF-8 or if writing to the inner writer fails.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.write_pending(true)?;
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+
+    /// Cleans the pending text & writes it to the inner writer.
+    fn write_pending(&mut self, last: bool) -> io::Result<()> {
+        self.cleaned.clear();
+        self.cleaner.clean(&mut self.cleaned, last)?;
+        self.inner.write_all(&self.cleaned)
+    }
+}
+
+impl<W: Write> Write for CleanWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.cleaner.pending.extend_from_slice(buf);
+        if self.cleaner.pending.len() >= self.cleaner.chunk_size {
+            self.write_pending(false)?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.write_pending(false)?;
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::clean_string;
+
+    const TEXT: &str = "Tiếng Việt là ngôn ngữ của người Việt, Vie\u{323}\u{302}t Nam!\nĐẮK LẮK 2024 ngu\u{31B}o\u{31B}\u{300}i";
+
+    fn read_cleaned(text: &[u8], chunk_size: usize, assembly: bool) -> io::Result<String> {
+        let mut cleaned = String::new();
+        CleanReader::new(text)
+            .with_chunk_size(chunk_size)
+            .with_assembly(assembly)
+            .read_to_string(&mut cleaned)?;
+        Ok(cleaned)
+    }
+
+    fn write_cleaned(text: &[u8], write_size: usize, chunk_size: usize) -> io::Result<String> {
+        let mut writer = CleanWriter::new(Vec::new()).with_chunk_size(chunk_size);
+        for chunk in text.chunks(write_size) {
+            writer.write_all(chunk)?;
+        }
+        Ok(String::from_utf8(writer.finish()?).unwrap())
+    }
+
+    #[test]
+    fn reader_chunk_sizes() {
+        let expected = clean_string(TEXT);
+        for chunk_size in [0, 1, 2, 3, 5, 7, 16, DEFAULT_CHUNK_SIZE] {
+            assert_eq!(
+                read_cleaned(TEXT.as_bytes(), chunk_size, false).unwrap(),
+                expected
+            );
+            assert_eq!(
+                read_cleaned(TEXT.as_bytes(), chunk_size, true).unwrap(),
+                expected
+            );
+        }
+        assert_eq!(read_cleaned(b"", 1, false).unwrap(), "");
+    }
+
+    #[test]
+    fn writer_chunk_sizes() {
+        let expected = clean_string(TEXT);
+        for write_size in [1, 2, 3, 7] {
+            for chunk_size in [1, 4, DEFAULT_CHUNK_SIZE] {
+                assert_eq!(
+                    write_cleaned(TEXT.as_bytes(), write_size, chunk_size).unwrap(),
+                    expected
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn writer_flush_keeps_end() {
+        let mut writer = CleanWriter::new(Vec::new());
+        writer.write_all("xin chào Vie".as_bytes()).unwrap();
+        writer.flush().unwrap();
+        assert_eq!(writer.get_ref(), b"xin chao ");
+        writer.write_all("\u{323}\u{302}t".as_bytes()).unwrap();
+        assert_eq!(writer.finish().unwrap(), b"xin chao Viet");
+    }
+
+    #[test]
+    fn invalid_utf8() {
+        let error = read_cleaned(b"vi\xFFet", 2, false).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+
+        // A code point cut at the end of the text is invalid too
+        let error = read_cleaned("Việt".as_bytes().get(..4).unwrap(), 1, false).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        let error = write_cleaned("Việt".as_bytes().get(..4).unwrap(), 1, 1).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn split_points() {
+        assert_eq!(split_point("xin chào"), 4);
+        assert_eq!(split_point("xin chào "), 10);
+        assert_eq!(split_point("chào"), 4);
+        assert_eq!(split_point("Vie\u{323}\u{302}"), 2);
+        assert_eq!(split_point("e\u{323}"), 0);
+        assert_eq!(split_point(""), 0);
+    }
+}
@@ -32,6 +32,7 @@ use std::fmt::Display;
 
 use crate::{
     editing::{add_modification_char, add_tone_char, get_tone_mark_placement, replace_nth_char},
+    normalize::{compose, is_combining_mark},
     parsing::{extract_letter_modifications, extract_tone, parse_syllable, SyllableComponents},
     processor::{modify_letter, AccentStyle, LetterModification, ToneMark},
     util::clean_char,
@@ -155,7 +156,14 @@ impl Syllable {
     }
 
     /// Push a character to the syllable. This will also trigger modification recalculation for the syllable.
+    ///
+    /// A precomposed Vietnamese letter such as `ư` or `ờ` keeps its modification and tone mark.
     pub fn push(&mut self, ch: char) {
+        if clean_char(ch) != ch {
+            self.set(format!("{self}{ch}"));
+            return;
+        }
+
         let clean_syllable = format!(
             "{}{}{}{}",
             self.initial_consonant, self.vowel, self.final_consonant, ch
@@ -205,6 +213,7 @@ impl Syllable {
 
     /// Set a new value for the current syllable. This will parse the value into consonants, vowel, tonemark & modifications.
     pub fn set(&mut self, raw: String) {
+        let raw = composed(raw);
         let (_, syllable) = parse_syllable(&raw).unwrap_or_else(|_| {
             (
                 "",
@@ -328,6 +337,7 @@ impl ComplexSyllable {
 
     /// Set a new value for the current syllable.
     pub fn set(&mut self, raw: String) {
+        let raw = composed(raw);
         let (_, syllable) = parse_syllable(&raw).unwrap_or_else(|_| {
             (
                 "",
@@ -411,6 +421,7 @@ impl SimpleSyllable {
 
     /// Set a new value for the current syllable.
     pub fn set(&mut self, raw: String) {
+        let raw = composed(raw);
         let (_, syllable) = parse_syllable(&raw).unwrap_or_else(|_| {
             (
                 "",
@@ -463,3 +474,12 @@ impl Display for SimpleSyllable {
         write!(f, "{result}")
     }
 }
+
+/// Compose the combining marks of a raw syllable, so decomposed input is parsed like precomposed input.
+fn composed(raw: String) -> String {
+    if raw.chars().any(is_combining_mark) {
+        compose(&raw).into_owned()
+    } else {
+        raw
+    }
+}
@@ -1,4 +1,7 @@
 //! Useful utilities functions that might be helpful for developing a Vietnamese IME.
+use std::borrow::Cow;
+
+use crate::normalize::is_combining_mark;
 
 /// Strip off tone mark & modifications from an input char.
 ///
@@ -67,6 +70,7 @@ pub const fn clean_char(ch: char) -> char {
 ///
 /// This function removes Vietnamese diacritics and tone marks from all characters
 /// in a string, returning a new string with base characters while preserving case.
+/// Decomposed letters, written with combining marks, are cleaned as well.
 ///
 /// # Examples
 ///
@@ -76,10 +80,159 @@ pub const fn clean_char(ch: char) -> char {
 /// assert_eq!(clean_string("Tiếng Việt"), "Tieng Viet");
 /// assert_eq!(clean_string("Xin chào"), "Xin chao");
 /// assert_eq!(clean_string("Hà Nội"), "Ha Noi");
+/// assert_eq!(clean_string("Ha\u{300} No\u{323}\u{302}i"), "Ha Noi");
 /// ```
 #[inline]
 pub fn clean_string(input: &str) -> String {
-    input.chars().map(clean_char).collect()
+    crate::normalize::compose(input)
+        .chars()
+        .map(clean_char)
+        .collect()
+}
+
+/// Strip off tone marks & modifications from an input string, borrowing it when there is nothing to strip.
+///
+/// This is the same as [`clean_string`], without allocating for text that is already clean, like ASCII text.
+///
+/// # Examples
+///
+/// ```
+/// use std::borrow::Cow;
+/// use vi::util::clean_str;
+///
+/// assert!(matches!(clean_str("Hello world"), Cow::Borrowed("Hello world")));
+/// assert_eq!(clean_str("Tiếng Việt"), "Tieng Viet");
+/// ```
+#[must_use]
+pub fn clean_str(input: &str) -> Cow<'_, str> {
+    if is_clean(input) {
+        Cow::Borrowed(input)
+    } else {
+        Cow::Owned(clean_string(input))
+    }
+}
+
+/// Strip off tone marks & modifications from a string in place, without reallocating it.
+///
+/// # Examples
+///
+/// ```
+/// use vi::util::make_clean;
+///
+/// let mut text = String::from("Xin chào Vie\u{323}\u{302}t Nam");
+/// make_clean(&mut text);
+/// assert_eq!(text, "Xin chao Viet Nam");
+/// ```
+pub fn make_clean(text: &mut String) {
+    if is_clean(text) {
+        return;
+    }
+
+    let mut bytes = std::mem::take(text).into_bytes();
+    let len = clean_bytes_in_place(&mut bytes).unwrap_or(bytes.len());
+    bytes.truncate(len);
+    *text = String::from_utf8(bytes)
+        .unwrap_or_else(|error| String::from_utf8_lossy(error.as_bytes()).into_owned());
+}
+
+/// Strip off tone marks & modifications from the UTF-8 text of a buffer in place.
+///
+/// The cleaned text is never longer than the original text. It's written at the start of the buffer, and its
+/// length is returned; the bytes after it are left unspecified.
+///
+/// # Errors
+///
+/// Returns an error if the buffer isn't valid UTF-8, in which case it's left unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use vi::util::clean_bytes_in_place;
+///
+/// let mut buffer = *b"H\xC3\xA0 N\xE1\xBB\x99i";
+/// let len = clean_bytes_in_place(&mut buffer)?;
+/// assert_eq!(&buffer[..len], b"Ha Noi");
+/// # Ok::<(), std::str::Utf8Error>(())
+/// ```
+pub fn clean_bytes_in_place(bytes: &mut [u8]) -> Result<usize, std::str::Utf8Error> {
+    clean_bytes_with(bytes, |bytes| {
+        bytes.iter().take_while(|byte| byte.is_ascii()).count()
+    })
+}
+
+/// Strip off tone marks & modifications from the UTF-8 text of a buffer in place, skipping the runs of ASCII
+/// bytes measured by `ascii_len`, which returns the length of the run at the start of the bytes it's given.
+pub(crate) fn clean_bytes_with(
+    bytes: &mut [u8],
+    ascii_len: impl Fn(&[u8]) -> usize,
+) -> Result<usize, std::str::Utf8Error> {
+    let len = std::str::from_utf8(bytes)?.len();
+    let mut read = 0;
+    let mut written = 0;
+    while read < len {
+        // ASCII is left as it is, except for a letter followed by combining marks
+        let mut run = ascii_len(bytes.get(read..).unwrap_or_default());
+        if read + run < len {
+            run = run.saturating_sub(1);
+        }
+        if run > 0 {
+            bytes.copy_within(read..read + run, written);
+            read += run;
+            written += run;
+        }
+
+        let Some((ch, width)) = char_at(bytes, read) else {
+            break;
+        };
+
+        // A letter is cleaned together with the combining marks that follow it
+        let mut end = read + width;
+        while let Some((_, width)) = char_at(bytes, end).filter(|(ch, _)| is_combining_mark(*ch)) {
+            end += width;
+        }
+
+        if end == read + width {
+            let cleaned = clean_char(ch);
+            let cleaned_len = cleaned.len_utf8();
+            if let Some(target) = bytes.get_mut(written..written + cleaned_len) {
+                cleaned.encode_utf8(target);
+            }
+            written += cleaned_len;
+        } else {
+            let cluster = std::str::from_utf8(bytes.get(read..end).unwrap_or_default())?;
+            let cleaned = clean_string(cluster);
+            if let Some(target) = bytes.get_mut(written..written + cleaned.len()) {
+                target.copy_from_slice(cleaned.as_bytes());
+            }
+            written += cleaned.len();
+        }
+        read = end;
+    }
+    debug_assert_eq!(read, len);
+    Ok(written)
+}
+
+/// Whether cleaning a string wouldn't change it.
+pub(crate) fn is_clean(input: &str) -> bool {
+    input.is_ascii()
+        || input
+            .chars()
+            .all(|ch| !is_combining_mark(ch) && clean_char(ch) == ch)
+}
+
+/// Decodes the character starting at a byte index of valid UTF-8 text, with its length in bytes.
+fn char_at(bytes: &[u8], index: usize) -> Option<(char, usize)> {
+    let width = match *bytes.get(index)? {
+        0..=0x7F => 1,
+        0xC0..=0xDF => 2,
+        0xE0..=0xEF => 3,
+        _ => 4,
+    };
+    let ch = std::str::from_utf8(bytes.get(index..index + width)?)
+        .ok()?
+        .chars()
+        .next()?;
+    Some((ch, width))
 }
 
 /// Check if a character is a vowel.
@@ -249,6 +402,24 @@ pub const fn is_vowel(c: char) -> bool {
     )
 }
 
+/// Returns the byte ranges of the runs of letters of a text. The combining marks after a letter are part of
+/// the run, so decomposed words aren't split.
+pub(crate) fn word_ranges(text: &str) -> impl Iterator<Item = (usize, usize)> + '_ {
+    let mut chars = text.char_indices().peekable();
+    std::iter::from_fn(move || {
+        let (start, _) = chars.find(|(_, ch)| ch.is_alphabetic())?;
+        let mut end = text.len();
+        while let Some(&(index, ch)) = chars.peek() {
+            if !ch.is_alphabetic() && !is_combining_mark(ch) {
+                end = index;
+                break;
+            }
+            chars.next();
+        }
+        Some((start, end))
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -537,4 +708,50 @@ mod tests {
         assert_eq!(CLEANED_E, 'E');
         assert_eq!(CLEANED_U, 'u');
     }
+
+    #[test]
+    fn test_word_ranges() {
+        let ranges: Vec<(usize, usize)> = word_ranges("  xin, chào").collect();
+        assert_eq!(ranges, vec![(2, 5), (7, 12)]);
+        assert_eq!(word_ranges("").count(), 0);
+
+        let ranges: Vec<(usize, usize)> = word_ranges("Vie\u{323}\u{302}t \u{301}a").collect();
+        assert_eq!(ranges, vec![(0, 8), (11, 12)]);
+    }
+
+    #[test]
+    fn test_clean_str_borrows_clean_text() {
+        assert!(matches!(clean_str(""), Cow::Borrowed("")));
+        assert!(matches!(clean_str("Hello, world!"), Cow::Borrowed(_)));
+        assert!(matches!(clean_str("naïve ß 日本"), Cow::Borrowed(_)));
+        assert!(matches!(clean_str("Việt"), Cow::Owned(_)));
+        assert_eq!(clean_str("Vie\u{323}\u{302}t"), "Viet");
+    }
+
+    #[test]
+    fn test_clean_in_place() {
+        for text in [
+            "",
+            "ASCII only",
+            "Tiếng Việt có dấu: Đắk Lắk, người",
+            "Vie\u{323}\u{302}t ngu\u{31B}o\u{31B}\u{300}i",
+            "\u{301}b\u{301}ư\u{300}\u{301} 日本",
+        ] {
+            let expected = clean_string(text);
+
+            let mut bytes = text.as_bytes().to_vec();
+            let len = clean_bytes_in_place(&mut bytes).unwrap();
+            assert_eq!(bytes.get(..len), Some(expected.as_bytes()), "{text}");
+
+            let mut string = text.to_string();
+            let capacity = string.capacity();
+            make_clean(&mut string);
+            assert_eq!(string, expected);
+            assert_eq!(string.capacity(), capacity);
+        }
+
+        let mut invalid = *b"vi\xFFet";
+        assert!(clean_bytes_in_place(&mut invalid).is_err());
+        assert_eq!(&invalid, b"vi\xFFet");
+    }
 }
@@ -7,10 +7,18 @@
 //! The starting consonant are called initial consonant, while the consonant at the end is called the final consonant.
 //! A cluster of consonant can contains 1 -> 3 characters.
 //! See: <https://en.wikibooks.org/wiki/Vietnamese/Consonants>
+//!
+//! [`is_valid_syllable`] only checks each part against the lists of valid consonants & vowels. With
+//! [`ValidationMode::Strict`], [`is_valid_syllable_with_mode`] also checks that the parts go together.
 
 use phf::{phf_set, Set};
 
-use crate::{parsing::parse_syllable, util::clean_char};
+use crate::{
+    normalize::compose,
+    parsing::{extract_tone, parse_syllable, SyllableComponents},
+    processor::ToneMark,
+    util::clean_char,
+};
 
 const SINGLE_INITIAL_CONSONANTS: Set<char> =
     phf_set!['b', 'c', 'd', 'đ', 'g', 'h', 'k', 'l', 'm', 'n', 'p', 'q', 'r', 's', 't', 'v', 'x',];
@@ -26,13 +34,71 @@ const VOWELS: Set<&'static str> = phf_set![
     "uye", "uoi", "ye", "yeu", "y", "eu", "ue", "uay"
 ];
 
+const STOP_FINAL_CONSONANTS: Set<&'static str> = phf_set!["c", "ch", "p", "t"];
+
+/// The vowels ending with a semivowel or a falling `a`, which never take a final consonant.
+const OPEN_VOWELS: Set<&'static str> = phf_set![
+    "ai", "ao", "au", "ay", "eo", "eu", "ia", "iu", "oi", "ui", "uu", "ieu", "yeu", "oai", "oao",
+    "oay", "oeo", "uoi", "uya", "uyu", "uay"
+];
+
+/// Determines which rules a syllable is validated against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ValidationMode {
+    /// The initial consonant, vowel & final consonant are each checked against the lists of valid ones (default).
+    #[default]
+    Basic,
+    /// Like [`ValidationMode::Basic`], and the parts of the syllable must also go together:
+    /// - the stop finals `c`, `ch`, `p` & `t` only take the acute or underdot tone mark, e.g. `các` but not `càc`;
+    /// - `k`, `gh` & `ngh` are only written before `e`, `ê` & `i` (and `y` for `k`), and `c`, `g` & `ng` never
+    ///   are, e.g. `ke` & `ghi` but not `ce` & `ghu`;
+    /// - the vowels ending with a semivowel, like `ao` & `ươi`, never take a final consonant, e.g. `táo` but not
+    ///   `táon`.
+    Strict,
+}
+
 /// Verify if a syllable is a valid vietnamese syllable.
 #[must_use]
 pub fn is_valid_syllable(input: &str) -> bool {
-    let Ok((_, components)) = parse_syllable(input) else {
+    is_valid_syllable_with_mode(input, ValidationMode::Basic)
+}
+
+/// Verify if a syllable is a valid vietnamese syllable, following the rules of a validation mode.
+///
+/// # Examples
+///
+/// ```
+/// use vi::validation::{is_valid_syllable_with_mode, ValidationMode};
+///
+/// assert!(is_valid_syllable_with_mode("các", ValidationMode::Strict));
+/// assert!(is_valid_syllable_with_mode("càc", ValidationMode::Basic));
+/// assert!(!is_valid_syllable_with_mode("càc", ValidationMode::Strict));
+/// assert!(!is_valid_syllable_with_mode("hỏp", ValidationMode::Strict));
+/// assert!(!is_valid_syllable_with_mode("ce", ValidationMode::Strict));
+/// ```
+#[must_use]
+pub fn is_valid_syllable_with_mode(input: &str, mode: ValidationMode) -> bool {
+    let input = compose(input);
+    let Ok((_, components)) = parse_syllable(&input) else {
         return false;
     };
 
+    if !has_valid_parts(&components) {
+        return false;
+    }
+
+    match mode {
+        ValidationMode::Basic => true,
+        ValidationMode::Strict => {
+            is_valid_tone_for_final(components.final_consonant, extract_tone(&input))
+                && is_valid_initial_for_vowel(components.initial_consonant, components.vowel)
+                && is_valid_final_for_vowel(components.vowel, components.final_consonant)
+        }
+    }
+}
+
+/// Checks the parts of a syllable against the lists of valid consonants & vowels.
+fn has_valid_parts(components: &SyllableComponents) -> bool {
     if !components.initial_consonant.is_empty()
         && !is_valid_initial_consonant(components.initial_consonant)
     {
@@ -61,6 +127,41 @@ pub fn is_valid_syllable(input: &str) -> bool {
     true
 }
 
+/// Checks that a stop final consonant only comes with the acute or underdot tone mark.
+fn is_valid_tone_for_final(final_consonant: &str, tone_mark: Option<ToneMark>) -> bool {
+    !STOP_FINAL_CONSONANTS.contains(final_consonant.to_lowercase().as_str())
+        || matches!(tone_mark, Some(ToneMark::Acute | ToneMark::Underdot))
+}
+
+/// Checks that a vowel ending with a semivowel isn't followed by a final consonant.
+fn is_valid_final_for_vowel(vowel: &str, final_consonant: &str) -> bool {
+    let cleaned_vowel: String = vowel
+        .chars()
+        .map(|c| clean_char(c).to_ascii_lowercase())
+        .collect();
+    final_consonant.is_empty() || !OPEN_VOWELS.contains(cleaned_vowel.as_str())
+}
+
+/// Checks the spelling rules of the initial consonants written differently before front vowels.
+fn is_valid_initial_for_vowel(initial_consonant: &str, vowel: &str) -> bool {
+    let Some(first_vowel) = vowel
+        .chars()
+        .next()
+        .map(|ch| clean_char(ch).to_ascii_lowercase())
+    else {
+        return true;
+    };
+    let before_e_or_i = matches!(first_vowel, 'e' | 'i');
+    match initial_consonant.to_lowercase().as_str() {
+        "k" => before_e_or_i || first_vowel == 'y',
+        "c" => !before_e_or_i && first_vowel != 'y',
+        "gh" | "ngh" => before_e_or_i,
+        "g" => first_vowel != 'e',
+        "ng" => !before_e_or_i,
+        _ => true,
+    }
+}
+
 /// Checks if the given string is a valid Vietnamese initial consonant.
 #[must_use]
 pub fn is_valid_initial_consonant(consonant: &str) -> bool {
@@ -89,3 +190,77 @@ pub fn is_valid_final_consonant(consonant: &str) -> bool {
     let consonant = consonant.to_lowercase();
     FINAL_CONSONANTS.contains(consonant.as_str())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strict_stop_finals() {
+        for valid in [
+            "các", "cạc", "hóp", "họp", "bách", "mặt", "Việt", "cam", "càng",
+        ] {
+            assert!(
+                is_valid_syllable_with_mode(valid, ValidationMode::Strict),
+                "{valid}"
+            );
+        }
+        for invalid in ["cac", "càc", "hỏp", "hõp", "bach", "mầt"] {
+            assert!(is_valid_syllable(invalid), "{invalid}");
+            assert!(
+                !is_valid_syllable_with_mode(invalid, ValidationMode::Strict),
+                "{invalid}"
+            );
+        }
+    }
+
+    #[test]
+    fn strict_open_vowels() {
+        for valid in [
+            "táo",
+            "tai",
+            "người",
+            "khuyu",
+            "gian",
+            "giường",
+            "quay",
+            "quang",
+            "tuân",
+            "xoong",
+            "hoàn",
+            "khuyên",
+            "huynh",
+        ] {
+            assert!(
+                is_valid_syllable_with_mode(valid, ValidationMode::Strict),
+                "{valid}"
+            );
+        }
+        for invalid in ["táon", "TAÓN", "taim", "đêun", "ngươin", "hoain"] {
+            assert!(
+                !is_valid_syllable_with_mode(invalid, ValidationMode::Strict),
+                "{invalid}"
+            );
+        }
+    }
+
+    #[test]
+    fn strict_initial_consonants() {
+        for valid in [
+            "ke", "kê", "ki", "kỳ", "ca", "co", "cu", "ghi", "ghế", "gì", "ga", "nghe", "nga", "Ké",
+        ] {
+            assert!(
+                is_valid_syllable_with_mode(valid, ValidationMode::Strict),
+                "{valid}"
+            );
+        }
+        for invalid in [
+            "ka", "ce", "ci", "cy", "ghu", "gho", "ge", "ngha", "nge", "ngi", "CE",
+        ] {
+            assert!(
+                !is_valid_syllable_with_mode(invalid, ValidationMode::Strict),
+                "{invalid}"
+            );
+        }
+    }
+}
@@ -4,6 +4,7 @@
 //! selects the best available optimization strategy while maintaining
 //! comprehensive safety guarantees and monitoring capabilities.
 
+use crate::normalize::compose;
 use crate::optimization_selector::{
     OptimizationSelector, OptimizationStrategy, VietnameseProcessor,
 };
@@ -261,9 +262,19 @@ impl VietnameseTextProcessor {
         }
     }
 
-    /// Create a Rust-only processor (optimized or standard)
+    /// Create a Rust-only processor (SIMD or optimized)
     fn create_rust_processor() -> Result<Box<dyn VietnameseProcessor>, AssemblyError> {
-        // Try optimized Rust first, fall back to standard Rust
+        // Try Rust SIMD first, fall back to optimized Rust
+        let simd_available = OptimizationSelector::get()
+            .profiles()
+            .iter()
+            .any(|p| p.strategy == OptimizationStrategy::RustSimd && p.available);
+        if simd_available {
+            return Ok(Box::new(
+                crate::optimization_selector::RustSimdProcessor::new()?,
+            ));
+        }
+
         let processor = crate::optimization_selector::RustOptimizedProcessor::new();
         Ok(Box::new(processor))
     }
@@ -321,6 +332,9 @@ impl VietnameseTextProcessor {
             OptimizationStrategy::X86_64Assembly => Ok(Box::new(
                 crate::optimization_selector::X86_64Processor::new()?,
             )),
+            OptimizationStrategy::RustSimd => Ok(Box::new(
+                crate::optimization_selector::RustSimdProcessor::new()?,
+            )),
             OptimizationStrategy::RustOptimized => Ok(Box::new(
                 crate::optimization_selector::RustOptimizedProcessor::new(),
             )),
@@ -360,6 +374,8 @@ impl VietnameseTextProcessor {
 
     /// Process a Vietnamese string, removing diacritics from all characters
     ///
+    /// Decomposed letters, written with combining marks, are composed before processing.
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -368,6 +384,9 @@ impl VietnameseTextProcessor {
     /// let mut processor = VietnameseTextProcessor::new()?;  // Fixed: added mut
     /// let result = processor.process_string("Tiếng Việt")?;
     /// assert_eq!(result, "Tieng Viet");
+    ///
+    /// let result = processor.process_string("Tie\u{302}\u{301}ng Vie\u{323}\u{302}t")?;
+    /// assert_eq!(result, "Tieng Viet");
     /// # Ok::<(), vi::AssemblyError>(())
     /// ```
     pub fn process_string(&mut self, input: &str) -> Result<String, AssemblyError> {
@@ -381,7 +400,8 @@ impl VietnameseTextProcessor {
             None
         };
 
-        let result = self.process_string_with_retry(input, self.config.max_retries);
+        let input = compose(input);
+        let result = self.process_string_with_retry(&input, self.config.max_retries);
 
         if let Some(start) = start_time {
             self.update_stats_string(start, input.chars().count(), result.is_ok());
@@ -0,0 +1,195 @@
+use vi::{
+    abbreviation::AbbreviationTable,
+    composer::{Composer, ComposerEvent, InputMode},
+    methods::BackspaceMode,
+    TELEX, VIQR, VNI,
+};
+
+#[test]
+fn test_composer_sentence_telex() {
+    let mut composer = Composer::new(&TELEX);
+
+    let _ = composer.push_str("Tooi yeeu Vieejt Nam!");
+
+    assert_eq!(composer.committed(), "Tôi yêu Việt Nam!");
+    assert_eq!(composer.preedit(), "");
+}
+
+#[test]
+fn test_composer_sentence_vni() {
+    let mut composer = Composer::new(&VNI);
+
+    let _ = composer.push_str("Nam 2024 co1 36 ti3nh");
+    let _ = composer.commit();
+
+    assert_eq!(composer.committed(), "Nam 2024 có 36 tỉnh");
+}
+
+#[test]
+fn test_composer_viqr_punctuation() {
+    let mut composer = Composer::new(&VIQR);
+
+    // `.` is a tone key after a vowel, but a plain punctuation mark otherwise
+    let _ = composer.push_str("Vie^.t Nam . Ha\\. ");
+
+    assert_eq!(composer.committed(), "Việt Nam . Ha. ");
+}
+
+#[test]
+fn test_composer_events() {
+    let mut composer = Composer::new(&TELEX);
+
+    assert_eq!(
+        composer.push('d'),
+        vec![ComposerEvent::PreeditChanged("d".to_owned())]
+    );
+    assert_eq!(
+        composer.push('d'),
+        vec![ComposerEvent::PreeditChanged("đ".to_owned())]
+    );
+    assert_eq!(
+        composer.push('.'),
+        vec![
+            ComposerEvent::Commit("đ.".to_owned()),
+            ComposerEvent::PreeditChanged(String::new()),
+        ]
+    );
+
+    // A boundary without preedit only commits itself
+    assert_eq!(
+        composer.push(' '),
+        vec![ComposerEvent::Commit(" ".to_owned())]
+    );
+
+    // Nothing to commit
+    assert_eq!(composer.commit(), vec![]);
+}
+
+#[test]
+fn test_composer_preedit_separate_from_committed() {
+    let mut composer = Composer::new(&TELEX);
+
+    let _ = composer.push_str("xin chaof");
+    assert_eq!(composer.committed(), "xin ");
+    assert_eq!(composer.preedit(), "chào");
+
+    assert_eq!(composer.take_committed(), "xin ");
+    assert_eq!(composer.committed(), "");
+    assert_eq!(composer.preedit(), "chào");
+
+    assert_eq!(
+        composer.reset(),
+        vec![ComposerEvent::PreeditChanged(String::new())]
+    );
+    assert_eq!(composer.preedit(), "");
+    assert_eq!(composer.committed(), "");
+}
+
+#[test]
+fn test_composer_mode_switch() {
+    let mut composer = Composer::new(&TELEX);
+    assert_eq!(composer.mode(), InputMode::Vietnamese);
+
+    let _ = composer.push_str("ddaay laf");
+    let events = composer.toggle_mode();
+    assert_eq!(
+        events,
+        vec![
+            ComposerEvent::Commit("là".to_owned()),
+            ComposerEvent::PreeditChanged(String::new()),
+        ]
+    );
+    assert_eq!(composer.mode(), InputMode::English);
+
+    // English mode commits every keystroke as is
+    assert_eq!(
+        composer.push(' '),
+        vec![ComposerEvent::Commit(" ".to_owned())]
+    );
+    let _ = composer.push_str("windows ");
+    assert_eq!(composer.preedit(), "");
+
+    let _ = composer.set_mode(InputMode::Vietnamese);
+    let _ = composer.push_str("nhes");
+    let _ = composer.commit();
+
+    assert_eq!(composer.committed(), "đây là windows nhé");
+}
+
+#[test]
+fn test_composer_english_restoration() {
+    let mut composer = Composer::new(&TELEX).with_english_restoration(true);
+
+    let _ = composer.push_str("mowr window vaf those file");
+    assert_eq!(composer.preedit(), "file");
+
+    let events = composer.push('.');
+    assert_eq!(
+        events,
+        vec![
+            ComposerEvent::Commit("file.".to_owned()),
+            ComposerEvent::PreeditChanged(String::new()),
+        ]
+    );
+    assert_eq!(composer.committed(), "mở window và those file.");
+}
+
+#[test]
+fn test_composer_abbreviations() {
+    let table: AbbreviationTable = "vn = Việt Nam\nko = không".parse().unwrap();
+    let mut composer = Composer::new(&TELEX).with_abbreviations(&table);
+
+    let _ = composer.push_str("VN ko phair laf vns");
+    let events = composer.push('!');
+    assert_eq!(
+        events,
+        vec![
+            ComposerEvent::Commit("vns!".to_owned()),
+            ComposerEvent::PreeditChanged(String::new()),
+        ]
+    );
+    assert_eq!(composer.committed(), "VIỆT NAM không phải là vns!");
+}
+
+#[test]
+fn test_composer_abbreviation_undo() {
+    let table: AbbreviationTable = "ko = không".parse().unwrap();
+    let mut composer = Composer::new(&TELEX).with_abbreviations(&table);
+
+    let _ = composer.push_str("ko ");
+    assert_eq!(composer.committed(), "không ");
+
+    // The undo key right after the expansion brings the abbreviation back
+    let events = composer.backspace(BackspaceMode::Keystroke);
+    assert_eq!(
+        events,
+        vec![
+            ComposerEvent::Retract("không ".to_owned()),
+            ComposerEvent::Commit("ko ".to_owned()),
+        ]
+    );
+    assert_eq!(composer.committed(), "ko ");
+    assert_eq!(composer.preedit(), "");
+
+    // Only the last expansion can be undone, and only until the next key
+    assert!(composer.backspace(BackspaceMode::Keystroke).is_empty());
+    let _ = composer.push_str("ko.");
+    let _ = composer.push('a');
+    let _ = composer.backspace(BackspaceMode::Character);
+    assert!(composer.backspace(BackspaceMode::Character).is_empty());
+    assert_eq!(composer.committed(), "ko không.");
+
+    let _ = composer.push_str("ko");
+    let _ = composer.commit();
+    let _ = composer.backspace(BackspaceMode::Character);
+    assert_eq!(composer.committed(), "ko không.ko");
+}
+
+#[test]
+fn test_composer_trailing_escape() {
+    let mut composer = Composer::new(&VIQR);
+    let _ = composer.push_str("a\\ a\\. a\\");
+    assert_eq!(composer.preedit(), "a");
+    let _ = composer.commit();
+    assert_eq!(composer.committed(), "a\\ a. a\\");
+}
@@ -0,0 +1,101 @@
+use vi::{
+    encoding::{detect_encoding, EncodingError, LegacyEncoding},
+    maps::VOWELS,
+};
+
+const TEXT: &str =
+    "Tiếng Việt là ngôn ngữ chính thức của nước Cộng hòa Xã hội chủ nghĩa Việt Nam. \
+    Người Việt đã viết chữ Quốc ngữ từ thế kỷ mười bảy, và hôm nay ĐẠI HỌC cũng dạy tiếng Việt.";
+
+fn vietnamese_letters() -> Vec<char> {
+    let lowercase: Vec<char> = VOWELS
+        .iter()
+        .copied()
+        .chain(['đ'])
+        .filter(|ch| !ch.is_ascii())
+        .collect();
+    let uppercase: Vec<char> = lowercase.iter().flat_map(|ch| ch.to_uppercase()).collect();
+    lowercase.into_iter().chain(uppercase).collect()
+}
+
+#[test]
+fn round_trip_every_letter() {
+    let letters = vietnamese_letters();
+    assert_eq!(letters.len(), 134);
+
+    for encoding in LegacyEncoding::ALL {
+        let mut encoded_letters = Vec::new();
+        for letter in &letters {
+            let text = letter.to_string();
+            let encoded = encoding.encode(&text).unwrap();
+            assert_eq!(encoding.decode(&encoded), text, "{encoding}: {letter}");
+            encoded_letters.push(encoded);
+        }
+
+        encoded_letters.sort();
+        encoded_letters.dedup();
+        assert_eq!(encoded_letters.len(), 134, "{encoding}");
+    }
+}
+
+#[test]
+fn round_trip_text() {
+    for encoding in LegacyEncoding::ALL {
+        let encoded = encoding.encode(TEXT).unwrap();
+        assert_eq!(encoding.decode(&encoded), TEXT, "{encoding}");
+    }
+}
+
+#[test]
+fn round_trip_vps() {
+    let encoded = LegacyEncoding::Vps.encode("Đà Lạt, ĐẶNG Thị Ẩn").unwrap();
+    assert_eq!(encoded, b"\xaa\xe0 L\xadt, \xaa\x14NG Th\xcb \x16n");
+    assert_eq!(LegacyEncoding::Vps.decode(&encoded), "Đà Lạt, ĐẶNG Thị Ẩn");
+
+    // The letters of Latin-1 keep their code, and its other characters are replaced
+    assert_eq!(
+        LegacyEncoding::Vps.encode("Tôi © ý").unwrap(),
+        b"T\xf4i \xa9 \xfd"
+    );
+    assert!(LegacyEncoding::Vps.encode("ñ").is_err());
+}
+
+#[test]
+fn encode_decomposed_text() {
+    let encoded = LegacyEncoding::Viscii
+        .encode("Vie\u{323}\u{302}t Nam")
+        .unwrap();
+    assert_eq!(encoded, b"Vi\xaet Nam");
+}
+
+#[test]
+fn encode_unmappable_character() {
+    let result = LegacyEncoding::Tcvn3.encode("Việt ☕");
+    assert_eq!(
+        result,
+        Err(EncodingError::Unmappable {
+            character: '☕',
+            index: 7,
+            encoding: LegacyEncoding::Tcvn3,
+        })
+    );
+
+    // VISCII replaces some control characters with letters.
+    assert!(LegacyEncoding::Viscii.encode("\u{2}").is_err());
+}
+
+#[test]
+fn detect_every_encoding() {
+    for encoding in LegacyEncoding::ALL {
+        let encoded = encoding.encode(TEXT).unwrap();
+        let guess = detect_encoding(&encoded).unwrap();
+        assert_eq!(guess.encoding, encoding);
+        assert!(guess.confidence() > 0.99, "{guess:?}");
+    }
+}
+
+#[test]
+fn detect_plain_ascii() {
+    assert_eq!(detect_encoding(b"Tieng Viet"), None);
+    assert_eq!(detect_encoding(b""), None);
+}
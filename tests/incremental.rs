@@ -1,9 +1,13 @@
 use vi::{
+    abbreviation::AbbreviationTable,
     methods::{
-        transform_buffer_incremental, transform_buffer_incremental_with_style, IncrementalBuffer,
+        transform_buffer_incremental, transform_buffer_incremental_with_style, BackspaceMode,
+        IncrementalBuffer, ToneEntryPolicy,
     },
     processor::AccentStyle,
-    transform_buffer, TELEX, VNI,
+    transform_buffer,
+    validation::ValidationMode,
+    TELEX, TELEX_EXTENDED, VIQR, VNI,
 };
 
 #[test]
@@ -146,6 +150,38 @@ fn test_incremental_buffer_w_insertion() {
     assert_eq!(buffer.view(), "w");
 }
 
+#[test]
+fn test_incremental_buffer_bracket_insertion() {
+    let mut buffer = transform_buffer_incremental(&TELEX_EXTENDED);
+
+    let _ = buffer.push('T');
+    let _ = buffer.push('}');
+    assert_eq!(buffer.view(), "TƯ");
+
+    let _ = buffer.push('{');
+    assert_eq!(buffer.view(), "TƯƠ");
+
+    // Typing the same bracket again gives back the bracket itself
+    let result = buffer.push('{');
+    assert_eq!(buffer.view(), "TƯ{");
+    assert!(result.letter_modification_removed);
+
+    buffer.clear();
+
+    // `w` is never inserted as ư by itself
+    let _ = buffer.push('w');
+    assert_eq!(buffer.view(), "w");
+
+    buffer.clear();
+
+    // Falls back to the raw bracket when the result is not a valid syllable
+    let _ = buffer.push('x');
+    let _ = buffer.push('y');
+    let _ = buffer.push('z');
+    let _ = buffer.push(']');
+    assert_eq!(buffer.view(), "xyz]");
+}
+
 #[test]
 fn test_incremental_buffer_accent_style() {
     let mut buffer_new = transform_buffer_incremental_with_style(&TELEX, AccentStyle::New);
@@ -239,3 +275,426 @@ fn test_incremental_buffer_constructor_variants() {
     assert_eq!(buffer3.view(), "");
     assert_eq!(buffer4.view(), "");
 }
+
+#[test]
+fn test_incremental_buffer_pop() {
+    let mut buffer = transform_buffer_incremental(&TELEX);
+
+    for ch in "dduwowcj".chars() {
+        let _ = buffer.push(ch);
+    }
+    assert_eq!(buffer.view(), "được");
+
+    assert_eq!(buffer.pop(), Some('j'));
+    assert_eq!(buffer.view(), "đươc");
+    assert_eq!(buffer.len(), 7);
+
+    assert_eq!(buffer.pop(), Some('c'));
+    assert_eq!(buffer.view(), "đươ");
+
+    // Popping keeps the state of the last executed action, so `ww` is still reset to `w`
+    buffer.clear();
+    let _ = buffer.push('w');
+    let _ = buffer.push('a');
+    assert_eq!(buffer.pop(), Some('a'));
+    assert_eq!(buffer.view(), "ư");
+    let _ = buffer.push('w');
+    assert_eq!(buffer.view(), "w");
+
+    buffer.clear();
+    assert_eq!(buffer.pop(), None);
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_incremental_buffer_pop_result() {
+    let mut buffer = transform_buffer_incremental(&TELEX);
+
+    for ch in "cass".chars() {
+        let _ = buffer.push(ch);
+    }
+    assert_eq!(buffer.view(), "cas");
+    assert!(buffer.result().tone_mark_removed);
+
+    assert_eq!(buffer.pop(), Some('s'));
+    assert_eq!(buffer.view(), "cá");
+    assert!(!buffer.result().tone_mark_removed);
+
+    // Consistent with a buffer that never received the popped keystroke
+    let mut expected = String::new();
+    transform_buffer(&TELEX, "cas".chars(), &mut expected);
+    assert_eq!(buffer.view(), expected);
+}
+
+#[test]
+fn test_incremental_buffer_backspace_character() {
+    let mut buffer = transform_buffer_incremental(&VNI);
+
+    for ch in "nguoi72".chars() {
+        let _ = buffer.push(ch);
+    }
+    assert_eq!(buffer.view(), "người");
+
+    assert_eq!(buffer.backspace(BackspaceMode::Character), Some('i'));
+    assert_eq!(buffer.view(), "ngườ");
+    // "ngườ" can't be typed without the "i", so the input is replaced with the visible characters
+    assert_eq!(buffer.input(), &['n', 'g', 'ư', 'ờ']);
+    assert_eq!(buffer.len(), 4);
+
+    // The tone mark on the removed character is not moved to the previous one
+    assert_eq!(buffer.backspace(BackspaceMode::Character), Some('ờ'));
+    assert_eq!(buffer.view(), "ngư");
+
+    // Typing continues from the remaining characters
+    let _ = buffer.push('a');
+    let _ = buffer.push('1');
+    assert_eq!(buffer.view(), "ngứa");
+
+    assert_eq!(buffer.backspace(BackspaceMode::Keystroke), Some('1'));
+    assert_eq!(buffer.view(), "ngưa");
+}
+
+#[test]
+fn test_incremental_buffer_backspace_character_keeps_tone() {
+    let mut buffer = transform_buffer_incremental(&TELEX);
+
+    for ch in "hoangf".chars() {
+        let _ = buffer.push(ch);
+    }
+    assert_eq!(buffer.view(), "hoàng");
+
+    assert_eq!(buffer.backspace(BackspaceMode::Character), Some('g'));
+    assert_eq!(buffer.backspace(BackspaceMode::Character), Some('n'));
+    assert_eq!(buffer.view(), "hoà");
+
+    while buffer.backspace(BackspaceMode::Character).is_some() {}
+    assert_eq!(buffer.view(), "");
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_incremental_buffer_backspace_character_keeps_input() {
+    for (keys, expected_input, expected) in [
+        ("tieens", "tiees", "tiế"),
+        ("vieetj", "vieej", "việ"),
+        ("toans", "toas", "toá"),
+        ("dduwowngf", "dduwownf", "đườn"),
+    ] {
+        let mut buffer = transform_buffer_incremental(&TELEX);
+        for ch in keys.chars() {
+            let _ = buffer.push(ch);
+        }
+        let _ = buffer.backspace(BackspaceMode::Character);
+        assert_eq!(buffer.view(), expected, "{keys}");
+        assert_eq!(buffer.input().iter().collect::<String>(), expected_input);
+
+        // The input and the result are the same as if the remaining keys were typed
+        let mut output = String::new();
+        let result = transform_buffer(&TELEX, expected_input.chars(), &mut output);
+        assert_eq!(output, expected);
+        assert_eq!(
+            buffer.result().tone_mark_removed,
+            result.tone_mark_removed,
+            "{keys}"
+        );
+    }
+}
+
+fn type_with_policy(input: &str, policy: ToneEntryPolicy) -> String {
+    let mut buffer = transform_buffer_incremental(&TELEX).with_tone_entry_policy(policy);
+    for ch in input.chars() {
+        let _ = buffer.push(ch);
+    }
+    buffer.view().to_owned()
+}
+
+#[test]
+fn test_incremental_buffer_free_tone_entry() {
+    assert_eq!(type_with_policy("toans", ToneEntryPolicy::Free), "toán");
+    assert_eq!(type_with_policy("tosan", ToneEntryPolicy::Free), "toán");
+    assert_eq!(type_with_policy("tsoan", ToneEntryPolicy::Free), "tsoan");
+    assert_eq!(type_with_policy("gisa", ToneEntryPolicy::Free), "giá");
+    assert_eq!(type_with_policy("fix", ToneEntryPolicy::Free), "fix");
+    assert_eq!(type_with_policy("taxi", ToneEntryPolicy::Free), "tãi");
+}
+
+#[test]
+fn test_incremental_buffer_strict_tone_entry() {
+    // Tone keys after the vowels are applied as usual
+    assert_eq!(type_with_policy("toans", ToneEntryPolicy::Strict), "toán");
+    assert_eq!(type_with_policy("hoafn", ToneEntryPolicy::Strict), "hoàn");
+    assert_eq!(type_with_policy("gif", ToneEntryPolicy::Strict), "gì");
+    assert_eq!(
+        type_with_policy("thuowngf", ToneEntryPolicy::Strict),
+        "thường"
+    );
+    assert_eq!(type_with_policy("toasf", ToneEntryPolicy::Strict), "toà");
+    assert_eq!(type_with_policy("toass", ToneEntryPolicy::Strict), "toas");
+
+    // Tone keys before a vowel stay literal
+    assert_eq!(type_with_policy("tsoan", ToneEntryPolicy::Strict), "tsoan");
+    assert_eq!(
+        type_with_policy("tsoans", ToneEntryPolicy::Strict),
+        "tsoans"
+    );
+    assert_eq!(type_with_policy("tosan", ToneEntryPolicy::Strict), "tosan");
+    assert_eq!(type_with_policy("gisa", ToneEntryPolicy::Strict), "gisa");
+
+    // Every tone key of the syllable goes back to its place
+    assert_eq!(
+        type_with_policy("toasfan", ToneEntryPolicy::Strict),
+        "toasfan"
+    );
+    assert_eq!(type_with_policy("thif", ToneEntryPolicy::Strict), "thì");
+}
+
+#[test]
+fn test_incremental_buffer_strict_tone_entry_english_words() {
+    for word in [
+        "fix", "taxi", "Taxi", "this", "This", "those", "basic", "first", "system",
+    ] {
+        assert_eq!(type_with_policy(word, ToneEntryPolicy::Strict), word);
+    }
+}
+
+#[test]
+fn test_incremental_buffer_strict_tone_entry_pop() {
+    let mut buffer =
+        transform_buffer_incremental(&TELEX).with_tone_entry_policy(ToneEntryPolicy::Strict);
+    for ch in "tosa".chars() {
+        let _ = buffer.push(ch);
+    }
+    assert_eq!(buffer.view(), "tosa");
+    assert_eq!(buffer.tone_entry_policy(), ToneEntryPolicy::Strict);
+
+    // Removing the vowel typed after the tone key brings the tone mark back
+    assert_eq!(buffer.pop(), Some('a'));
+    assert_eq!(buffer.view(), "tó");
+}
+
+fn finish_with_restoration(input: &str) -> (String, bool) {
+    let mut buffer = transform_buffer_incremental(&TELEX).with_english_restoration(true);
+    for ch in input.chars() {
+        let _ = buffer.push(ch);
+    }
+    let result = buffer.finish();
+    (buffer.view().to_owned(), result.english_restored)
+}
+
+#[test]
+fn test_incremental_buffer_english_restoration() {
+    // Not a valid syllable
+    assert_eq!(
+        finish_with_restoration("window"),
+        ("window".to_owned(), true)
+    );
+    assert_eq!(
+        finish_with_restoration("Window"),
+        ("Window".to_owned(), true)
+    );
+    // Valid syllables spelled by English words
+    assert_eq!(finish_with_restoration("those"), ("those".to_owned(), true));
+    assert_eq!(finish_with_restoration("THIS"), ("THIS".to_owned(), true));
+    // Untouched words are not reported
+    assert_eq!(finish_with_restoration("fix"), ("fix".to_owned(), false));
+    // Vietnamese words are kept
+    assert_eq!(
+        finish_with_restoration("vieetj"),
+        ("việt".to_owned(), false)
+    );
+    assert_eq!(finish_with_restoration("its"), ("ít".to_owned(), false));
+    assert_eq!(
+        finish_with_restoration("nguwowif"),
+        ("người".to_owned(), false)
+    );
+}
+
+#[test]
+fn test_incremental_buffer_english_restoration_disabled() {
+    let mut buffer = transform_buffer_incremental(&TELEX);
+    for ch in "those".chars() {
+        let _ = buffer.push(ch);
+    }
+    assert!(!buffer.finish().english_restored);
+    assert_eq!(buffer.view(), "thoé");
+}
+
+fn finish_with_abbreviations(table: &AbbreviationTable, input: &str) -> (String, bool) {
+    let mut buffer = transform_buffer_incremental(&TELEX).with_abbreviations(table);
+    for ch in input.chars() {
+        let _ = buffer.push(ch);
+    }
+    let result = buffer.finish();
+    (buffer.view().to_owned(), result.abbreviation_expanded)
+}
+
+#[test]
+fn test_incremental_buffer_abbreviations() {
+    let table: AbbreviationTable = "vn = Việt Nam\nko = không\nđc = được".parse().unwrap();
+
+    assert_eq!(
+        finish_with_abbreviations(&table, "vn"),
+        ("Việt Nam".to_owned(), true)
+    );
+    assert_eq!(
+        finish_with_abbreviations(&table, "Vn"),
+        ("Việt Nam".to_owned(), true)
+    );
+    assert_eq!(
+        finish_with_abbreviations(&table, "VN"),
+        ("VIỆT NAM".to_owned(), true)
+    );
+    assert_eq!(
+        finish_with_abbreviations(&table, "Ko"),
+        ("Không".to_owned(), true)
+    );
+    // The transformed output is looked up when the keystrokes are not an abbreviation
+    assert_eq!(
+        finish_with_abbreviations(&table, "ddc"),
+        ("được".to_owned(), true)
+    );
+    assert_eq!(
+        finish_with_abbreviations(&table, "vns"),
+        ("vns".to_owned(), false)
+    );
+}
+
+#[test]
+fn test_incremental_buffer_abbreviation_disabled() {
+    let mut table = AbbreviationTable::new();
+    let _ = table.insert("ko", "không");
+    assert!(table.set_enabled("ko", false));
+
+    assert_eq!(
+        finish_with_abbreviations(&table, "ko"),
+        ("ko".to_owned(), false)
+    );
+}
+
+#[test]
+fn test_incremental_buffer_abbreviation_undo() {
+    let mut table = AbbreviationTable::new();
+    let _ = table.insert("ko", "không");
+
+    let mut buffer = transform_buffer_incremental(&TELEX).with_abbreviations(&table);
+    let _ = buffer.push('k');
+    let _ = buffer.push('o');
+    assert!(buffer.finish().abbreviation_expanded);
+    assert_eq!(buffer.view(), "không");
+
+    // Undoing the expansion keeps the keystrokes
+    assert!(buffer.undo_expansion());
+    assert_eq!(buffer.view(), "ko");
+    assert_eq!(buffer.input(), &['k', 'o']);
+    assert!(!buffer.result().abbreviation_expanded);
+    assert!(!buffer.undo_expansion());
+
+    // Typing continues from the abbreviation
+    assert!(buffer.finish().abbreviation_expanded);
+    let _ = buffer.push('o');
+    assert_eq!(buffer.view(), "kô");
+    assert!(!buffer.finish().abbreviation_expanded);
+}
+
+#[test]
+fn test_incremental_buffer_abbreviation_backspace() {
+    let mut table = AbbreviationTable::new();
+    let _ = table.insert("ko", "không");
+
+    let mut buffer = transform_buffer_incremental(&TELEX).with_abbreviations(&table);
+    let _ = buffer.push('k');
+    let _ = buffer.push('o');
+    let _ = buffer.finish();
+    // Backspacing removes from the abbreviation, not from its expansion
+    assert_eq!(buffer.backspace(BackspaceMode::Character), Some('o'));
+    assert_eq!(buffer.view(), "k");
+    assert_eq!(buffer.input(), &['k']);
+
+    let _ = buffer.push('o');
+    let _ = buffer.finish();
+    assert_eq!(buffer.pop(), Some('o'));
+    assert_eq!(buffer.view(), "k");
+    assert!(!buffer.result().abbreviation_expanded);
+}
+
+#[test]
+fn test_incremental_buffer_strict_validation() {
+    let type_word = |input: &str, mode: ValidationMode| {
+        let mut buffer = transform_buffer_incremental(&TELEX).with_validation_mode(mode);
+        for ch in input.chars() {
+            let _ = buffer.push(ch);
+        }
+        buffer.view().to_owned()
+    };
+
+    let cases = [
+        // Stop finals only take the acute or underdot tone mark
+        ("cacs", "các", "các"),
+        ("hopj", "họp", "họp"),
+        ("cacf", "càc", "cacf"),
+        ("hopr", "hỏp", "hopr"),
+        ("tuowcx", "tưỡc", "tươcx"),
+        // The tone mark comes last, so a syllable without one is not checked yet
+        ("tuocws", "tước", "tước"),
+        ("vieetj", "việt", "việt"),
+        // Spelling of the initial consonants before front vowels
+        ("cees", "cế", "cês"),
+        ("ghis", "ghí", "ghí"),
+        ("ghuf", "ghù", "ghuf"),
+        ("kyf", "kỳ", "kỳ"),
+    ];
+    for (input, basic, strict) in cases {
+        assert_eq!(type_word(input, ValidationMode::Basic), basic, "{input}");
+        assert_eq!(type_word(input, ValidationMode::Strict), strict, "{input}");
+    }
+}
+
+#[test]
+fn test_incremental_buffer_all_caps() {
+    let type_word = |input: &str| {
+        let mut buffer = transform_buffer_incremental(&TELEX_EXTENDED);
+        for ch in input.chars() {
+            let _ = buffer.push(ch);
+        }
+        buffer.view().to_owned()
+    };
+
+    let cases = [
+        ("vieetj", "VIỆT"),
+        ("dduwowngf", "ĐƯỜNG"),
+        ("ddawcj", "ĐẶC"),
+        ("tr[", "TRƠ"),
+        ("ng]", "NGƯ"),
+    ];
+    for (input, expected) in cases {
+        assert_eq!(type_word(&input.to_uppercase()), expected, "{input}");
+        assert_eq!(vi::case::to_upper(&type_word(input)), expected, "{input}");
+    }
+    // A single capital letter starts a capitalized word, not an all caps one
+    assert_eq!(type_word("T["), "Tơ");
+
+    let mut buffer = transform_buffer_incremental(&TELEX);
+    for ch in "GIW".chars() {
+        let _ = buffer.push(ch);
+    }
+    assert_eq!(buffer.view(), "GIƯ");
+}
+
+#[test]
+fn test_incremental_buffer_trailing_escape() {
+    let mut buffer = IncrementalBuffer::new(&VIQR);
+    for ch in "a\\".chars() {
+        let _ = buffer.push(ch);
+    }
+    assert_eq!(buffer.view(), "a");
+    let _ = buffer.finish();
+    assert_eq!(buffer.view(), "a\\");
+
+    // An escape character followed by another character is never inserted
+    buffer.clear();
+    for ch in "a\\.".chars() {
+        let _ = buffer.push(ch);
+    }
+    let _ = buffer.finish();
+    assert_eq!(buffer.view(), "a.");
+}
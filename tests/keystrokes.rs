@@ -0,0 +1,135 @@
+use vi::{
+    keymap::OwnedDefinition,
+    keystrokes::{to_keystrokes, word_to_keystrokes, KeystrokeError, KeystrokeMode},
+    processor::ToneMark,
+    transform_buffer,
+    validation::is_valid_syllable,
+    Action, Definition, TELEX, VIQR, VNI,
+};
+
+fn transform(definition: &Definition, keystrokes: &str) -> String {
+    let mut output = String::new();
+    transform_buffer(definition, keystrokes.chars(), &mut output);
+    output
+}
+
+fn vietnamese_words() -> Vec<String> {
+    include_str!("../testdata/input/simple_telex.txt")
+        .split_whitespace()
+        .map(|keystrokes| transform(&TELEX, keystrokes))
+        .filter(|word| is_valid_syllable(word))
+        .collect()
+}
+
+#[test]
+fn round_trip_all_definitions() {
+    let words = vietnamese_words();
+    assert!(words.len() > 50);
+
+    for definition in [&TELEX, &VNI, &VIQR] {
+        for mode in [KeystrokeMode::Canonical, KeystrokeMode::Minimal] {
+            for word in &words {
+                let keystrokes = word_to_keystrokes(definition, word, mode).unwrap();
+                assert_eq!(transform(definition, &keystrokes), *word, "{keystrokes}");
+            }
+        }
+    }
+}
+
+#[test]
+fn minimal_is_never_longer_than_canonical() {
+    for definition in [&TELEX, &VNI, &VIQR] {
+        for word in vietnamese_words() {
+            let canonical =
+                word_to_keystrokes(definition, &word, KeystrokeMode::Canonical).unwrap();
+            let minimal = word_to_keystrokes(definition, &word, KeystrokeMode::Minimal).unwrap();
+            assert!(minimal.len() <= canonical.len(), "{minimal} {canonical}");
+        }
+    }
+}
+
+#[test]
+fn canonical_keystrokes() {
+    let cases = [
+        ("việt", "vieetj", "vie6t5", "vie^t."),
+        ("đường", "dduwowngf", "d9u7o7ng2", "ddu+o+ng`"),
+        ("Tiếng", "Tieengs", "Tie6ng1", "Tie^ng'"),
+        ("ĂN", "AWN", "A8N", "A(N"),
+        ("hoa", "hoa", "hoa", "hoa"),
+    ];
+    for (word, telex, vni, viqr) in cases {
+        assert_eq!(
+            word_to_keystrokes(&TELEX, word, KeystrokeMode::Canonical).unwrap(),
+            telex
+        );
+        assert_eq!(
+            word_to_keystrokes(&VNI, word, KeystrokeMode::Canonical).unwrap(),
+            vni
+        );
+        assert_eq!(
+            word_to_keystrokes(&VIQR, word, KeystrokeMode::Canonical).unwrap(),
+            viqr
+        );
+    }
+}
+
+#[test]
+fn minimal_keystrokes() {
+    let cases = [
+        ("việt", "vietej", "viet65"),
+        ("người", "ngwoif", "nguoi72"),
+        ("ư", "w", "u7"),
+    ];
+    for (word, telex, vni) in cases {
+        assert_eq!(
+            word_to_keystrokes(&TELEX, word, KeystrokeMode::Minimal).unwrap(),
+            telex
+        );
+        assert_eq!(
+            word_to_keystrokes(&VNI, word, KeystrokeMode::Minimal).unwrap(),
+            vni
+        );
+    }
+}
+
+#[test]
+fn text_keystrokes() {
+    let text = "Xin chào, tôi là người Việt Nam.";
+    assert_eq!(
+        to_keystrokes(&TELEX, text, KeystrokeMode::Canonical).unwrap(),
+        "Xin chaof, tooi laf nguwowif Vieetj Nam."
+    );
+    assert_eq!(
+        to_keystrokes(&VNI, text, KeystrokeMode::Canonical).unwrap(),
+        "Xin chao2, to6i la2 ngu7o7i2 Vie6t5 Nam."
+    );
+    // Keys between words are escaped
+    assert_eq!(
+        to_keystrokes(&VIQR, text, KeystrokeMode::Canonical).unwrap(),
+        "Xin chao`, to^i la` ngu+o+i` Vie^t. Nam\\."
+    );
+}
+
+#[test]
+fn keystroke_errors() {
+    let mut definition = OwnedDefinition::new();
+    let _ = definition.insert('s', vec![Action::AddTonemark(ToneMark::Acute)]);
+
+    assert_eq!(
+        to_keystrokes(&definition, "cá", KeystrokeMode::Canonical),
+        Ok("cas".to_owned())
+    );
+    assert_eq!(
+        to_keystrokes(&definition, "cà", KeystrokeMode::Canonical),
+        Err(KeystrokeError::MissingKey {
+            word: "cà".to_owned(),
+            action: Action::AddTonemark(ToneMark::Grave),
+        })
+    );
+    assert_eq!(
+        word_to_keystrokes(&definition, "as", KeystrokeMode::Minimal),
+        Err(KeystrokeError::Untypeable {
+            word: "as".to_owned(),
+        })
+    );
+}
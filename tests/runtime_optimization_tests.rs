@@ -1,8 +1,15 @@
 //! Integration tests for runtime optimization selection and production API
 
+use std::borrow::Cow;
+
+use vi::optimization_selector::{
+    AppleSiliconProcessor, GenericArm64Processor, OptimizationProfile, RustOptimizedProcessor,
+    RustSimdProcessor, RustStandardProcessor, X86_64Processor,
+};
 use vi::{
     AssemblyError, CpuArchitecture, CpuCapabilities, OptimizationPreference, OptimizationSelector,
-    OptimizationStrategy, PerformanceTier, ProcessorBuilder, VietnameseTextProcessor,
+    OptimizationStrategy, PerformanceTier, ProcessorBuilder, VietnameseProcessor,
+    VietnameseTextProcessor,
 };
 
 #[test]
@@ -75,13 +82,14 @@ fn test_optimization_selector() {
         OptimizationStrategy::AppleSiliconAssembly
             | OptimizationStrategy::GenericArm64Assembly
             | OptimizationStrategy::X86_64Assembly
+            | OptimizationStrategy::RustSimd
             | OptimizationStrategy::RustOptimized
             | OptimizationStrategy::RustStandard
     ));
 
     // Should have profiles for all strategies
     let profiles = selector.profiles();
-    assert_eq!(profiles.len(), 5);
+    assert_eq!(profiles.len(), 6);
 
     // At least one profile should be available (Rust implementations)
     let available_count = profiles.iter().filter(|p| p.available).count();
@@ -194,6 +202,120 @@ fn test_string_processing() -> Result<(), AssemblyError> {
     Ok(())
 }
 
+/// Every processor available on this machine
+fn available_processors() -> Result<Vec<Box<dyn VietnameseProcessor>>, AssemblyError> {
+    let mut processors: Vec<Box<dyn VietnameseProcessor>> = vec![
+        Box::new(RustSimdProcessor::new()?),
+        Box::new(RustOptimizedProcessor::new()),
+        Box::new(RustStandardProcessor::new()),
+    ];
+    if let Ok(processor) = X86_64Processor::new() {
+        processors.push(Box::new(processor));
+    }
+    if let Ok(processor) = GenericArm64Processor::new() {
+        processors.push(Box::new(processor));
+    }
+    if let Ok(processor) = AppleSiliconProcessor::new() {
+        processors.push(Box::new(processor));
+    }
+    Ok(processors)
+}
+
+#[test]
+fn test_borrowed_and_in_place_processing() -> Result<(), AssemblyError> {
+    for processor in &available_processors()? {
+        let name = processor.processor_name();
+        assert!(
+            matches!(processor.process_cow("Hello World")?, Cow::Borrowed(_)),
+            "{name}"
+        );
+        assert_eq!(
+            processor.process_cow("Chúc mừng năm mới")?,
+            "Chuc mung nam moi"
+        );
+
+        let mut text = String::from("Hẹn gặp lại");
+        processor.process_in_place(&mut text)?;
+        assert_eq!(text, "Hen gap lai", "{name}");
+
+        let mut buffer = "Cảm ơn bạn".as_bytes().to_vec();
+        let len = processor.process_bytes_in_place(&mut buffer)?;
+        assert_eq!(buffer.get(..len), Some(&b"Cam on ban"[..]), "{name}");
+
+        let mut invalid = *b"\xFF";
+        assert!(matches!(
+            processor.process_bytes_in_place(&mut invalid),
+            Err(AssemblyError::InvalidInput)
+        ));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_decomposed_processing() -> Result<(), AssemblyError> {
+    let input = "Vie\u{323}\u{302}t Nam, Tie\u{302}\u{301}ng Vie\u{323}\u{302}t, x\u{301}";
+    let expected = vi::clean_string(input);
+    assert_eq!(expected, "Viet Nam, Tieng Viet, x\u{301}");
+
+    for processor in &available_processors()? {
+        let name = processor.processor_name();
+        assert_eq!(processor.process_string(input)?, expected, "{name}");
+        assert_eq!(processor.process_cow(input)?, expected, "{name}");
+
+        let mut text = String::from(input);
+        processor.process_in_place(&mut text)?;
+        assert_eq!(text, expected, "{name}");
+
+        let mut buffer = input.as_bytes().to_vec();
+        let len = processor.process_bytes_in_place(&mut buffer)?;
+        assert_eq!(buffer.get(..len), Some(expected.as_bytes()), "{name}");
+    }
+
+    Ok(())
+}
+
+/// A processor that only implements the required methods, failing on texts with an `x`.
+struct MinimalProcessor(OptimizationProfile);
+
+impl VietnameseProcessor for MinimalProcessor {
+    fn process_char(&self, ch: char) -> Result<char, AssemblyError> {
+        Ok(vi::clean_char(ch))
+    }
+
+    fn process_string(&self, input: &str) -> Result<String, AssemblyError> {
+        if input.contains('x') {
+            return Err(AssemblyError::InvalidInput);
+        }
+        Ok(vi::clean_string(input))
+    }
+
+    fn processor_name(&self) -> &'static str {
+        "Minimal"
+    }
+
+    fn performance_info(&self) -> &OptimizationProfile {
+        &self.0
+    }
+}
+
+#[test]
+fn test_default_in_place_processing() -> Result<(), AssemblyError> {
+    let profile = RustStandardProcessor::new().performance_info().clone();
+    let processor = MinimalProcessor(profile);
+
+    let mut text = String::from("Hẹn gặp lại");
+    processor.process_in_place(&mut text)?;
+    assert_eq!(text, "Hen gap lai");
+
+    // The text is kept when processing fails
+    let mut text = String::from("Xin chào, xin lỗi");
+    assert!(processor.process_in_place(&mut text).is_err());
+    assert_eq!(text, "Xin chào, xin lỗi");
+
+    Ok(())
+}
+
 #[test]
 fn test_performance_monitoring() -> Result<(), AssemblyError> {
     let mut processor = ProcessorBuilder::new().with_monitoring(true).build()?;
@@ -329,7 +451,9 @@ fn test_force_rust_only() -> Result<(), AssemblyError> {
     let strategy = processor.selected_strategy();
     assert!(matches!(
         strategy,
-        OptimizationStrategy::RustOptimized | OptimizationStrategy::RustStandard
+        OptimizationStrategy::RustSimd
+            | OptimizationStrategy::RustOptimized
+            | OptimizationStrategy::RustStandard
     ));
 
     // Verify preference is set correctly
@@ -387,6 +511,43 @@ fn test_specific_strategy_selection() -> Result<(), AssemblyError> {
     Ok(())
 }
 
+#[test]
+fn test_rust_simd_strategy() -> Result<(), AssemblyError> {
+    let available = OptimizationSelector::get()
+        .profiles()
+        .iter()
+        .any(|p| p.strategy == OptimizationStrategy::RustSimd && p.available);
+    if !available {
+        return Ok(());
+    }
+
+    let mut processor = ProcessorBuilder::new()
+        .with_strategy(OptimizationStrategy::RustSimd)
+        .build()?;
+    assert_eq!(
+        processor.selected_strategy(),
+        OptimizationStrategy::RustSimd
+    );
+
+    // Long runs of ASCII between Vietnamese letters, and letters followed by combining marks
+    let input = format!(
+        "{}Tiếng Việt{}Vie\u{323}\u{302}t{}",
+        "a".repeat(31),
+        " ".repeat(40),
+        "x".repeat(17)
+    );
+    assert_eq!(processor.process_string(&input)?, vi::clean_string(&input));
+
+    // Rust-only processing uses SIMD when it's available
+    let processor = ProcessorBuilder::new().force_rust_only().build()?;
+    assert_eq!(
+        processor.selected_strategy(),
+        OptimizationStrategy::RustSimd
+    );
+
+    Ok(())
+}
+
 #[test]
 fn test_unavailable_strategy_error() {
     // Try to force a strategy that might not be available
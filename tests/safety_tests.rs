@@ -53,6 +53,32 @@ fn test_vietnamese_character_processing() {
     }
 }
 
+#[test]
+fn test_utf8_bulk_processing() {
+    setup_safety();
+    let processor = SafeAssemblyProcessor::new();
+
+    // Letters of every UTF-8 length, around the 16-byte ASCII blocks of the kernels
+    let text = "Đắk Lắk, Thừa Thiên Huế & Hải Phòng: ƠƯ ơư Ỹ ỹ ÿ ẞ 日本 😀 ";
+    for ascii_len in [0, 1, 15, 16, 17, 33] {
+        let input = format!("{}{}", "a".repeat(ascii_len), text.repeat(3));
+        let mut bytes = input.clone().into_bytes();
+        let written = processor.process_bytes_safe(&mut bytes).unwrap();
+        assert_eq!(
+            bytes.get(..written),
+            Some(vi::clean_string(&input).as_bytes()),
+            "Failed for input: {input}"
+        );
+    }
+
+    // The input must be valid UTF-8
+    let mut invalid = *b"Vi\xE1\xBBt";
+    assert!(matches!(
+        processor.process_bytes_safe(&mut invalid),
+        Err(AssemblyError::InvalidInput)
+    ));
+}
+
 #[test]
 fn test_empty_input_handling() {
     setup_safety();
@@ -1,5 +1,6 @@
 use serde::Serialize;
 use std::fmt::Write;
+use vi::methods::{IncrementalBuffer, UndoPolicy};
 
 #[derive(Serialize)]
 #[allow(dead_code)]
@@ -40,3 +41,27 @@ where
     }
     result
 }
+
+/// Checks the output of each input under the [`UndoPolicy::LITERAL`], [`UndoPolicy::TOGGLE`] and
+/// [`UndoPolicy::LAST_ACTION_ONLY`] presets, in that order.
+///
+/// # Panics
+///
+/// Panics if an output doesn't match.
+#[allow(dead_code)]
+pub fn assert_undo_matrix(definition: &vi::Definition, matrix: &[(&str, [&str; 3])]) {
+    let policies = [
+        UndoPolicy::LITERAL,
+        UndoPolicy::TOGGLE,
+        UndoPolicy::LAST_ACTION_ONLY,
+    ];
+    for (input, expected) in matrix {
+        for (policy, expected) in policies.iter().zip(expected) {
+            let mut buffer = IncrementalBuffer::new(definition).with_undo_policy(*policy);
+            for ch in input.chars() {
+                let _ = buffer.push(ch);
+            }
+            assert_eq!(buffer.view(), *expected, "{input} with {policy:?}");
+        }
+    }
+}
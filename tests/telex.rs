@@ -8,6 +8,14 @@ fn snapshot_transform(lines: &str) -> String {
     })
 }
 
+fn snapshot_transform_extended(lines: &str) -> String {
+    shared::transform_lines(lines, |word| {
+        let mut trasformed_word = String::new();
+        vi::transform_buffer(&vi::TELEX_EXTENDED, word.chars(), &mut trasformed_word);
+        trasformed_word
+    })
+}
+
 macro_rules! gen_test_telex {
     ($name:tt, $path: tt) => {
         gen_test!(snapshot_transform, $name, $path);
@@ -18,3 +26,72 @@ gen_test_telex!(
     non_vietnamese_telex,
     "../testdata/input/non_vietnamese_telex.txt"
 );
+gen_test!(
+    snapshot_transform_extended,
+    extended_telex,
+    "../testdata/input/extended_telex.txt"
+);
+
+#[test]
+fn undo_policy_tone_mark() {
+    shared::assert_undo_matrix(
+        &vi::TELEX,
+        &[
+            ("cas", ["cá", "cá", "cá"]),
+            ("cass", ["cas", "cas", "cas"]),
+            ("casss", ["cass", "cá", "cass"]),
+            ("cassss", ["casss", "cas", "casss"]),
+            ("casse", ["case", "case", "case"]),
+            ("casf", ["cà", "cà", "cà"]),
+            ("casts", ["cats", "cats", "cáts"]),
+            ("toasns", ["toans", "toans", "toáns"]),
+            ("toasnss", ["toanss", "toán", "toánss"]),
+            ("caszs", ["cá", "cá", "cá"]),
+        ],
+    );
+}
+
+#[test]
+fn undo_policy_letter_modification() {
+    shared::assert_undo_matrix(
+        &vi::TELEX,
+        &[
+            ("aa", ["â", "â", "â"]),
+            ("aaa", ["aa", "aa", "aa"]),
+            ("aaaa", ["aaa", "â", "aaa"]),
+            ("aaaaa", ["aaaa", "aa", "aaaa"]),
+            ("aw", ["ă", "ă", "ă"]),
+            ("aww", ["aw", "aw", "aw"]),
+            ("awww", ["aww", "ă", "aww"]),
+            ("ow", ["ơ", "ơ", "ơ"]),
+            ("oww", ["ow", "ow", "ow"]),
+            ("owww", ["oww", "ơ", "oww"]),
+            ("uw", ["ư", "ư", "ư"]),
+            ("uwww", ["uww", "ư", "uww"]),
+            ("dd", ["đ", "đ", "đ"]),
+            ("ddd", ["dd", "dd", "dd"]),
+            ("dddd", ["ddd", "đ", "ddd"]),
+        ],
+    );
+}
+
+#[test]
+fn undo_policy_letter_insertion() {
+    shared::assert_undo_matrix(
+        &vi::TELEX,
+        &[
+            ("w", ["ư", "ư", "ư"]),
+            ("ww", ["w", "w", "w"]),
+            ("www", ["ww", "ư", "ww"]),
+            ("wwww", ["www", "w", "www"]),
+        ],
+    );
+    shared::assert_undo_matrix(
+        &vi::TELEX_EXTENDED,
+        &[
+            ("n]", ["nư", "nư", "nư"]),
+            ("n]]", ["n]", "n]", "n]"]),
+            ("n]]]", ["n]]", "nư", "n]]"]),
+        ],
+    );
+}
@@ -0,0 +1,20 @@
+mod shared;
+
+fn snapshot_transform(lines: &str) -> String {
+    shared::transform_lines(lines, |word| {
+        let mut trasformed_word = String::new();
+        vi::transform_buffer(&vi::VIQR, word.chars(), &mut trasformed_word);
+        trasformed_word
+    })
+}
+
+macro_rules! gen_test_viqr {
+    ($name:tt, $path: tt) => {
+        gen_test!(snapshot_transform, $name, $path);
+    };
+}
+gen_test_viqr!(simple_viqr, "../testdata/input/simple_viqr.txt");
+gen_test_viqr!(
+    non_vietnamese_viqr,
+    "../testdata/input/non_vietnamese_viqr.txt"
+);
@@ -18,3 +18,41 @@ gen_test_vi!(
     non_vietnamese_vni,
     "../testdata/input/non_vietnamese_vni.txt"
 );
+
+#[test]
+fn undo_policy_tone_mark() {
+    shared::assert_undo_matrix(
+        &vi::VNI,
+        &[
+            ("ca1", ["cá", "cá", "cá"]),
+            ("ca11", ["ca1", "ca1", "ca1"]),
+            ("ca111", ["ca11", "cá", "ca11"]),
+            ("ca1111", ["ca111", "ca1", "ca111"]),
+            ("ca12", ["cà", "cà", "cà"]),
+            ("ca1t1", ["cat1", "cat1", "cát1"]),
+            ("toa1n1", ["toan1", "toan1", "toán1"]),
+            ("ca101", ["cá", "cá", "cá"]),
+        ],
+    );
+}
+
+#[test]
+fn undo_policy_letter_modification() {
+    shared::assert_undo_matrix(
+        &vi::VNI,
+        &[
+            ("a6", ["â", "â", "â"]),
+            ("a66", ["a6", "a6", "a6"]),
+            ("a666", ["a66", "â", "a66"]),
+            ("a8", ["ă", "ă", "ă"]),
+            ("a888", ["a88", "ă", "a88"]),
+            ("o7", ["ơ", "ơ", "ơ"]),
+            ("o77", ["o7", "o7", "o7"]),
+            ("o777", ["o77", "ơ", "o77"]),
+            ("d9", ["đ", "đ", "đ"]),
+            ("d99", ["d9", "d9", "d9"]),
+            ("d999", ["d99", "đ", "d99"]),
+            ("to6t6", ["tot6", "tot6", "tôt6"]),
+        ],
+    );
+}